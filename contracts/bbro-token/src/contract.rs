@@ -1,6 +1,8 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult};
+use cosmwasm_std::{
+    attr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Uint128,
+};
 
 use cw20_base::{
     allowances::{
@@ -13,6 +15,7 @@ use cw20_base::{
         query as cw20_query,
     },
     msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    state::{BALANCES, TOKEN_INFO},
     ContractError,
 };
 
@@ -53,7 +56,8 @@ pub fn instantiate(
 ///
 /// ## Messages
 ///
-/// Proxy for cw20-base contract ExecuteMsg
+/// Proxy for cw20-base contract ExecuteMsg.
+/// Minter is allowed to burn tokens with BurnFrom without allowance
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -73,7 +77,7 @@ pub fn execute(
             expires,
         } => cw20_decrease_allowance(deps, env, info, spender, amount, expires),
         ExecuteMsg::Mint { recipient, amount } => cw20_mint(deps, env, info, recipient, amount),
-        ExecuteMsg::BurnFrom { owner, amount } => cw20_burn_from(deps, env, info, owner, amount),
+        ExecuteMsg::BurnFrom { owner, amount } => burn_from(deps, env, info, owner, amount),
         ExecuteMsg::UpdateMarketing {
             project,
             description,
@@ -84,6 +88,54 @@ pub fn execute(
     }
 }
 
+/// ## Description
+/// Burns specified amount of tokens from owner balance.
+/// Minter burns tokens without allowance, any other sender must have enough allowance.
+/// Returns [`Response`] with specified attributes if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **owner** is a field of type [`String`].
+///
+/// * **amount** is an object of type [`Uint128`].
+fn burn_from(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let token_info = TOKEN_INFO.load(deps.storage)?;
+    if token_info.mint.map(|m| m.minter) != Some(info.sender.clone()) {
+        return cw20_burn_from(deps, env, info, owner, amount);
+    }
+
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    BALANCES.update(
+        deps.storage,
+        &owner_addr,
+        |balance: Option<Uint128>| -> StdResult<_> {
+            Ok(balance.unwrap_or_default().checked_sub(amount)?)
+        },
+    )?;
+    TOKEN_INFO.update(deps.storage, |mut meta| -> StdResult<_> {
+        meta.total_supply = meta.total_supply.checked_sub(amount)?;
+        Ok(meta)
+    })?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "burn_from"),
+        attr("from", owner),
+        attr("by", info.sender),
+        attr("amount", amount),
+    ]))
+}
+
 /// ## Description
 /// Available query messages of the contract
 /// ## Params
@@ -5,8 +5,9 @@ use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
 use services::{
     bonding::{
-        ClaimInfoResponse, ClaimsResponse, ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg,
-        MigrateMsg, QueryMsg, SimulateExchangeResponse, StateResponse,
+        BondMarketResponse, BondMarketsResponse, BonderResponse, BondersResponse,
+        BondingCapacityResponse, ClaimInfoResponse, ClaimsResponse, ConfigResponse, Cw20HookMsg,
+        ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, SimulateExchangeResponse, StateResponse,
     },
     ownership_proposal::OwnershipProposalResponse,
 };
@@ -28,4 +29,9 @@ fn main() {
     export_schema(&schema_for!(StateResponse), &out_dir);
     export_schema(&schema_for!(OwnershipProposalResponse), &out_dir);
     export_schema(&schema_for!(SimulateExchangeResponse), &out_dir);
+    export_schema(&schema_for!(BondMarketResponse), &out_dir);
+    export_schema(&schema_for!(BondMarketsResponse), &out_dir);
+    export_schema(&schema_for!(BondingCapacityResponse), &out_dir);
+    export_schema(&schema_for!(BonderResponse), &out_dir);
+    export_schema(&schema_for!(BondersResponse), &out_dir);
 }
@@ -1,25 +1,40 @@
 use cosmwasm_std::{
-    to_binary, Attribute, CanonicalAddr, CosmosMsg, Decimal, DepsMut, Env, MessageInfo, Response,
-    Uint128, WasmMsg,
+    to_binary, Addr, Attribute, CanonicalAddr, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo,
+    Response, StdResult, Uint128, WasmMsg,
 };
 use cw20::{Cw20ExecuteMsg, Expiration};
 
-use astroport::asset::AssetInfo;
+use astroport::{
+    asset::{Asset, AssetInfo},
+    generator::{Cw20HookMsg as GeneratorHookMsg, ExecuteMsg as GeneratorExecuteMsg},
+    querier::query_pair_info,
+};
 
 use crate::{
     error::ContractError,
     state::{
-        load_claims, load_config, load_state, store_claims, store_config, store_state, BondType,
-        BondingMode, ClaimInfo,
+        load_bond_market, load_claims, load_config, load_state, record_bonder_bond,
+        record_bonder_claim, record_epoch_bond, register_bond_market as register_market,
+        store_bond_market, store_claims, store_config, store_state, BondLock, BondMarket, BondType,
+        BondingLimits, BondingMode, ClaimInfo, Config, LpPair, LpStaking, PriceSource,
+    },
+    utils::{
+        apply_discount, assert_slippage, compute_discount, extract_native_token,
+        get_share_in_assets, query_bond_lock_discount, query_bond_market_bro_amount,
+        query_current_epoch,
     },
-    utils::{apply_discount, extract_native_token, get_share_in_assets},
 };
 
 use services::{
+    bonding::{
+        BondLockMsg, BondMarketAssetMsg, BondingLimitsMsg, DiscountControl, EpochLimits,
+        LpStakingMsg, PriceSourceMsg,
+    },
     oracle::ExecuteMsg as OracleExecuteMsg,
     querier::{
-        query_bro_ust_pair, query_is_oracle_ready_to_trigger, query_oracle_price,
-        query_staking_config,
+        query_bro_ust_pair, query_generator_deposit, query_generator_pending_token,
+        query_generator_reward_info, query_is_oracle_ready_to_trigger, query_oracle_price,
+        query_staking_config, query_token_balance,
     },
     staking::Cw20HookMsg as StakingHookMsg,
 };
@@ -78,14 +93,21 @@ pub fn distribute_reward(deps: DepsMut, amount: Uint128) -> Result<Response, Con
 /// * **lp_bonding_discount** is an object of type [`Decimal`]
 ///
 /// * **vesting_period_blocks** is a field of type [`u64`]
+///
+/// * **min_bro_payout** is an [`Option`] of type [`Uint128`]
+///
+/// * **lock_epochs** is an [`Option`] of type [`u64`]
+#[allow(clippy::too_many_arguments)]
 pub fn lp_bond(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     sender_raw: CanonicalAddr,
     lp_amount: Uint128,
     lp_token: CanonicalAddr,
     lp_bonding_discount: Decimal,
     vesting_period_blocks: u64,
+    min_bro_payout: Option<Uint128>,
+    lock_epochs: Option<u64>,
 ) -> Result<Response, ContractError> {
     let config = load_config(deps.storage)?;
     let mut state = load_state(deps.storage)?;
@@ -117,37 +139,80 @@ pub fn lp_bond(
         .amount,
     )?;
 
-    let bro_payout = apply_discount(lp_bonding_discount, bro_amount)?;
+    state.decay_bonding_debt(&config.discount_control, env.block.height);
+    let mut discount = compute_discount(
+        lp_bonding_discount,
+        &config.discount_control,
+        state.lp_bonding_debt,
+        state.lp_bonding_balance,
+    )?;
+
+    let bond_lock = match lock_epochs {
+        Some(lock_epochs) => {
+            let (staking_contract, lock_discount) =
+                query_bond_lock_discount(&deps.querier, deps.api, &config.bond_lock, lock_epochs)?;
+            discount = discount + lock_discount;
+            Some((staking_contract, lock_epochs))
+        }
+        None => None,
+    };
+
+    let bro_payout = apply_discount(discount, bro_amount)?;
     if bro_payout < config.min_bro_payout {
         return Err(ContractError::BondPayoutIsTooLow {});
     }
 
+    assert_slippage(bro_payout, min_bro_payout)?;
+
     if bro_payout > state.lp_bonding_balance {
         return Err(ContractError::NotEnoughForBondPayout {});
     }
 
+    apply_bonding_limits(
+        deps.branch(),
+        &config,
+        env.block.height,
+        &sender_raw,
+        &BondType::LpBond,
+        bro_payout,
+    )?;
+
     state.lp_bonding_balance = state.lp_bonding_balance.checked_sub(bro_payout)?;
+    state.lp_bonding_debt = state.lp_bonding_debt.checked_add(bro_payout)?;
+    state.record_bond(&BondType::LpBond, lp_amount, bro_payout)?;
     store_state(deps.storage, &state)?;
 
-    let mut claims = load_claims(deps.storage, &sender_raw)?;
-    claims.push(ClaimInfo {
-        bond_type: BondType::LpBond,
-        amount: bro_payout,
-        claimable_at: Expiration::AtHeight(env.block.height + vesting_period_blocks),
-    });
-
-    store_claims(deps.storage, &sender_raw, &claims)?;
+    record_bonder_bond(
+        deps.storage,
+        &sender_raw,
+        &BondType::LpBond,
+        lp_amount,
+        bro_payout,
+    )?;
 
-    let mut msgs: Vec<CosmosMsg> = vec![CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: deps.api.addr_humanize(&lp_token)?.to_string(),
-        funds: vec![],
-        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+    // stake bonded lp tokens in astroport generator if lp staking is enabled
+    let lp_msg = match &config.lp_staking {
+        Some(lp_staking) => Cw20ExecuteMsg::Send {
+            contract: deps
+                .api
+                .addr_humanize(&lp_staking.generator_contract)?
+                .to_string(),
+            amount: lp_amount,
+            msg: to_binary(&GeneratorHookMsg::Deposit {})?,
+        },
+        None => Cw20ExecuteMsg::Transfer {
             recipient: deps
                 .api
                 .addr_humanize(&config.treasury_contract)?
                 .to_string(),
             amount: lp_amount,
-        })?,
+        },
+    };
+
+    let mut msgs: Vec<CosmosMsg> = vec![CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: deps.api.addr_humanize(&lp_token)?.to_string(),
+        funds: vec![],
+        msg: to_binary(&lp_msg)?,
     })];
 
     let oracle_can_be_updated =
@@ -160,12 +225,43 @@ pub fn lp_bond(
         }))
     }
 
-    Ok(Response::new().add_messages(msgs).add_attributes(vec![
-        ("action", "lp_bond"),
-        ("sender", &deps.api.addr_humanize(&sender_raw)?.to_string()),
-        ("lp_amount", &lp_amount.to_string()),
-        ("bro_payout", &bro_payout.to_string()),
-    ]))
+    let sender = deps.api.addr_humanize(&sender_raw)?;
+    match bond_lock {
+        Some((staking_contract, epochs_locked)) => msgs.push(bond_lock_msg(
+            deps.api.addr_humanize(&config.bro_token)?,
+            staking_contract,
+            sender.to_string(),
+            bro_payout,
+            epochs_locked,
+        )?),
+        None => {
+            let mut claims = load_claims(deps.storage, &sender_raw)?;
+            claims.push(ClaimInfo {
+                bond_type: BondType::LpBond,
+                amount: bro_payout,
+                claimable_at: Expiration::AtHeight(env.block.height + vesting_period_blocks),
+                vesting_start_block: Some(env.block.height),
+                claimed_amount: Uint128::zero(),
+            });
+
+            store_claims(deps.storage, &sender_raw, &claims)?;
+        }
+    }
+
+    let mut attributes = vec![
+        Attribute::new("action", "lp_bond"),
+        Attribute::new("sender", sender.to_string()),
+        Attribute::new("lp_amount", lp_amount.to_string()),
+        Attribute::new("bro_payout", bro_payout.to_string()),
+        Attribute::new("discount", discount.to_string()),
+    ];
+    if let Some(lock_epochs) = lock_epochs {
+        attributes.push(Attribute::new("lock_epochs", lock_epochs.to_string()));
+    }
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attributes(attributes))
 }
 
 /// ## Description
@@ -178,11 +274,21 @@ pub fn lp_bond(
 /// * **env** is an object of type [`Env`]
 ///
 /// * **info** is an object of type [`MessageInfo`]
-pub fn ust_bond(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+///
+/// * **min_bro_payout** is an [`Option`] of type [`Uint128`]
+///
+/// * **lock_epochs** is an [`Option`] of type [`u64`]
+pub fn ust_bond(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    min_bro_payout: Option<Uint128>,
+    lock_epochs: Option<u64>,
+) -> Result<Response, ContractError> {
     let config = load_config(deps.storage)?;
     let mut state = load_state(deps.storage)?;
 
-    let bond_asset = extract_native_token(&info.funds)?;
+    let bond_asset = extract_native_token(&info.funds, "uusd")?;
 
     let oracle_contract = deps.api.addr_humanize(&config.oracle_contract)?;
     let bro_amount = query_oracle_price(
@@ -193,18 +299,58 @@ pub fn ust_bond(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response,
     )?
     .amount;
 
-    let bro_payout = apply_discount(config.ust_bonding_discount, bro_amount)?;
+    state.decay_bonding_debt(&config.discount_control, env.block.height);
+    let mut discount = compute_discount(
+        config.ust_bonding_discount,
+        &config.discount_control,
+        state.ust_bonding_debt,
+        state.ust_bonding_balance,
+    )?;
+
+    let bond_lock = match lock_epochs {
+        Some(lock_epochs) => {
+            let (staking_contract, lock_discount) =
+                query_bond_lock_discount(&deps.querier, deps.api, &config.bond_lock, lock_epochs)?;
+            discount = discount + lock_discount;
+            Some((staking_contract, lock_epochs))
+        }
+        None => None,
+    };
+
+    let bro_payout = apply_discount(discount, bro_amount)?;
     if bro_payout < config.min_bro_payout {
         return Err(ContractError::BondPayoutIsTooLow {});
     }
 
+    assert_slippage(bro_payout, min_bro_payout)?;
+
     if bro_payout > state.ust_bonding_balance {
         return Err(ContractError::NotEnoughForBondPayout {});
     }
 
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    apply_bonding_limits(
+        deps.branch(),
+        &config,
+        env.block.height,
+        &sender_raw,
+        &BondType::UstBond,
+        bro_payout,
+    )?;
+
     state.ust_bonding_balance = state.ust_bonding_balance.checked_sub(bro_payout)?;
+    state.ust_bonding_debt = state.ust_bonding_debt.checked_add(bro_payout)?;
+    state.record_bond(&BondType::UstBond, bond_asset.amount, bro_payout)?;
     store_state(deps.storage, &state)?;
 
+    record_bonder_bond(
+        deps.storage,
+        &sender_raw,
+        &BondType::UstBond,
+        bond_asset.amount,
+        bro_payout,
+    )?;
+
     let mut msgs: Vec<CosmosMsg> = vec![bond_asset.into_msg(
         &deps.querier,
         deps.api.addr_humanize(&config.treasury_contract)?,
@@ -220,47 +366,281 @@ pub fn ust_bond(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response,
         }))
     }
 
-    match config.bonding_mode {
-        BondingMode::Normal {
-            vesting_period_blocks,
-            ..
-        } => {
-            let sender_raw = deps.api.addr_canonicalize(&info.sender.to_string())?;
+    let bro_token = deps.api.addr_humanize(&config.bro_token)?;
+    match (bond_lock, config.bonding_mode) {
+        (Some((staking_contract, epochs_locked)), _) => msgs.push(bond_lock_msg(
+            bro_token,
+            staking_contract,
+            info.sender.to_string(),
+            bro_payout,
+            epochs_locked,
+        )?),
+        (
+            None,
+            BondingMode::Normal {
+                vesting_period_blocks,
+                ..
+            },
+        ) => {
             let mut claims = load_claims(deps.storage, &sender_raw)?;
             claims.push(ClaimInfo {
                 bond_type: BondType::UstBond,
                 amount: bro_payout,
                 claimable_at: Expiration::AtHeight(env.block.height + vesting_period_blocks),
+                vesting_start_block: Some(env.block.height),
+                claimed_amount: Uint128::zero(),
             });
 
             store_claims(deps.storage, &sender_raw, &claims)?;
         }
-        BondingMode::Community {
-            staking_contract,
+        (
+            None,
+            BondingMode::Community {
+                staking_contract,
+                epochs_locked,
+            },
+        ) => msgs.push(bond_lock_msg(
+            bro_token,
+            deps.api.addr_humanize(&staking_contract)?,
+            info.sender.to_string(),
+            bro_payout,
             epochs_locked,
-        } => msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: deps.api.addr_humanize(&config.bro_token)?.to_string(),
-            funds: vec![],
-            msg: to_binary(&Cw20ExecuteMsg::Send {
-                contract: deps.api.addr_humanize(&staking_contract)?.to_string(),
-                amount: bro_payout,
-                msg: to_binary(&StakingHookMsg::CommunityBondLock {
-                    sender: info.sender.to_string(),
-                    epochs_locked,
-                })?,
-            })?,
-        })),
+        )?),
     };
 
+    let mut attributes = vec![
+        Attribute::new("action", "ust_bond"),
+        Attribute::new("sender", info.sender.to_string()),
+        Attribute::new("bro_payout", bro_payout.to_string()),
+        Attribute::new("discount", discount.to_string()),
+    ];
+    if let Some(lock_epochs) = lock_epochs {
+        attributes.push(Attribute::new("lock_epochs", lock_epochs.to_string()));
+    }
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attributes(attributes))
+}
+
+/// ## Description
+/// Returns message which sends bond payout to staking contract
+/// to be locked for specified amount of epochs on behalf of bonder
+/// ## Params
+/// * **bro_token** is an object of type [`Addr`]
+///
+/// * **staking_contract** is an object of type [`Addr`]
+///
+/// * **sender** is a field of type [`String`]
+///
+/// * **amount** is an object of type [`Uint128`]
+///
+/// * **epochs_locked** is a field of type [`u64`]
+fn bond_lock_msg(
+    bro_token: Addr,
+    staking_contract: Addr,
+    sender: String,
+    amount: Uint128,
+    epochs_locked: u64,
+) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: bro_token.to_string(),
+        funds: vec![],
+        msg: to_binary(&Cw20ExecuteMsg::Send {
+            contract: staking_contract.to_string(),
+            amount,
+            msg: to_binary(&StakingHookMsg::CommunityBondLock {
+                sender,
+                epochs_locked,
+            })?,
+        })?,
+    }))
+}
+
+/// ## Description
+/// Bond bro tokens by providing asset amount to specified bond market.
+/// Returns [`Response`] with specified attributes and messages if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **sender** is an object of type [`Addr`]
+///
+/// * **market_id** is a field of type [`u64`]
+///
+/// * **bond_asset** is an object of type [`Asset`]
+///
+/// * **min_bro_payout** is an [`Option`] of type [`Uint128`]
+pub fn bond(
+    mut deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    market_id: u64,
+    bond_asset: Asset,
+    min_bro_payout: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let config = load_config(deps.storage)?;
+    let mut market = load_bond_market(deps.storage, market_id)?;
+    if !market.enabled {
+        return Err(ContractError::BondMarketDisabled {});
+    }
+
+    let bro_amount = query_bond_market_bro_amount(
+        &deps.querier,
+        deps.api,
+        deps.api.addr_humanize(&config.astroport_factory)?,
+        deps.api.addr_humanize(&config.bro_token)?,
+        &market,
+        bond_asset.amount,
+    )?;
+
+    market.decay_debt(env.block.height);
+    let discount = compute_discount(
+        market.discount,
+        &market.discount_control,
+        market.debt,
+        market.capacity,
+    )?;
+
+    let bro_payout = apply_discount(discount, bro_amount)?;
+    if bro_payout < config.min_bro_payout {
+        return Err(ContractError::BondPayoutIsTooLow {});
+    }
+
+    assert_slippage(bro_payout, min_bro_payout)?;
+
+    if bro_payout > market.capacity {
+        return Err(ContractError::NotEnoughForBondPayout {});
+    }
+
+    let bond_type = BondType::MarketBond { market_id };
+    let sender_raw = deps.api.addr_canonicalize(sender.as_str())?;
+    apply_bonding_limits(
+        deps.branch(),
+        &config,
+        env.block.height,
+        &sender_raw,
+        &bond_type,
+        bro_payout,
+    )?;
+
+    market.capacity = market.capacity.checked_sub(bro_payout)?;
+    market.debt = market.debt.checked_add(bro_payout)?;
+    market.total_bonded = market.total_bonded.checked_add(bond_asset.amount)?;
+    store_bond_market(deps.storage, market_id, &market)?;
+
+    let mut state = load_state(deps.storage)?;
+    state.record_bond(&bond_type, bond_asset.amount, bro_payout)?;
+    store_state(deps.storage, &state)?;
+
+    record_bonder_bond(
+        deps.storage,
+        &sender_raw,
+        &bond_type,
+        bond_asset.amount,
+        bro_payout,
+    )?;
+
+    let mut claims = load_claims(deps.storage, &sender_raw)?;
+    claims.push(ClaimInfo {
+        bond_type,
+        amount: bro_payout,
+        claimable_at: Expiration::AtHeight(env.block.height + market.vesting_period_blocks),
+        vesting_start_block: Some(env.block.height),
+        claimed_amount: Uint128::zero(),
+    });
+
+    store_claims(deps.storage, &sender_raw, &claims)?;
+
+    let bond_amount = bond_asset.amount;
+    let mut msgs: Vec<CosmosMsg> = vec![bond_asset.into_msg(
+        &deps.querier,
+        deps.api.addr_humanize(&config.treasury_contract)?,
+    )?];
+
+    if let PriceSource::Oracle { oracle_contract } = market.price_source {
+        let oracle_contract = deps.api.addr_humanize(&oracle_contract)?;
+        let oracle_can_be_updated =
+            query_is_oracle_ready_to_trigger(&deps.querier, oracle_contract.clone())?;
+        if oracle_can_be_updated {
+            msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: oracle_contract.to_string(),
+                funds: vec![],
+                msg: to_binary(&OracleExecuteMsg::UpdatePrice {})?,
+            }))
+        }
+    }
+
     Ok(Response::new().add_messages(msgs).add_attributes(vec![
-        ("action", "ust_bond"),
-        ("sender", &info.sender.to_string()),
+        ("action", "bond"),
+        ("sender", sender.as_str()),
+        ("market_id", &market_id.to_string()),
+        ("bond_amount", &bond_amount.to_string()),
         ("bro_payout", &bro_payout.to_string()),
+        ("discount", &discount.to_string()),
     ]))
 }
 
 /// ## Description
-/// Claim available reward amount.
+/// Adds bro payout to amounts paid out in current epoch if bonding limits are configured.
+/// Returns [`ContractError`] if epoch capacity or address cap is exceeded
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **config** is an object of type [`Config`]
+///
+/// * **current_block** is a field of type [`u64`]
+///
+/// * **sender_raw** is an object of type [`CanonicalAddr`]
+///
+/// * **bond_type** is an object of type [`BondType`]
+///
+/// * **bro_payout** is an object of type [`Uint128`]
+fn apply_bonding_limits(
+    deps: DepsMut,
+    config: &Config,
+    current_block: u64,
+    sender_raw: &CanonicalAddr,
+    bond_type: &BondType,
+    bro_payout: Uint128,
+) -> Result<(), ContractError> {
+    let bonding_limits = match &config.bonding_limits {
+        Some(bonding_limits) => bonding_limits,
+        None => return Ok(()),
+    };
+
+    let limits = match bond_type {
+        BondType::UstBond => bonding_limits.ust_bond.clone(),
+        BondType::LpBond => bonding_limits.lp_bond.clone(),
+        BondType::MarketBond { market_id } => {
+            match load_bond_market(deps.storage, *market_id)?.epoch_limits {
+                Some(epoch_limits) => epoch_limits,
+                None => return Ok(()),
+            }
+        }
+    };
+
+    let (epoch, _) = query_current_epoch(
+        &deps.querier,
+        deps.api
+            .addr_humanize(&bonding_limits.epoch_manager_contract)?,
+        current_block,
+    )?;
+
+    record_epoch_bond(
+        deps.storage,
+        sender_raw,
+        bond_type,
+        &limits,
+        epoch,
+        bro_payout,
+    )
+}
+
+/// ## Description
+/// Claim vested amount of all bonder claims.
 /// Returns [`Response`] with specified attributes and messages if operation was successful,
 /// otherwise returns [`ContractError`]
 /// ## Params
@@ -275,24 +655,28 @@ pub fn claim(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, Con
     let sender_raw = deps.api.addr_canonicalize(&info.sender.to_string())?;
 
     let mut amount = Uint128::zero();
-    // if claim passed vesting period add claimable amount and remove it from claims list
-    let claims: Vec<ClaimInfo> = load_claims(deps.storage, &sender_raw)?
-        .into_iter()
-        .filter(|c| {
-            if c.claimable_at.is_expired(&env.block) {
-                amount += c.amount;
-                false
-            } else {
-                true
-            }
-        })
-        .collect();
+    let mut claims: Vec<ClaimInfo> = vec![];
+    // add vested amount of every claim and remove fully claimed ones from claims list
+    for mut claim in load_claims(deps.storage, &sender_raw)? {
+        let claimable_amount = claim.claimable_amount(&env.block)?;
+        amount = amount.checked_add(claimable_amount)?;
+        claim.claimed_amount = claim.claimed_amount.checked_add(claimable_amount)?;
+
+        if claim.claimed_amount < claim.amount {
+            claims.push(claim);
+        }
+    }
 
     if amount.is_zero() {
         return Err(ContractError::NothingToClaim {});
     }
 
     store_claims(deps.storage, &sender_raw, &claims)?;
+    record_bonder_claim(deps.storage, &sender_raw, amount)?;
+
+    let mut state = load_state(deps.storage)?;
+    state.total_bro_claimed = state.total_bro_claimed.checked_add(amount)?;
+    store_state(deps.storage, &state)?;
 
     Ok(Response::new()
         .add_messages(vec![CosmosMsg::Wasm(WasmMsg::Execute {
@@ -310,13 +694,219 @@ pub fn claim(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, Con
         ]))
 }
 
+/// ## Description
+/// Claims lp staking rewards from astroport generator and sends them to treasury.
+/// Returns [`Response`] with specified attributes and messages if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **env** is an object of type [`Env`]
+pub fn harvest_lp_rewards(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let config = load_config(deps.storage)?;
+
+    let (msgs, reward_amount, proxy_reward_amount) =
+        withdraw_lp_msgs(deps.as_ref(), &env, &config, Uint128::zero())?;
+    if reward_amount.is_zero() && proxy_reward_amount.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+
+    Ok(Response::new().add_messages(msgs).add_attributes(vec![
+        ("action", "harvest_lp_rewards"),
+        ("reward_amount", &reward_amount.to_string()),
+        ("proxy_reward_amount", &proxy_reward_amount.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Withdraws specified amount of staked lp tokens from astroport generator
+/// and sends them to treasury along with staking rewards.
+/// Returns [`Response`] with specified attributes and messages if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **amount** is an object of type [`Uint128`]
+pub fn unstake_lp(deps: DepsMut, env: Env, amount: Uint128) -> Result<Response, ContractError> {
+    let config = load_config(deps.storage)?;
+
+    let (msgs, reward_amount, proxy_reward_amount) =
+        withdraw_lp_msgs(deps.as_ref(), &env, &config, amount)?;
+
+    Ok(Response::new().add_messages(msgs).add_attributes(vec![
+        ("action", "unstake_lp"),
+        ("amount", &amount.to_string()),
+        ("reward_amount", &reward_amount.to_string()),
+        ("proxy_reward_amount", &proxy_reward_amount.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Returns messages which withdraw specified amount of lp tokens from astroport generator
+/// and send withdrawn lp tokens with staking rewards to treasury.
+/// Generator pays pending rewards to the contract on every lp tokens deposit,
+/// so reward token balances of the contract are sent along with pending rewards.
+/// Also returns base and proxy reward amounts
+/// ## Params
+/// * **deps** is an object of type [`Deps`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **config** is an object of type [`Config`]
+///
+/// * **amount** is an object of type [`Uint128`]
+fn withdraw_lp_msgs(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    amount: Uint128,
+) -> Result<(Vec<CosmosMsg>, Uint128, Uint128), ContractError> {
+    let generator_contract = match &config.lp_staking {
+        Some(lp_staking) => deps.api.addr_humanize(&lp_staking.generator_contract)?,
+        None => return Err(ContractError::LpStakingDisabled {}),
+    };
+
+    let lp_token = match &config.bonding_mode {
+        BondingMode::Normal { lp_token, .. } => deps.api.addr_humanize(lp_token)?,
+        BondingMode::Community { .. } => return Err(ContractError::LpBondingDisabled {}),
+    };
+
+    let treasury_contract = deps.api.addr_humanize(&config.treasury_contract)?;
+    let reward_info =
+        query_generator_reward_info(&deps.querier, generator_contract.clone(), lp_token.clone())?;
+    let pending_rewards = query_generator_pending_token(
+        &deps.querier,
+        generator_contract.clone(),
+        lp_token.clone(),
+        env.contract.address.clone(),
+    )?;
+
+    // withdraw with zero amount only claims pending rewards
+    let mut msgs: Vec<CosmosMsg> = vec![CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: generator_contract.to_string(),
+        funds: vec![],
+        msg: to_binary(&GeneratorExecuteMsg::Withdraw {
+            lp_token: lp_token.clone(),
+            amount,
+        })?,
+    })];
+
+    // bro balance of the contract is reserved for bond payouts
+    let bro_token = deps.api.addr_humanize(&config.bro_token)?;
+    let reward_token_balance = |token: &Addr| -> StdResult<Uint128> {
+        if *token == bro_token {
+            return Ok(Uint128::zero());
+        }
+
+        query_token_balance(&deps.querier, token.clone(), env.contract.address.clone())
+    };
+
+    let reward_amount = pending_rewards
+        .pending
+        .checked_add(reward_token_balance(&reward_info.base_reward_token)?)?;
+    let mut proxy_reward_amount = pending_rewards.pending_on_proxy.unwrap_or_default();
+    let mut transfers = vec![
+        (lp_token, amount),
+        (reward_info.base_reward_token, reward_amount),
+    ];
+    if let Some(proxy_reward_token) = reward_info.proxy_reward_token {
+        proxy_reward_amount =
+            proxy_reward_amount.checked_add(reward_token_balance(&proxy_reward_token)?)?;
+        transfers.push((proxy_reward_token, proxy_reward_amount));
+    }
+
+    for (token, amount) in transfers {
+        if !amount.is_zero() {
+            msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: token.to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: treasury_contract.to_string(),
+                    amount,
+                })?,
+            }));
+        }
+    }
+
+    Ok((msgs, reward_amount, proxy_reward_amount))
+}
+
+/// ## Description
+/// Returns messages which withdraw the whole lp token deposit of the contract
+/// from astroport generator and send it with pending staking rewards to treasury.
+/// Returns no messages if lp staking is disabled or nothing is deposited.
+/// Also returns withdrawn lp token amount
+/// ## Params
+/// * **deps** is an object of type [`Deps`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **config** is an object of type [`Config`]
+fn withdraw_lp_deposit_msgs(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+) -> Result<(Vec<CosmosMsg>, Uint128), ContractError> {
+    let (generator_contract, lp_token) = match (&config.lp_staking, &config.bonding_mode) {
+        (Some(lp_staking), BondingMode::Normal { lp_token, .. }) => (
+            deps.api.addr_humanize(&lp_staking.generator_contract)?,
+            deps.api.addr_humanize(lp_token)?,
+        ),
+        _ => return Ok((vec![], Uint128::zero())),
+    };
+
+    let deposit = query_generator_deposit(
+        &deps.querier,
+        generator_contract,
+        lp_token,
+        env.contract.address.clone(),
+    )?;
+    if deposit.is_zero() {
+        return Ok((vec![], Uint128::zero()));
+    }
+
+    let (msgs, _, _) = withdraw_lp_msgs(deps, env, config, deposit)?;
+    Ok((msgs, deposit))
+}
+
+/// ## Description
+/// Withdraws all staked lp tokens from astroport generator, sends them to treasury
+/// along with staking rewards and disables lp staking.
+/// Returns [`Response`] with specified attributes and messages if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **env** is an object of type [`Env`]
+pub fn disable_lp_staking(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let mut config = load_config(deps.storage)?;
+    if config.lp_staking.is_none() {
+        return Err(ContractError::LpStakingDisabled {});
+    }
+
+    let (msgs, withdrawn_amount) = withdraw_lp_deposit_msgs(deps.as_ref(), &env, &config)?;
+
+    config.lp_staking = None;
+    store_config(deps.storage, &config)?;
+
+    Ok(Response::new().add_messages(msgs).add_attributes(vec![
+        ("action", "disable_lp_staking"),
+        ("withdrawn_amount", &withdrawn_amount.to_string()),
+    ]))
+}
+
 /// ## Description
 /// Updates contract settings.
+/// Staked lp tokens are withdrawn to treasury when generator contract changes.
 /// Returns [`Response`] with specified attributes and messages if operation was successful,
 /// otherwise returns [`ContractError`]
 /// ## Params
 /// * **deps** is an object of type [`DepsMut`]
 ///
+/// * **env** is an object of type [`Env`]
+///
 /// * **rewards_pool_contract** is an [`Option`] of type [`String`]
 ///
 /// * **treasury_contract** is an [`Option`] of type [`String`]
@@ -328,19 +918,33 @@ pub fn claim(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, Con
 /// * **ust_bonding_discount** is an [`Option`] of type [`Decimal`]
 ///
 /// * **min_bro_payout** is an [`Option`] of type [`Uint128`]
+///
+/// * **discount_control** is an [`Option`] of type [`DiscountControl`]
+///
+/// * **bonding_limits** is an [`Option`] of type [`BondingLimitsMsg`]
+///
+/// * **bond_lock** is an [`Option`] of type [`BondLockMsg`]
+///
+/// * **lp_staking** is an [`Option`] of type [`LpStakingMsg`]
 #[allow(clippy::too_many_arguments)]
 pub fn update_config(
     deps: DepsMut,
+    env: Env,
     rewards_pool_contract: Option<String>,
     treasury_contract: Option<String>,
     astroport_factory: Option<String>,
     oracle_contract: Option<String>,
     ust_bonding_discount: Option<Decimal>,
     min_bro_payout: Option<Uint128>,
+    discount_control: Option<DiscountControl>,
+    bonding_limits: Option<BondingLimitsMsg>,
+    bond_lock: Option<BondLockMsg>,
+    lp_staking: Option<LpStakingMsg>,
 ) -> Result<Response, ContractError> {
     let mut config = load_config(deps.storage)?;
 
     let mut attributes: Vec<Attribute> = vec![Attribute::new("action", "update_config")];
+    let mut msgs: Vec<CosmosMsg> = vec![];
 
     if let Some(rewards_pool_contract) = rewards_pool_contract {
         config.rewards_pool_contract = deps.api.addr_canonicalize(&rewards_pool_contract)?;
@@ -387,19 +991,77 @@ pub fn update_config(
         ));
     }
 
+    if let Some(discount_control) = discount_control {
+        attributes.push(Attribute::new(
+            "discount_control_changed",
+            format!(
+                "min_discount: {}, control_variable: {}, debt_decay_blocks: {}",
+                discount_control.min_discount,
+                discount_control.control_variable,
+                discount_control.debt_decay_blocks
+            ),
+        ));
+        config.discount_control = Some(discount_control);
+    }
+
+    if let Some(bonding_limits) = bonding_limits {
+        attributes.push(Attribute::new(
+            "bonding_limits_changed",
+            &bonding_limits.epoch_manager_contract,
+        ));
+        config.bonding_limits = Some(BondingLimits::from_msg(bonding_limits, deps.api)?);
+    }
+
+    if let Some(bond_lock) = bond_lock {
+        attributes.push(Attribute::new(
+            "bond_lock_changed",
+            format!(
+                "staking_contract: {}, max_lock_discount: {}",
+                bond_lock.staking_contract, bond_lock.max_lock_discount
+            ),
+        ));
+        config.bond_lock = Some(BondLock::from_msg(bond_lock, deps.api)?);
+    }
+
+    if let Some(lp_staking) = lp_staking {
+        let lp_staking = LpStaking::from_msg(lp_staking, deps.api)?;
+        if config.lp_staking.as_ref() != Some(&lp_staking) {
+            let (withdraw_msgs, withdrawn_amount) =
+                withdraw_lp_deposit_msgs(deps.as_ref(), &env, &config)?;
+            if !withdraw_msgs.is_empty() {
+                msgs.extend(withdraw_msgs);
+                attributes.push(Attribute::new(
+                    "lp_withdrawn_amount",
+                    withdrawn_amount.to_string(),
+                ));
+            }
+        }
+
+        attributes.push(Attribute::new(
+            "lp_staking_changed",
+            deps.api.addr_humanize(&lp_staking.generator_contract)?,
+        ));
+        config.lp_staking = Some(lp_staking);
+    }
+
     config.validate()?;
     store_config(deps.storage, &config)?;
 
-    Ok(Response::new().add_attributes(attributes))
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attributes(attributes))
 }
 
 /// ## Description
 /// Updates specific settings for bonding mode config.
+/// Staked lp tokens are withdrawn to treasury when lp token changes.
 /// Returns [`Response`] with specified attributes and messages if operation was successful,
 /// otherwise returns [`ContractError`]
 /// ## Params
 /// * **deps** is an object of type [`DepsMut`]
 ///
+/// * **env** is an object of type [`Env`]
+///
 /// * **ust_bonding_reward_ratio_normal** is an [`Option`] of type [`Decimal`]
 ///
 /// * **lp_token_normal** is an [`Option`] of type [`String`]
@@ -414,6 +1076,7 @@ pub fn update_config(
 #[allow(clippy::too_many_arguments)]
 pub fn update_bonding_mode_config(
     deps: DepsMut,
+    env: Env,
     ust_bonding_reward_ratio_normal: Option<Decimal>,
     lp_token_normal: Option<String>,
     lp_bonding_discount_normal: Option<Decimal>,
@@ -425,6 +1088,23 @@ pub fn update_bonding_mode_config(
 
     let mut attributes: Vec<Attribute> =
         vec![Attribute::new("action", "update_bonding_mode_config")];
+    let mut msgs: Vec<CosmosMsg> = vec![];
+
+    if let (Some(lp_token_normal), BondingMode::Normal { lp_token, .. }) =
+        (&lp_token_normal, &config.bonding_mode)
+    {
+        if deps.api.addr_canonicalize(lp_token_normal)? != *lp_token {
+            let (withdraw_msgs, withdrawn_amount) =
+                withdraw_lp_deposit_msgs(deps.as_ref(), &env, &config)?;
+            if !withdraw_msgs.is_empty() {
+                msgs.extend(withdraw_msgs);
+                attributes.push(Attribute::new(
+                    "lp_withdrawn_amount",
+                    withdrawn_amount.to_string(),
+                ));
+            }
+        }
+    }
 
     match config.bonding_mode {
         BondingMode::Normal {
@@ -511,5 +1191,268 @@ pub fn update_bonding_mode_config(
     config.validate()?;
     store_config(deps.storage, &config)?;
 
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attributes(attributes))
+}
+
+/// ## Description
+/// Registers new bond market.
+/// Returns [`Response`] with specified attributes and messages if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **asset** is an object of type [`BondMarketAssetMsg`]
+///
+/// * **discount** is an object of type [`Decimal`]
+///
+/// * **vesting_period_blocks** is a field of type [`u64`]
+///
+/// * **price_source** is an object of type [`PriceSourceMsg`]
+///
+/// * **discount_control** is an [`Option`] of type [`DiscountControl`]
+///
+/// * **epoch_limits** is an [`Option`] of type [`EpochLimits`]
+#[allow(clippy::too_many_arguments)]
+pub fn register_bond_market(
+    deps: DepsMut,
+    asset: BondMarketAssetMsg,
+    discount: Decimal,
+    vesting_period_blocks: u64,
+    price_source: PriceSourceMsg,
+    discount_control: Option<DiscountControl>,
+    epoch_limits: Option<EpochLimits>,
+) -> Result<Response, ContractError> {
+    let config = load_config(deps.storage)?;
+    if epoch_limits.is_some() && config.bonding_limits.is_none() {
+        return Err(ContractError::BondingLimitsDisabled {});
+    }
+
+    let bro_info = AssetInfo::Token {
+        contract_addr: deps.api.addr_humanize(&config.bro_token)?,
+    };
+
+    let (asset_info, lp_pair) = match asset {
+        BondMarketAssetMsg::NativeToken { denom } => (AssetInfo::NativeToken { denom }, None),
+        BondMarketAssetMsg::Token { contract_addr } => (
+            AssetInfo::Token {
+                contract_addr: deps.api.addr_validate(&contract_addr)?,
+            },
+            None,
+        ),
+        BondMarketAssetMsg::LpToken { paired_asset } => {
+            if paired_asset == bro_info {
+                return Err(ContractError::InvalidBondMarketAsset {});
+            }
+
+            let pair_info = query_pair_info(
+                &deps.querier,
+                deps.api.addr_humanize(&config.astroport_factory)?,
+                &[bro_info.clone(), paired_asset.clone()],
+            )?;
+
+            (
+                AssetInfo::Token {
+                    contract_addr: pair_info.liquidity_token,
+                },
+                Some(LpPair {
+                    pair_contract: deps
+                        .api
+                        .addr_canonicalize(pair_info.contract_addr.as_str())?,
+                    paired_asset,
+                }),
+            )
+        }
+    };
+
+    if asset_info == bro_info {
+        return Err(ContractError::InvalidBondMarketAsset {});
+    }
+
+    let market = BondMarket {
+        asset_info,
+        lp_pair,
+        discount,
+        capacity: Uint128::zero(),
+        vesting_period_blocks,
+        price_source: PriceSource::from_msg(price_source, deps.api)?,
+        enabled: true,
+        discount_control,
+        debt: Uint128::zero(),
+        last_debt_decay_block: 0,
+        epoch_limits,
+        total_bonded: Uint128::zero(),
+    };
+
+    market.validate()?;
+    let market_id = register_market(deps.storage, &market)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "register_bond_market"),
+        ("market_id", &market_id.to_string()),
+        ("asset", &market.asset_info.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Updates specified bond market settings.
+/// Returns [`Response`] with specified attributes and messages if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **market_id** is a field of type [`u64`]
+///
+/// * **discount** is an [`Option`] of type [`Decimal`]
+///
+/// * **vesting_period_blocks** is an [`Option`] of type [`u64`]
+///
+/// * **price_source** is an [`Option`] of type [`PriceSourceMsg`]
+///
+/// * **enabled** is an [`Option`] of type [`bool`]
+///
+/// * **discount_control** is an [`Option`] of type [`DiscountControl`]
+///
+/// * **epoch_limits** is an [`Option`] of type [`EpochLimits`]
+#[allow(clippy::too_many_arguments)]
+pub fn update_bond_market(
+    deps: DepsMut,
+    market_id: u64,
+    discount: Option<Decimal>,
+    vesting_period_blocks: Option<u64>,
+    price_source: Option<PriceSourceMsg>,
+    enabled: Option<bool>,
+    discount_control: Option<DiscountControl>,
+    epoch_limits: Option<EpochLimits>,
+) -> Result<Response, ContractError> {
+    let mut market = load_bond_market(deps.storage, market_id)?;
+
+    let mut attributes: Vec<Attribute> = vec![
+        Attribute::new("action", "update_bond_market"),
+        Attribute::new("market_id", market_id.to_string()),
+    ];
+
+    if let Some(discount) = discount {
+        market.discount = discount;
+        attributes.push(Attribute::new("discount_changed", discount.to_string()));
+    }
+
+    if let Some(vesting_period_blocks) = vesting_period_blocks {
+        market.vesting_period_blocks = vesting_period_blocks;
+        attributes.push(Attribute::new(
+            "vesting_period_blocks_changed",
+            vesting_period_blocks.to_string(),
+        ));
+    }
+
+    if let Some(price_source) = price_source {
+        market.price_source = PriceSource::from_msg(price_source, deps.api)?;
+        attributes.push(Attribute::new("price_source_changed", "true"));
+    }
+
+    if let Some(enabled) = enabled {
+        market.enabled = enabled;
+        attributes.push(Attribute::new("enabled_changed", enabled.to_string()));
+    }
+
+    if let Some(discount_control) = discount_control {
+        attributes.push(Attribute::new(
+            "discount_control_changed",
+            format!(
+                "min_discount: {}, control_variable: {}, debt_decay_blocks: {}",
+                discount_control.min_discount,
+                discount_control.control_variable,
+                discount_control.debt_decay_blocks
+            ),
+        ));
+        market.discount_control = Some(discount_control);
+    }
+
+    if let Some(epoch_limits) = epoch_limits {
+        if load_config(deps.storage)?.bonding_limits.is_none() {
+            return Err(ContractError::BondingLimitsDisabled {});
+        }
+
+        market.epoch_limits = Some(epoch_limits);
+        attributes.push(Attribute::new("epoch_limits_changed", "true"));
+    }
+
+    market.validate()?;
+    store_bond_market(deps.storage, market_id, &market)?;
+
     Ok(Response::new().add_attributes(attributes))
 }
+
+/// ## Description
+/// Increases capacity of specified bond market by received bro amount.
+/// Returns [`Response`] with specified attributes and messages if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **market_id** is a field of type [`u64`]
+///
+/// * **amount** is an object of type [`Uint128`]
+pub fn fund_bond_market(
+    deps: DepsMut,
+    market_id: u64,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let mut market = load_bond_market(deps.storage, market_id)?;
+    market.capacity = market.capacity.checked_add(amount)?;
+    store_bond_market(deps.storage, market_id, &market)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "fund_bond_market"),
+        ("market_id", &market_id.to_string()),
+        ("amount", &amount.to_string()),
+        ("capacity", &market.capacity.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Decreases capacity of specified bond market and sends withdrawn bro amount to treasury.
+/// Returns [`Response`] with specified attributes and messages if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **market_id** is a field of type [`u64`]
+///
+/// * **amount** is an [`Option`] of type [`Uint128`]
+pub fn withdraw_bond_market_capacity(
+    deps: DepsMut,
+    market_id: u64,
+    amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let config = load_config(deps.storage)?;
+    let mut market = load_bond_market(deps.storage, market_id)?;
+
+    let amount = amount.unwrap_or(market.capacity);
+    if amount.is_zero() || amount > market.capacity {
+        return Err(ContractError::InvalidCapacityWithdrawAmount {});
+    }
+
+    market.capacity = market.capacity.checked_sub(amount)?;
+    store_bond_market(deps.storage, market_id, &market)?;
+
+    Ok(Response::new()
+        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: deps.api.addr_humanize(&config.bro_token)?.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: deps
+                    .api
+                    .addr_humanize(&config.treasury_contract)?
+                    .to_string(),
+                amount,
+            })?,
+        }))
+        .add_attributes(vec![
+            ("action", "withdraw_bond_market_capacity"),
+            ("market_id", &market_id.to_string()),
+            ("amount", &amount.to_string()),
+            ("capacity", &market.capacity.to_string()),
+        ]))
+}
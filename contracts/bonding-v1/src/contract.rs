@@ -12,9 +12,15 @@ use crate::{
     error::ContractError,
     migration::{load_config_v100, MigrationMsgV100},
     queries,
-    state::{load_config, store_config, store_state, update_owner, BondingMode, Config, State},
+    state::{
+        load_bond_market, load_config, load_state, store_config, store_state, update_owner,
+        BondLock, BondingLimits, BondingMode, Config, LpStaking, State,
+    },
+    utils::extract_native_token,
 };
 
+use astroport::asset::{Asset, AssetInfo};
+
 use services::{
     bonding::{Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
     ownership_proposal::{
@@ -34,7 +40,7 @@ const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 /// ## Params
 /// * **deps** is an object of type [`DepsMut`].
 ///
-/// * **_env** is an object of type [`Env`].
+/// * **env** is an object of type [`Env`].
 ///
 /// * **_info** is an object of type [`MessageInfo`].
 ///
@@ -42,7 +48,7 @@ const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
@@ -60,12 +66,31 @@ pub fn instantiate(
         ust_bonding_discount: msg.ust_bonding_discount,
         min_bro_payout: msg.min_bro_payout,
         bonding_mode,
+        discount_control: msg.discount_control,
+        bonding_limits: msg
+            .bonding_limits
+            .map(|limits| BondingLimits::from_msg(limits, deps.api))
+            .transpose()?,
+        bond_lock: msg
+            .bond_lock
+            .map(|bond_lock| BondLock::from_msg(bond_lock, deps.api))
+            .transpose()?,
+        lp_staking: msg
+            .lp_staking
+            .map(|lp_staking| LpStaking::from_msg(lp_staking, deps.api))
+            .transpose()?,
     };
 
     config.validate()?;
     store_config(deps.storage, &config)?;
 
-    store_state(deps.storage, &State::default())?;
+    store_state(
+        deps.storage,
+        &State {
+            last_debt_decay_block: env.block.height,
+            ..State::default()
+        },
+    )?;
 
     Ok(Response::default())
 }
@@ -86,9 +111,11 @@ pub fn instantiate(
 /// * **ExecuteMsg::Receive(msg)** Receives a message of type [`Cw20ReceiveMsg`]
 /// and processes it depending on the received template
 ///
-/// * **ExecuteMsg::UstBond {}** Bond bro tokens by providing ust amount
+/// * **ExecuteMsg::UstBond { min_bro_payout, lock_epochs }** Bond bro tokens by providing ust amount
 ///
-/// * **ExecuteMsg::Claim {}** Claim available reward amount
+/// * **ExecuteMsg::Bond { market_id, min_bro_payout }** Bond bro tokens by providing native token amount to specified bond market
+///
+/// * **ExecuteMsg::Claim {}** Claim vested amount of all bonder claims
 ///
 /// * **ExecuteMsg::UpdateConfig {
 ///         rewards_pool_contract,
@@ -97,6 +124,10 @@ pub fn instantiate(
 ///         oracle_contract,
 ///         ust_bonding_discount,
 ///         min_bro_payout,
+///         discount_control,
+///         bonding_limits,
+///         bond_lock,
+///         lp_staking,
 ///     }** Updates contract settings
 ///
 /// * **ExecuteMsg::UpdateBondingModeConfig {
@@ -108,6 +139,36 @@ pub fn instantiate(
 ///         epochs_locked_community,
 ///     }** Updates specific settings for bonding mode config
 ///
+/// * **ExecuteMsg::RegisterBondMarket {
+///         asset,
+///         discount,
+///         vesting_period_blocks,
+///         price_source,
+///         discount_control,
+///         epoch_limits,
+///     }** Registers new bond market
+///
+/// * **ExecuteMsg::UpdateBondMarket {
+///         market_id,
+///         discount,
+///         vesting_period_blocks,
+///         price_source,
+///         enabled,
+///         discount_control,
+///         epoch_limits,
+///     }** Updates specified bond market settings
+///
+/// * **ExecuteMsg::WithdrawBondMarketCapacity {
+///         market_id,
+///         amount,
+///     }** Withdraws unused capacity of specified bond market to treasury
+///
+/// * **ExecuteMsg::HarvestLpRewards {}** Claims lp staking rewards from astroport generator and sends them to treasury
+///
+/// * **ExecuteMsg::UnstakeLp { amount }** Withdraws staked lp tokens from astroport generator to treasury
+///
+/// * **ExecuteMsg::DisableLpStaking {}** Withdraws all staked lp tokens to treasury and disables lp staking
+///
 /// * **ExecuteMsg::ProposeNewOwner {
 ///         new_owner,
 ///         expires_in_blocks,
@@ -125,7 +186,28 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
-        ExecuteMsg::UstBond {} => commands::ust_bond(deps, env, info),
+        ExecuteMsg::UstBond {
+            min_bro_payout,
+            lock_epochs,
+        } => commands::ust_bond(deps, env, info, min_bro_payout, lock_epochs),
+        ExecuteMsg::Bond {
+            market_id,
+            min_bro_payout,
+        } => {
+            let bond_asset = match load_bond_market(deps.storage, market_id)?.asset_info {
+                AssetInfo::NativeToken { denom } => extract_native_token(&info.funds, &denom)?,
+                AssetInfo::Token { .. } => return Err(ContractError::InvalidFundsInput {}),
+            };
+
+            commands::bond(
+                deps,
+                env,
+                info.sender,
+                market_id,
+                bond_asset,
+                min_bro_payout,
+            )
+        }
         ExecuteMsg::Claim {} => commands::claim(deps, env, info),
         ExecuteMsg::UpdateConfig {
             rewards_pool_contract,
@@ -134,16 +216,25 @@ pub fn execute(
             oracle_contract,
             ust_bonding_discount,
             min_bro_payout,
+            discount_control,
+            bonding_limits,
+            bond_lock,
+            lp_staking,
         } => {
             assert_owner(deps.storage, deps.api, info.sender)?;
             commands::update_config(
                 deps,
+                env,
                 rewards_pool_contract,
                 treasury_contract,
                 astroport_factory,
                 oracle_contract,
                 ust_bonding_discount,
                 min_bro_payout,
+                discount_control.map(|discount_control| *discount_control),
+                bonding_limits.map(|bonding_limits| *bonding_limits),
+                bond_lock.map(|bond_lock| *bond_lock),
+                lp_staking.map(|lp_staking| *lp_staking),
             )
         }
         ExecuteMsg::UpdateBondingModeConfig {
@@ -157,6 +248,7 @@ pub fn execute(
             assert_owner(deps.storage, deps.api, info.sender)?;
             commands::update_bonding_mode_config(
                 deps,
+                env,
                 ust_bonding_reward_ratio_normal,
                 lp_token_normal,
                 lp_bonding_discount_normal,
@@ -165,6 +257,59 @@ pub fn execute(
                 epochs_locked_community,
             )
         }
+        ExecuteMsg::RegisterBondMarket {
+            asset,
+            discount,
+            vesting_period_blocks,
+            price_source,
+            discount_control,
+            epoch_limits,
+        } => {
+            assert_owner(deps.storage, deps.api, info.sender)?;
+            commands::register_bond_market(
+                deps,
+                asset,
+                discount,
+                vesting_period_blocks,
+                price_source,
+                discount_control,
+                epoch_limits,
+            )
+        }
+        ExecuteMsg::UpdateBondMarket {
+            market_id,
+            discount,
+            vesting_period_blocks,
+            price_source,
+            enabled,
+            discount_control,
+            epoch_limits,
+        } => {
+            assert_owner(deps.storage, deps.api, info.sender)?;
+            commands::update_bond_market(
+                deps,
+                market_id,
+                discount,
+                vesting_period_blocks,
+                price_source,
+                enabled,
+                discount_control,
+                epoch_limits,
+            )
+        }
+        ExecuteMsg::WithdrawBondMarketCapacity { market_id, amount } => {
+            assert_owner(deps.storage, deps.api, info.sender)?;
+            commands::withdraw_bond_market_capacity(deps, market_id, amount)
+        }
+        ExecuteMsg::HarvestLpRewards {} => commands::harvest_lp_rewards(deps, env),
+        ExecuteMsg::UnstakeLp { amount } => {
+            assert_owner(deps.storage, deps.api, info.sender)?;
+            commands::unstake_lp(deps, env, amount)
+        }
+        ExecuteMsg::DisableLpStaking {} => {
+            assert_owner(deps.storage, deps.api, info.sender)?;
+            commands::disable_lp_staking(deps, env)
+        }
         ExecuteMsg::ProposeNewOwner {
             new_owner,
             expires_in_blocks,
@@ -222,7 +367,10 @@ pub fn receive_cw20(
 
             commands::distribute_reward(deps, cw20_msg.amount)
         }
-        Ok(Cw20HookMsg::LpBond {}) => {
+        Ok(Cw20HookMsg::LpBond {
+            min_bro_payout,
+            lock_epochs,
+        }) => {
             let (lp_token, lp_bonding_discount, vesting_period_blocks) = match config.bonding_mode {
                 BondingMode::Normal {
                     lp_bonding_discount,
@@ -246,8 +394,48 @@ pub fn receive_cw20(
                 lp_token,
                 lp_bonding_discount,
                 vesting_period_blocks,
+                min_bro_payout,
+                lock_epochs,
+            )
+        }
+        Ok(Cw20HookMsg::Bond {
+            market_id,
+            min_bro_payout,
+        }) => {
+            let market = load_bond_market(deps.storage, market_id)?;
+            if market.asset_info
+                != (AssetInfo::Token {
+                    contract_addr: info.sender,
+                })
+            {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            let sender = deps.api.addr_validate(&cw20_msg.sender)?;
+            commands::bond(
+                deps,
+                env,
+                sender,
+                market_id,
+                Asset {
+                    info: market.asset_info,
+                    amount: cw20_msg.amount,
+                },
+                min_bro_payout,
             )
         }
+        Ok(Cw20HookMsg::FundBondMarket { market_id }) => {
+            if info.sender != deps.api.addr_humanize(&config.bro_token)? {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            // only owner allowed to fund bond markets
+            if config.owner != deps.api.addr_canonicalize(&cw20_msg.sender)? {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            commands::fund_bond_market(deps, market_id, cw20_msg.amount)
+        }
         Err(_) => Err(ContractError::InvalidHookData {}),
     }
 }
@@ -274,7 +462,7 @@ fn assert_owner(storage: &dyn Storage, api: &dyn Api, sender: Addr) -> Result<()
 /// ## Params
 /// * **deps** is an object of type [`Deps`].
 ///
-/// * **_env** is an object of type [`Env`].
+/// * **env** is an object of type [`Env`].
 ///
 /// * **msg** is an object of type [`ExecuteMsg`].
 ///
@@ -286,22 +474,62 @@ fn assert_owner(storage: &dyn Storage, api: &dyn Api, sender: Addr) -> Result<()
 ///
 /// * **QueryMsg::Claims { address }** Returns available claims for bonder by specified address
 ///
-/// * **QueryMsg::SimulateUstBond { uusd_amount }** Returns simulated bro bond using specified uusd amount
+/// * **QueryMsg::SimulateUstBond { uusd_amount, lock_epochs }** Returns simulated bro bond using specified uusd amount
+///
+/// * **QueryMsg::SimulateLpBond { lp_amount, lock_epochs }** Returns simulated bro bond using specified ust/bro lp token amount
+///
+/// * **QueryMsg::BondingCapacity { start_after, limit }** Returns bonding capacity available in current epoch
 ///
-/// * **QueryMsg::SimulateLpBond { lp_amount }** Returns simulated bro bond using specified ust/bro lp token amount
+/// * **QueryMsg::BondMarket { market_id }** Returns information about specified bond market
+///
+/// * **QueryMsg::BondMarkets { start_after, limit }** Returns a list of bond markets
+///
+/// * **QueryMsg::SimulateBond { market_id, amount }** Returns simulated bro bond using specified asset amount of bond market
+///
+/// * **QueryMsg::Bonder { address }** Returns lifetime bonding totals of specified bonder
+///
+/// * **QueryMsg::Bonders { start_after, limit }** Returns a list of bonders with their lifetime bonding totals
 ///
 /// * **QueryMsg::OwnershipProposal {}** Returns information about created ownership proposal otherwise returns not-found error
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_binary(&queries::query_config(deps)?),
-        QueryMsg::State {} => to_binary(&queries::query_state(deps)?),
-        QueryMsg::Claims { address } => to_binary(&queries::query_claims(deps, address)?),
-        QueryMsg::SimulateUstBond { uusd_amount } => {
-            to_binary(&queries::simulate_ust_bond(deps, uusd_amount)?)
+        QueryMsg::State {} => to_binary(&queries::query_state(deps, env)?),
+        QueryMsg::Claims { address } => to_binary(&queries::query_claims(deps, env, address)?),
+        QueryMsg::SimulateUstBond {
+            uusd_amount,
+            lock_epochs,
+        } => to_binary(&queries::simulate_ust_bond(
+            deps,
+            env,
+            uusd_amount,
+            lock_epochs,
+        )?),
+        QueryMsg::SimulateLpBond {
+            lp_amount,
+            lock_epochs,
+        } => to_binary(&queries::simulate_lp_bond(
+            deps,
+            env,
+            lp_amount,
+            lock_epochs,
+        )?),
+        QueryMsg::BondingCapacity { start_after, limit } => to_binary(
+            &queries::query_bonding_capacity(deps, env, start_after, limit)?,
+        ),
+        QueryMsg::BondMarket { market_id } => {
+            to_binary(&queries::query_bond_market(deps, env, market_id)?)
+        }
+        QueryMsg::BondMarkets { start_after, limit } => {
+            to_binary(&queries::query_bond_markets(deps, env, start_after, limit)?)
         }
-        QueryMsg::SimulateLpBond { lp_amount } => {
-            to_binary(&queries::simulate_lp_bond(deps, lp_amount)?)
+        QueryMsg::SimulateBond { market_id, amount } => {
+            to_binary(&queries::simulate_bond(deps, env, market_id, amount)?)
+        }
+        QueryMsg::Bonder { address } => to_binary(&queries::query_bonder(deps, address)?),
+        QueryMsg::Bonders { start_after, limit } => {
+            to_binary(&queries::query_bonders(deps, start_after, limit)?)
         }
         QueryMsg::OwnershipProposal {} => to_binary(&query_ownership_proposal(deps)?),
     }
@@ -312,11 +540,11 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
 /// ## Params
 /// * **_deps** is an object of type [`Deps`].
 ///
-/// * **_env** is an object of type [`Env`].
+/// * **env** is an object of type [`Env`].
 ///
 /// * **_msg** is an object of type [`MigrateMsg`].
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
     let contract_version = get_contract_version(deps.storage)?;
 
     match contract_version.contract.as_ref() {
@@ -338,10 +566,18 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, Co
                     ust_bonding_discount: config.ust_bonding_discount,
                     min_bro_payout: config.min_bro_payout,
                     bonding_mode,
+                    discount_control: None,
+                    bonding_limits: None,
+                    bond_lock: None,
+                    lp_staking: None,
                 };
 
                 new_config.validate()?;
                 store_config(deps.storage, &new_config)?;
+                migrate_debt_decay(deps.storage, env.block.height)?;
+            }
+            "1.1.0" => {
+                migrate_debt_decay(deps.storage, env.block.height)?;
             }
             _ => return Err(ContractError::MigrationError {}),
         },
@@ -358,3 +594,16 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, Co
         ("new_contract_version", CONTRACT_VERSION),
     ]))
 }
+
+/// ## Description
+/// Starts bonding debt decay from migration block,
+/// otherwise the first bond after migration decays debt over all blocks since genesis
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **current_block** is a field of type [`u64`]
+fn migrate_debt_decay(storage: &mut dyn Storage, current_block: u64) -> StdResult<()> {
+    let mut state = load_state(storage)?;
+    state.last_debt_decay_block = current_block;
+    store_state(storage, &state)
+}
@@ -34,6 +34,9 @@ pub enum ContractError {
     #[error("BRO bonding payout is too low")]
     BondPayoutIsTooLow {},
 
+    #[error("BRO bonding payout is less than expected minimum payout")]
+    BondSlippageExceeded {},
+
     #[error("Insufficient BRO balance for bond payout")]
     NotEnoughForBondPayout {},
 
@@ -42,4 +45,31 @@ pub enum ContractError {
 
     #[error("LP Token bonding disabled")]
     LpBondingDisabled {},
+
+    #[error("Locking bond payout in staking contract disabled")]
+    BondLockDisabled {},
+
+    #[error("Invalid lockup period")]
+    InvalidLockupPeriod {},
+
+    #[error("LP Token staking disabled")]
+    LpStakingDisabled {},
+
+    #[error("Bonding limits disabled")]
+    BondingLimitsDisabled {},
+
+    #[error("Bond market disabled")]
+    BondMarketDisabled {},
+
+    #[error("Invalid bond market capacity withdraw amount")]
+    InvalidCapacityWithdrawAmount {},
+
+    #[error("Invalid bond market asset")]
+    InvalidBondMarketAsset {},
+
+    #[error("Bonding capacity for current epoch exceeded")]
+    EpochBondingCapacityExceeded {},
+
+    #[error("Address bonding cap for current epoch exceeded")]
+    AddressBondingCapExceeded {},
 }
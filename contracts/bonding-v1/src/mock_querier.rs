@@ -8,9 +8,14 @@ use services::staking::LockupConfigResponse;
 use std::collections::HashMap;
 use std::str::FromStr;
 
-use astroport::{asset::PairInfo, factory::QueryMsg as FactoryQueryMsg};
+use astroport::{
+    asset::PairInfo,
+    factory::QueryMsg as FactoryQueryMsg,
+    generator::{PendingTokenResponse, QueryMsg as GeneratorQueryMsg, RewardInfoResponse},
+};
 use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20QueryMsg, TokenInfoResponse};
 use services::{
+    epoch_manager::{EpochInfoResponse, QueryMsg as EpochManagerQueryMsg},
     oracle::{ConsultPriceResponse, QueryMsg as OracleQueryMsg},
     staking::{ConfigResponse as StakingConfigResponse, QueryMsg as StakingQueryMsg},
 };
@@ -22,6 +27,10 @@ pub const MOCK_LP_TOKEN_ADDR: &str = "bro_ust_lp";
 pub const MOCK_BRO_TOKEN_ADDR: &str = "bro_token";
 pub const MOCK_ORACLE_ADDR: &str = "oracle";
 pub const MOCK_STAKING_ADDR: &str = "bro_staking";
+pub const MOCK_EPOCH_MANAGER_ADDR: &str = "epoch_manager";
+pub const MOCK_GENERATOR_ADDR: &str = "generator";
+pub const MOCK_ASTRO_TOKEN_ADDR: &str = "astro_token";
+pub const MOCK_PROXY_REWARD_TOKEN_ADDR: &str = "proxy_reward_token";
 
 /// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
 /// this uses our CustomQuerier.
@@ -41,6 +50,7 @@ pub struct WasmMockQuerier {
     base: MockQuerier<TerraQueryWrapper>,
     token_querier: TokenQuerier,
     tax_querier: TaxQuerier,
+    generator_deposit: Uint128,
 }
 
 #[derive(Clone, Default)]
@@ -159,12 +169,52 @@ impl WasmMockQuerier {
                         }
                         _ => panic!("DO NOT ENTER HERE"),
                     }
+                } else if contract_addr == MOCK_EPOCH_MANAGER_ADDR {
+                    match from_binary(msg).unwrap() {
+                        EpochManagerQueryMsg::EpochInfo {} => SystemResult::Ok(ContractResult::Ok(
+                            to_binary(&EpochInfoResponse {
+                                epoch: 100,
+                                blocks_per_year: 1000,
+                                bbro_emission_rate: Decimal::one(),
+                            })
+                            .unwrap(),
+                        )),
+                        _ => panic!("DO NOT ENTER HERE"),
+                    }
+                } else if contract_addr == MOCK_GENERATOR_ADDR {
+                    match from_binary(msg).unwrap() {
+                        GeneratorQueryMsg::RewardInfo { .. } => {
+                            SystemResult::Ok(ContractResult::Ok(
+                                to_binary(&RewardInfoResponse {
+                                    base_reward_token: Addr::unchecked(MOCK_ASTRO_TOKEN_ADDR),
+                                    proxy_reward_token: Some(Addr::unchecked(
+                                        MOCK_PROXY_REWARD_TOKEN_ADDR,
+                                    )),
+                                })
+                                .unwrap(),
+                            ))
+                        }
+                        GeneratorQueryMsg::Deposit { .. } => SystemResult::Ok(ContractResult::Ok(
+                            to_binary(&self.generator_deposit).unwrap(),
+                        )),
+                        GeneratorQueryMsg::PendingToken { .. } => {
+                            SystemResult::Ok(ContractResult::Ok(
+                                to_binary(&PendingTokenResponse {
+                                    pending: Uint128::from(10_000000u128),
+                                    pending_on_proxy: Some(Uint128::from(5_000000u128)),
+                                })
+                                .unwrap(),
+                            ))
+                        }
+                        _ => panic!("DO NOT ENTER HERE"),
+                    }
                 } else if contract_addr == MOCK_STAKING_ADDR {
                     match from_binary(msg).unwrap() {
                         StakingQueryMsg::Config {} => SystemResult::Ok(ContractResult::Ok(
                             to_binary(&StakingConfigResponse {
                                 owner: "owner".to_string(),
                                 paused: false,
+                                emergency_mode: false,
                                 bro_token: "bro_token".to_string(),
                                 rewards_pool_contract: "rewards".to_string(),
                                 bbro_minter_contract: "bbro_minter".to_string(),
@@ -172,6 +222,7 @@ impl WasmMockQuerier {
                                 community_bonding_contract: Some(
                                     "community_bonding0000".to_string(),
                                 ),
+                                bond_lock_contracts: vec![],
                                 unstake_period_blocks: 10,
                                 min_staking_amount: Uint128::from(1u128),
                                 lockup_config: LockupConfigResponse {
@@ -180,7 +231,11 @@ impl WasmMockQuerier {
                                     base_rate: Decimal::from_str("0.1").unwrap(),
                                     linear_growth: Decimal::from_str("0.1").unwrap(),
                                     exponential_growth: Decimal::from_str("0.1").unwrap(),
+                                    early_unlock_penalty: None,
                                 },
+                                boost: None,
+                                instant_unstake: None,
+                                staker_history_limit: 0,
                             })
                             .unwrap(),
                         )),
@@ -241,6 +296,7 @@ impl WasmMockQuerier {
             base,
             token_querier: TokenQuerier::default(),
             tax_querier: TaxQuerier::default(),
+            generator_deposit: Uint128::zero(),
         }
     }
 
@@ -248,4 +304,9 @@ impl WasmMockQuerier {
     pub fn with_token_balances(&mut self, balances: &[(&String, &[(&String, &Uint128)])]) {
         self.token_querier = TokenQuerier::new(balances);
     }
+
+    // configure lp token amount deposited in generator mock
+    pub fn with_generator_deposit(&mut self, amount: Uint128) {
+        self.generator_deposit = amount;
+    }
 }
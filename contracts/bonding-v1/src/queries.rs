@@ -1,14 +1,22 @@
 use astroport::asset::AssetInfo;
-use cosmwasm_std::{Deps, StdError, StdResult, Uint128};
+use cosmwasm_std::{Api, Deps, Env, StdError, StdResult, Uint128};
 
 use crate::{
-    state::{load_claims, load_config, load_state, BondingMode},
-    utils::{apply_discount, get_share_in_assets},
+    state::{
+        load_bond_market, load_bonder, load_claims, load_config, load_epoch_bonded, load_state,
+        read_bond_markets, read_bonders, BondMarket, BondType, BonderInfo, BondingMode,
+    },
+    utils::{
+        apply_discount, compute_discount, get_share_in_assets, query_bond_lock_discount,
+        query_bond_market_bro_amount, query_current_epoch,
+    },
 };
 
 use services::{
     bonding::{
-        BondingModeMsg, ClaimInfoResponse, ClaimsResponse, ConfigResponse,
+        BondMarketCapacityResponse, BondMarketResponse, BondMarketsResponse,
+        BondTypeCapacityResponse, BonderResponse, BondersResponse, BondingCapacityResponse,
+        BondingModeMsg, ClaimInfoResponse, ClaimsResponse, ConfigResponse, EpochLimits,
         SimulateExchangeResponse, StateResponse,
     },
     querier::{query_bro_ust_pair, query_oracle_price},
@@ -61,6 +69,19 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
         ust_bonding_discount: config.ust_bonding_discount,
         min_bro_payout: config.min_bro_payout,
         bonding_mode,
+        discount_control: config.discount_control,
+        bonding_limits: config
+            .bonding_limits
+            .map(|limits| limits.to_msg(deps.api))
+            .transpose()?,
+        bond_lock: config
+            .bond_lock
+            .map(|bond_lock| bond_lock.to_msg(deps.api))
+            .transpose()?,
+        lp_staking: config
+            .lp_staking
+            .map(|lp_staking| lp_staking.to_msg(deps.api))
+            .transpose()?,
     };
 
     Ok(resp)
@@ -70,11 +91,23 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
 /// Returns bonding contract state in the [`StateResponse`] object
 /// ## Params
 /// * **deps** is an object of type [`Deps`]
-pub fn query_state(deps: Deps) -> StdResult<StateResponse> {
-    let state = load_state(deps.storage)?;
+///
+/// * **env** is an object of type [`Env`]
+pub fn query_state(deps: Deps, env: Env) -> StdResult<StateResponse> {
+    let config = load_config(deps.storage)?;
+    let mut state = load_state(deps.storage)?;
+    state.decay_bonding_debt(&config.discount_control, env.block.height);
+
     let resp = StateResponse {
         ust_bonding_balance: state.ust_bonding_balance,
         lp_bonding_balance: state.lp_bonding_balance,
+        ust_bonding_debt: state.ust_bonding_debt,
+        lp_bonding_debt: state.lp_bonding_debt,
+        total_ust_bonded: state.total_ust_bonded,
+        total_lp_bonded: state.total_lp_bonded,
+        total_bro_payout: state.total_bro_payout,
+        total_bro_claimed: state.total_bro_claimed,
+        bonds_count: state.bonds_count,
     };
 
     Ok(resp)
@@ -85,17 +118,25 @@ pub fn query_state(deps: Deps) -> StdResult<StateResponse> {
 /// ## Params
 /// * **deps** is an object of type [`Deps`]
 ///
+/// * **env** is an object of type [`Env`]
+///
 /// * **address** is a field of type [`String`]
-pub fn query_claims(deps: Deps, address: String) -> StdResult<ClaimsResponse> {
+pub fn query_claims(deps: Deps, env: Env, address: String) -> StdResult<ClaimsResponse> {
     let address_raw = deps.api.addr_canonicalize(&address)?;
     let claims: Vec<ClaimInfoResponse> = load_claims(deps.storage, &address_raw)?
         .into_iter()
-        .map(|c| ClaimInfoResponse {
-            bond_type: c.bond_type.to_string(),
-            amount: c.amount,
-            claimable_at: c.claimable_at,
+        .map(|c| {
+            Ok(ClaimInfoResponse {
+                bond_type: c.bond_type.to_string(),
+                amount: c.amount,
+                claimable_at: c.claimable_at,
+                vested_amount: c.vested_amount(&env.block),
+                claimed_amount: c.claimed_amount,
+                claimable_amount: c.claimable_amount(&env.block)?,
+                remaining_amount: c.amount.checked_sub(c.vested_amount(&env.block))?,
+            })
         })
-        .collect();
+        .collect::<StdResult<Vec<ClaimInfoResponse>>>()?;
 
     let resp = ClaimsResponse { claims };
     Ok(resp)
@@ -106,10 +147,20 @@ pub fn query_claims(deps: Deps, address: String) -> StdResult<ClaimsResponse> {
 /// ## Params
 /// * **deps** is an object of type [`Deps`]
 ///
+/// * **env** is an object of type [`Env`]
+///
 /// * **uusd_amount** is an object of type [`Uint128`]
-pub fn simulate_ust_bond(deps: Deps, uusd_amount: Uint128) -> StdResult<SimulateExchangeResponse> {
+///
+/// * **lock_epochs** is an [`Option`] of type [`u64`]
+pub fn simulate_ust_bond(
+    deps: Deps,
+    env: Env,
+    uusd_amount: Uint128,
+    lock_epochs: Option<u64>,
+) -> StdResult<SimulateExchangeResponse> {
     let config = load_config(deps.storage)?;
-    let state = load_state(deps.storage)?;
+    let mut state = load_state(deps.storage)?;
+    state.decay_bonding_debt(&config.discount_control, env.block.height);
 
     let bro_amount = query_oracle_price(
         &deps.querier,
@@ -121,13 +172,28 @@ pub fn simulate_ust_bond(deps: Deps, uusd_amount: Uint128) -> StdResult<Simulate
     )?
     .amount;
 
-    let bro_payout = apply_discount(config.ust_bonding_discount, bro_amount)?;
+    let mut discount = compute_discount(
+        config.ust_bonding_discount,
+        &config.discount_control,
+        state.ust_bonding_debt,
+        state.ust_bonding_balance,
+    )?;
+
+    if let Some(lock_epochs) = lock_epochs {
+        let (_, lock_discount) =
+            query_bond_lock_discount(&deps.querier, deps.api, &config.bond_lock, lock_epochs)
+                .map_err(|e| StdError::generic_err(e.to_string()))?;
+        discount = discount + lock_discount;
+    }
+
+    let bro_payout = apply_discount(discount, bro_amount)?;
     let can_be_exchanged =
         bro_payout >= config.min_bro_payout && bro_payout <= state.ust_bonding_balance;
 
     let resp = SimulateExchangeResponse {
         bro_payout,
         can_be_exchanged,
+        discount,
     };
 
     Ok(resp)
@@ -138,10 +204,20 @@ pub fn simulate_ust_bond(deps: Deps, uusd_amount: Uint128) -> StdResult<Simulate
 /// ## Params
 /// * **deps** is an object of type [`Deps`]
 ///
+/// * **env** is an object of type [`Env`]
+///
 /// * **lp_amount** is an object of type [`Uint128`]
-pub fn simulate_lp_bond(deps: Deps, lp_amount: Uint128) -> StdResult<SimulateExchangeResponse> {
+///
+/// * **lock_epochs** is an [`Option`] of type [`u64`]
+pub fn simulate_lp_bond(
+    deps: Deps,
+    env: Env,
+    lp_amount: Uint128,
+    lock_epochs: Option<u64>,
+) -> StdResult<SimulateExchangeResponse> {
     let config = load_config(deps.storage)?;
-    let state = load_state(deps.storage)?;
+    let mut state = load_state(deps.storage)?;
+    state.decay_bonding_debt(&config.discount_control, env.block.height);
 
     let (lp_token, lp_bonding_discount) = match config.bonding_mode {
         BondingMode::Normal {
@@ -180,14 +256,311 @@ pub fn simulate_lp_bond(deps: Deps, lp_amount: Uint128) -> StdResult<SimulateExc
         .amount,
     )?;
 
-    let bro_payout = apply_discount(lp_bonding_discount, bro_amount)?;
+    let mut discount = compute_discount(
+        lp_bonding_discount,
+        &config.discount_control,
+        state.lp_bonding_debt,
+        state.lp_bonding_balance,
+    )?;
+
+    if let Some(lock_epochs) = lock_epochs {
+        let (_, lock_discount) =
+            query_bond_lock_discount(&deps.querier, deps.api, &config.bond_lock, lock_epochs)
+                .map_err(|e| StdError::generic_err(e.to_string()))?;
+        discount = discount + lock_discount;
+    }
+
+    let bro_payout = apply_discount(discount, bro_amount)?;
     let can_be_exchanged =
         bro_payout >= config.min_bro_payout && bro_payout <= state.lp_bonding_balance;
 
     let resp = SimulateExchangeResponse {
         bro_payout,
         can_be_exchanged,
+        discount,
     };
 
     Ok(resp)
 }
+
+/// ## Description
+/// Returns bonding capacity available in current epoch in the [`BondingCapacityResponse`] object
+/// ## Params
+/// * **deps** is an object of type [`Deps`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **start_after** is an [`Option`] field of type [`u64`]
+///
+/// * **limit** is an [`Option`] field of type [`u32`]
+pub fn query_bonding_capacity(
+    deps: Deps,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<BondingCapacityResponse> {
+    let config = load_config(deps.storage)?;
+    let state = load_state(deps.storage)?;
+    let markets = read_bond_markets(deps.storage, start_after, limit)?;
+
+    let bonding_limits = match config.bonding_limits {
+        Some(bonding_limits) => bonding_limits,
+        None => {
+            let unlimited = |balance: Uint128| BondTypeCapacityResponse {
+                epoch_capacity: None,
+                address_cap: None,
+                bonded_amount: Uint128::zero(),
+                available_amount: balance,
+            };
+
+            return Ok(BondingCapacityResponse {
+                epoch: None,
+                epoch_end_block: None,
+                ust_bond: unlimited(state.ust_bonding_balance),
+                lp_bond: unlimited(state.lp_bonding_balance),
+                bond_markets: markets
+                    .into_iter()
+                    .map(|(market_id, market)| BondMarketCapacityResponse {
+                        market_id,
+                        capacity: unlimited(market.capacity),
+                    })
+                    .collect(),
+            });
+        }
+    };
+
+    let (epoch, epoch_end_block) = query_current_epoch(
+        &deps.querier,
+        deps.api
+            .addr_humanize(&bonding_limits.epoch_manager_contract)?,
+        env.block.height,
+    )?;
+
+    let capacity = |bond_type: BondType,
+                    limits: EpochLimits,
+                    balance: Uint128|
+     -> StdResult<BondTypeCapacityResponse> {
+        let bonded_amount = load_epoch_bonded(deps.storage, &bond_type, epoch)?;
+        let available_amount = match limits.epoch_capacity {
+            Some(epoch_capacity) => epoch_capacity.saturating_sub(bonded_amount).min(balance),
+            None => balance,
+        };
+
+        Ok(BondTypeCapacityResponse {
+            epoch_capacity: limits.epoch_capacity,
+            address_cap: limits.address_cap,
+            bonded_amount,
+            available_amount,
+        })
+    };
+
+    let bond_markets = markets
+        .into_iter()
+        .map(|(market_id, market)| {
+            let limits = market.epoch_limits.unwrap_or(EpochLimits {
+                epoch_capacity: None,
+                address_cap: None,
+            });
+
+            Ok(BondMarketCapacityResponse {
+                market_id,
+                capacity: capacity(BondType::MarketBond { market_id }, limits, market.capacity)?,
+            })
+        })
+        .collect::<StdResult<Vec<BondMarketCapacityResponse>>>()?;
+
+    Ok(BondingCapacityResponse {
+        epoch: Some(epoch),
+        epoch_end_block: Some(epoch_end_block),
+        ust_bond: capacity(
+            BondType::UstBond,
+            bonding_limits.ust_bond,
+            state.ust_bonding_balance,
+        )?,
+        lp_bond: capacity(
+            BondType::LpBond,
+            bonding_limits.lp_bond,
+            state.lp_bonding_balance,
+        )?,
+        bond_markets,
+    })
+}
+
+/// ## Description
+/// Returns information about specified bond market in the [`BondMarketResponse`] object
+/// ## Params
+/// * **deps** is an object of type [`Deps`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **market_id** is a field of type [`u64`]
+pub fn query_bond_market(deps: Deps, env: Env, market_id: u64) -> StdResult<BondMarketResponse> {
+    let market = load_bond_market(deps.storage, market_id)?;
+    bond_market_response(deps.api, env.block.height, market_id, market)
+}
+
+/// ## Description
+/// Returns a list of bond markets in the [`BondMarketsResponse`] object
+/// ## Params
+/// * **deps** is an object of type [`Deps`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **start_after** is an [`Option`] field of type [`u64`]
+///
+/// * **limit** is an [`Option`] field of type [`u32`]
+pub fn query_bond_markets(
+    deps: Deps,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<BondMarketsResponse> {
+    let markets = read_bond_markets(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|(market_id, market)| {
+            bond_market_response(deps.api, env.block.height, market_id, market)
+        })
+        .collect::<StdResult<Vec<BondMarketResponse>>>()?;
+
+    Ok(BondMarketsResponse { markets })
+}
+
+/// ## Description
+/// Returns lifetime bonding totals of specified bonder in the [`BonderResponse`] object
+/// ## Params
+/// * **deps** is an object of type [`Deps`]
+///
+/// * **address** is a field of type [`String`]
+pub fn query_bonder(deps: Deps, address: String) -> StdResult<BonderResponse> {
+    let address_raw = deps.api.addr_canonicalize(&address)?;
+    let bonder = load_bonder(deps.storage, &address_raw)?;
+    Ok(bonder_response(address, bonder))
+}
+
+/// ## Description
+/// Returns a list of bonders with their lifetime bonding totals in the [`BondersResponse`] object
+/// ## Params
+/// * **deps** is an object of type [`Deps`]
+///
+/// * **start_after** is an [`Option`] field of type [`String`]
+///
+/// * **limit** is an [`Option`] field of type [`u32`]
+pub fn query_bonders(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<BondersResponse> {
+    let start_after = start_after
+        .map(|address| deps.api.addr_canonicalize(&address))
+        .transpose()?;
+
+    let bonders = read_bonders(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|(address_raw, bonder)| {
+            Ok(bonder_response(
+                deps.api.addr_humanize(&address_raw)?.to_string(),
+                bonder,
+            ))
+        })
+        .collect::<StdResult<Vec<BonderResponse>>>()?;
+
+    Ok(BondersResponse { bonders })
+}
+
+fn bonder_response(address: String, bonder: BonderInfo) -> BonderResponse {
+    BonderResponse {
+        address,
+        ust_bonded: bonder.ust_bonded,
+        lp_bonded: bonder.lp_bonded,
+        market_bonded: bonder.market_bonded,
+        bro_payout: bonder.bro_payout,
+        bro_claimed: bonder.bro_claimed,
+        bonds_count: bonder.bonds_count,
+    }
+}
+
+/// ## Description
+/// Returns simulated bro bond using specified asset amount of bond market
+/// in the [`SimulateExchangeResponse`] object
+/// ## Params
+/// * **deps** is an object of type [`Deps`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **market_id** is a field of type [`u64`]
+///
+/// * **amount** is an object of type [`Uint128`]
+pub fn simulate_bond(
+    deps: Deps,
+    env: Env,
+    market_id: u64,
+    amount: Uint128,
+) -> StdResult<SimulateExchangeResponse> {
+    let config = load_config(deps.storage)?;
+    let mut market = load_bond_market(deps.storage, market_id)?;
+
+    let bro_amount = query_bond_market_bro_amount(
+        &deps.querier,
+        deps.api,
+        deps.api.addr_humanize(&config.astroport_factory)?,
+        deps.api.addr_humanize(&config.bro_token)?,
+        &market,
+        amount,
+    )?;
+
+    market.decay_debt(env.block.height);
+    let discount = compute_discount(
+        market.discount,
+        &market.discount_control,
+        market.debt,
+        market.capacity,
+    )?;
+
+    let bro_payout = apply_discount(discount, bro_amount)?;
+    let can_be_exchanged =
+        market.enabled && bro_payout >= config.min_bro_payout && bro_payout <= market.capacity;
+
+    let resp = SimulateExchangeResponse {
+        bro_payout,
+        can_be_exchanged,
+        discount,
+    };
+
+    Ok(resp)
+}
+
+/// ## Description
+/// Converts bond market of type [`BondMarket`] into [`BondMarketResponse`] object
+/// with debt decayed up to current block
+fn bond_market_response(
+    api: &dyn Api,
+    current_block: u64,
+    market_id: u64,
+    mut market: BondMarket,
+) -> StdResult<BondMarketResponse> {
+    market.decay_debt(current_block);
+
+    let (pair_contract, paired_asset) = match market.lp_pair {
+        Some(lp_pair) => (
+            Some(api.addr_humanize(&lp_pair.pair_contract)?.to_string()),
+            Some(lp_pair.paired_asset),
+        ),
+        None => (None, None),
+    };
+
+    Ok(BondMarketResponse {
+        market_id,
+        asset_info: market.asset_info,
+        pair_contract,
+        paired_asset,
+        discount: market.discount,
+        capacity: market.capacity,
+        vesting_period_blocks: market.vesting_period_blocks,
+        price_source: market.price_source.to_msg(api)?,
+        enabled: market.enabled,
+        discount_control: market.discount_control,
+        debt: market.debt,
+        epoch_limits: market.epoch_limits,
+        total_bonded: market.total_bonded,
+    })
+}
@@ -1,16 +1,24 @@
-use std::str::FromStr;
+use std::{convert::TryInto, str::FromStr};
 
 use cosmwasm_std::{
-    Api, CanonicalAddr, Decimal, QuerierWrapper, StdError, StdResult, Storage, Uint128,
+    Api, BlockInfo, CanonicalAddr, Decimal, Order, QuerierWrapper, StdError, StdResult, Storage,
+    Uint128,
 };
 use cw20::Expiration;
-use cw_storage_plus::{Item, Map};
+use cw_storage_plus::{Bound, Item, Map, U64Key};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::ContractError;
 
-use services::{bonding::BondingModeMsg, querier::query_staking_config};
+use astroport::asset::AssetInfo;
+use services::{
+    bonding::{
+        BondLockMsg, BondingLimitsMsg, BondingModeMsg, DiscountControl, EpochLimits, LpStakingMsg,
+        MarketBondedAmount, PriceSourceMsg,
+    },
+    querier::query_staking_config,
+};
 
 /// ## Description
 /// Stores config struct of type [`Config`] at the given key
@@ -24,6 +32,28 @@ static STATE: Item<State> = Item::new("state");
 /// A map which stores bonder claims info with [`CanonicalAddr`] type as key and [`Vec<ClaimInfo>`] type as value
 static CLAIMS: Map<&[u8], Vec<ClaimInfo>> = Map::new("claims");
 
+/// ## Description
+/// A map which stores bro amount paid out in epoch with bond type as key and [`EpochBondedAmount`] type as value
+static EPOCH_BONDED: Map<&str, EpochBondedAmount> = Map::new("epoch_bonded");
+
+/// ## Description
+/// A map which stores bro amount paid out in epoch to bonder
+/// with ([`CanonicalAddr`], bond type) as key and [`EpochBondedAmount`] type as value
+static ADDRESS_EPOCH_BONDED: Map<(&[u8], &str), EpochBondedAmount> =
+    Map::new("address_epoch_bonded");
+
+/// ## Description
+/// A map which stores bond markets with [`U64Key`] type as key and [`BondMarket`] type as value
+static BOND_MARKETS: Map<U64Key, BondMarket> = Map::new("bond_markets");
+
+/// ## Description
+/// Stores id of the last registered bond market
+static LAST_BOND_MARKET_ID: Item<u64> = Item::new("last_bond_market_id");
+
+/// ## Description
+/// A map which stores bonder lifetime totals with [`CanonicalAddr`] type as key and [`BonderInfo`] type as value
+static BONDERS: Map<&[u8], BonderInfo> = Map::new("bonders");
+
 /// ## Description
 /// This structure describes the main control config of bonding contract.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -46,6 +76,26 @@ pub struct Config {
     pub min_bro_payout: Uint128,
     /// bonding mode
     pub bonding_mode: BondingMode,
+    /// dynamic discount config,
+    /// if value is set to none
+    /// than bonding discounts are fixed
+    #[serde(default)]
+    pub discount_control: Option<DiscountControl>,
+    /// per epoch bonding limits config,
+    /// if value is set to none
+    /// than bonding is limited only by bonding balance
+    #[serde(default)]
+    pub bonding_limits: Option<BondingLimits>,
+    /// config for locking bond payout in staking contract,
+    /// if value is set to none
+    /// than bonders can't choose lockup period
+    #[serde(default)]
+    pub bond_lock: Option<BondLock>,
+    /// config for staking bonded lp tokens in astroport generator,
+    /// if value is set to none
+    /// than bonded lp tokens are sent to treasury
+    #[serde(default)]
+    pub lp_staking: Option<LpStaking>,
 }
 
 impl Config {
@@ -86,6 +136,51 @@ impl Config {
             BondingMode::Community { .. } => {}
         }
 
+        if let Some(discount_control) = &self.discount_control {
+            if discount_control.min_discount > self.ust_bonding_discount {
+                return Err(StdError::generic_err(
+                    "min_discount must be less than or equal to ust_bonding_discount",
+                ));
+            }
+
+            if let BondingMode::Normal {
+                lp_bonding_discount,
+                ..
+            } = self.bonding_mode
+            {
+                if discount_control.min_discount > lp_bonding_discount {
+                    return Err(StdError::generic_err(
+                        "min_discount must be less than or equal to lp_bonding_discount",
+                    ));
+                }
+            }
+
+            if discount_control.debt_decay_blocks == 0 {
+                return Err(StdError::generic_err(
+                    "debt_decay_blocks must be greater than zero",
+                ));
+            }
+        }
+
+        if let Some(bond_lock) = &self.bond_lock {
+            if bond_lock.max_lock_discount > one {
+                return Err(StdError::generic_err(
+                    "max_lock_discount must be less than 1.0",
+                ));
+            }
+
+            if let BondingMode::Community {
+                staking_contract, ..
+            } = &self.bonding_mode
+            {
+                if bond_lock.staking_contract != *staking_contract {
+                    return Err(StdError::generic_err(
+                        "bond_lock staking_contract must be the same as community bonding mode staking_contract",
+                    ));
+                }
+            }
+        }
+
         Ok(())
     }
 }
@@ -159,6 +254,127 @@ impl BondingMode {
     }
 }
 
+/// ## Description
+/// This structure describes the per epoch bonding limits settings.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BondingLimits {
+    /// epoch-manager contract address
+    pub epoch_manager_contract: CanonicalAddr,
+    /// limits for ust bonding
+    pub ust_bond: EpochLimits,
+    /// limits for lp token bonding
+    pub lp_bond: EpochLimits,
+}
+
+impl BondingLimits {
+    pub fn from_msg(limits: BondingLimitsMsg, api: &dyn Api) -> StdResult<Self> {
+        Ok(BondingLimits {
+            epoch_manager_contract: api.addr_canonicalize(&limits.epoch_manager_contract)?,
+            ust_bond: limits.ust_bond,
+            lp_bond: limits.lp_bond,
+        })
+    }
+
+    pub fn to_msg(&self, api: &dyn Api) -> StdResult<BondingLimitsMsg> {
+        Ok(BondingLimitsMsg {
+            epoch_manager_contract: api.addr_humanize(&self.epoch_manager_contract)?.to_string(),
+            ust_bond: self.ust_bond.clone(),
+            lp_bond: self.lp_bond.clone(),
+        })
+    }
+}
+
+/// ## Description
+/// This structure describes the settings for locking bond payout in staking contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BondLock {
+    /// staking contract address
+    pub staking_contract: CanonicalAddr,
+    /// extra discount for locking bond payout for max lockup period
+    pub max_lock_discount: Decimal,
+}
+
+impl BondLock {
+    pub fn from_msg(bond_lock: BondLockMsg, api: &dyn Api) -> StdResult<Self> {
+        Ok(BondLock {
+            staking_contract: api.addr_canonicalize(&bond_lock.staking_contract)?,
+            max_lock_discount: bond_lock.max_lock_discount,
+        })
+    }
+
+    pub fn to_msg(&self, api: &dyn Api) -> StdResult<BondLockMsg> {
+        Ok(BondLockMsg {
+            staking_contract: api.addr_humanize(&self.staking_contract)?.to_string(),
+            max_lock_discount: self.max_lock_discount,
+        })
+    }
+}
+
+/// ## Description
+/// This structure describes the settings for staking bonded lp tokens in astroport generator.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LpStaking {
+    /// astroport generator contract address
+    pub generator_contract: CanonicalAddr,
+}
+
+impl LpStaking {
+    pub fn from_msg(lp_staking: LpStakingMsg, api: &dyn Api) -> StdResult<Self> {
+        Ok(LpStaking {
+            generator_contract: api.addr_canonicalize(&lp_staking.generator_contract)?,
+        })
+    }
+
+    pub fn to_msg(&self, api: &dyn Api) -> StdResult<LpStakingMsg> {
+        Ok(LpStakingMsg {
+            generator_contract: api.addr_humanize(&self.generator_contract)?.to_string(),
+        })
+    }
+}
+
+/// ## Description
+/// This structure describes lifetime bonding totals of bonder.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct BonderInfo {
+    /// uusd amount bonded via ust bonding
+    pub ust_bonded: Uint128,
+    /// lp token amount bonded via lp token bonding
+    pub lp_bonded: Uint128,
+    /// asset amounts bonded via bond markets
+    #[serde(default)]
+    pub market_bonded: Vec<MarketBondedAmount>,
+    /// bro amount paid out to bonder
+    pub bro_payout: Uint128,
+    /// bro amount claimed by bonder
+    pub bro_claimed: Uint128,
+    /// number of bonds
+    pub bonds_count: u64,
+}
+
+/// ## Description
+/// This structure describes bro amount paid out in epoch.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct EpochBondedAmount {
+    /// epoch of last bond
+    pub epoch: u64,
+    /// bro amount paid out in epoch
+    pub amount: Uint128,
+}
+
+impl EpochBondedAmount {
+    /// ## Description
+    /// Returns paid out amount if stored epoch equals to specified one, otherwise returns zero
+    /// ## Params
+    /// * **epoch** is a field of type [`u64`]
+    pub fn amount_at(&self, epoch: u64) -> Uint128 {
+        if self.epoch == epoch {
+            self.amount
+        } else {
+            Uint128::zero()
+        }
+    }
+}
+
 /// ## Description
 /// This structure describes state of bonding contract.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -167,6 +383,30 @@ pub struct State {
     pub ust_bonding_balance: Uint128,
     /// available bro balance for lp token bonding
     pub lp_bonding_balance: Uint128,
+    /// decayed bro amount bonded via ust bonding
+    #[serde(default)]
+    pub ust_bonding_debt: Uint128,
+    /// decayed bro amount bonded via lp token bonding
+    #[serde(default)]
+    pub lp_bonding_debt: Uint128,
+    /// block at which bonding debt was last decayed
+    #[serde(default)]
+    pub last_debt_decay_block: u64,
+    /// total uusd amount bonded via ust bonding
+    #[serde(default)]
+    pub total_ust_bonded: Uint128,
+    /// total lp token amount bonded via lp token bonding
+    #[serde(default)]
+    pub total_lp_bonded: Uint128,
+    /// total bro amount paid out to bonders
+    #[serde(default)]
+    pub total_bro_payout: Uint128,
+    /// total bro amount claimed by bonders
+    #[serde(default)]
+    pub total_bro_claimed: Uint128,
+    /// total number of bonds
+    #[serde(default)]
+    pub bonds_count: u64,
 }
 
 impl Default for State {
@@ -174,7 +414,90 @@ impl Default for State {
         State {
             ust_bonding_balance: Uint128::zero(),
             lp_bonding_balance: Uint128::zero(),
+            ust_bonding_debt: Uint128::zero(),
+            lp_bonding_debt: Uint128::zero(),
+            last_debt_decay_block: 0,
+            total_ust_bonded: Uint128::zero(),
+            total_lp_bonded: Uint128::zero(),
+            total_bro_payout: Uint128::zero(),
+            total_bro_claimed: Uint128::zero(),
+            bonds_count: 0,
+        }
+    }
+}
+
+impl State {
+    /// ## Description
+    /// Adds bond to total bonded amounts and bro payout
+    /// ## Params
+    /// * **bond_type** is an object of type [`BondType`]
+    ///
+    /// * **bond_amount** is an object of type [`Uint128`]
+    ///
+    /// * **bro_payout** is an object of type [`Uint128`]
+    pub fn record_bond(
+        &mut self,
+        bond_type: &BondType,
+        bond_amount: Uint128,
+        bro_payout: Uint128,
+    ) -> StdResult<()> {
+        match bond_type {
+            BondType::UstBond => {
+                self.total_ust_bonded = self.total_ust_bonded.checked_add(bond_amount)?
+            }
+            BondType::LpBond => {
+                self.total_lp_bonded = self.total_lp_bonded.checked_add(bond_amount)?
+            }
+            // bonded assets differ between markets,
+            // so their totals are tracked in bond markets
+            BondType::MarketBond { .. } => {}
         }
+
+        self.total_bro_payout = self.total_bro_payout.checked_add(bro_payout)?;
+        self.bonds_count += 1;
+        Ok(())
+    }
+
+    /// ## Description
+    /// Decays bonding debt linearly over debt decay period.
+    /// Debt is reset if dynamic discount is disabled
+    /// ## Params
+    /// * **discount_control** is an [`Option`] of type [`DiscountControl`]
+    ///
+    /// * **current_block** is a field of type [`u64`]
+    pub fn decay_bonding_debt(
+        &mut self,
+        discount_control: &Option<DiscountControl>,
+        current_block: u64,
+    ) {
+        let passed_blocks = current_block.saturating_sub(self.last_debt_decay_block);
+        self.ust_bonding_debt = decay_debt(self.ust_bonding_debt, discount_control, passed_blocks);
+        self.lp_bonding_debt = decay_debt(self.lp_bonding_debt, discount_control, passed_blocks);
+        self.last_debt_decay_block = current_block;
+    }
+}
+
+/// ## Description
+/// Returns debt decayed linearly over debt decay period or zero if dynamic discount is disabled
+/// ## Params
+/// * **debt** is an object of type [`Uint128`]
+///
+/// * **discount_control** is an [`Option`] of type [`DiscountControl`]
+///
+/// * **passed_blocks** is a field of type [`u64`]
+fn decay_debt(
+    debt: Uint128,
+    discount_control: &Option<DiscountControl>,
+    passed_blocks: u64,
+) -> Uint128 {
+    match discount_control {
+        Some(DiscountControl {
+            debt_decay_blocks, ..
+        }) => debt.multiply_ratio(
+            debt_decay_blocks.saturating_sub(passed_blocks),
+            *debt_decay_blocks,
+        ),
+        None => Uint128::zero(),
     }
 }
 
@@ -187,6 +510,11 @@ pub enum BondType {
     UstBond,
     /// lp token bond type
     LpBond,
+    /// bond market bond type
+    MarketBond {
+        /// bond market id
+        market_id: u64,
+    },
 }
 
 impl ToString for BondType {
@@ -194,7 +522,137 @@ impl ToString for BondType {
         match self {
             BondType::UstBond => "ust_bond".to_string(),
             BondType::LpBond => "lp_bond".to_string(),
+            BondType::MarketBond { market_id } => format!("market_bond_{}", market_id),
+        }
+    }
+}
+
+/// ## Description
+/// This structure describes the source of bro price for bond market asset.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceSource {
+    /// price oracle contract which returns bro amount for specified asset amount
+    Oracle { oracle_contract: CanonicalAddr },
+    /// fixed amount of bro for one unit of asset
+    Fixed { bro_per_asset: Decimal },
+}
+
+impl PriceSource {
+    pub fn from_msg(price_source: PriceSourceMsg, api: &dyn Api) -> StdResult<Self> {
+        match price_source {
+            PriceSourceMsg::Oracle { oracle_contract } => Ok(PriceSource::Oracle {
+                oracle_contract: api.addr_canonicalize(&oracle_contract)?,
+            }),
+            PriceSourceMsg::Fixed { bro_per_asset } => Ok(PriceSource::Fixed { bro_per_asset }),
+        }
+    }
+
+    pub fn to_msg(&self, api: &dyn Api) -> StdResult<PriceSourceMsg> {
+        match self {
+            PriceSource::Oracle { oracle_contract } => Ok(PriceSourceMsg::Oracle {
+                oracle_contract: api.addr_humanize(oracle_contract)?.to_string(),
+            }),
+            PriceSource::Fixed { bro_per_asset } => Ok(PriceSourceMsg::Fixed {
+                bro_per_asset: *bro_per_asset,
+            }),
+        }
+    }
+}
+
+/// ## Description
+/// This structure describes astroport pair of bro token and paired asset.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LpPair {
+    /// astroport pair address
+    pub pair_contract: CanonicalAddr,
+    /// asset paired with bro token
+    pub paired_asset: AssetInfo,
+}
+
+/// ## Description
+/// This structure describes bond market of bonding contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BondMarket {
+    /// asset accepted by bond market
+    pub asset_info: AssetInfo,
+    /// astroport pair info if accepted asset is lp token
+    pub lp_pair: Option<LpPair>,
+    /// discount percentage for bonding
+    pub discount: Decimal,
+    /// available bro balance for bonding
+    pub capacity: Uint128,
+    /// vesting period for withdrawal
+    pub vesting_period_blocks: u64,
+    /// source of bro price for bonded asset
+    pub price_source: PriceSource,
+    /// whether bonding is enabled
+    pub enabled: bool,
+    /// dynamic discount settings based on bond market debt
+    #[serde(default)]
+    pub discount_control: Option<DiscountControl>,
+    /// bro payout of bond market which decays over debt decay period
+    #[serde(default)]
+    pub debt: Uint128,
+    /// block at which bond market debt was decayed last time
+    #[serde(default)]
+    pub last_debt_decay_block: u64,
+    /// per epoch limits of bond market
+    #[serde(default)]
+    pub epoch_limits: Option<EpochLimits>,
+    /// total asset amount bonded via bond market
+    #[serde(default)]
+    pub total_bonded: Uint128,
+}
+
+impl BondMarket {
+    pub fn validate(&self) -> StdResult<()> {
+        if self.discount > Decimal::from_str("1.0")? || self.discount <= Decimal::zero() {
+            return Err(StdError::generic_err(
+                "bond market discount must be less than 1.0 and non-negative",
+            ));
+        }
+
+        if self.vesting_period_blocks == 0 {
+            return Err(StdError::generic_err(
+                "vesting_period_blocks must be greater than zero",
+            ));
+        }
+
+        if let PriceSource::Fixed { bro_per_asset } = self.price_source {
+            if bro_per_asset.is_zero() {
+                return Err(StdError::generic_err(
+                    "bro_per_asset must be greater than zero",
+                ));
+            }
+        }
+
+        if let Some(discount_control) = &self.discount_control {
+            if discount_control.min_discount > self.discount {
+                return Err(StdError::generic_err(
+                    "min_discount must be less than or equal to bond market discount",
+                ));
+            }
+
+            if discount_control.debt_decay_blocks == 0 {
+                return Err(StdError::generic_err(
+                    "debt_decay_blocks must be greater than zero",
+                ));
+            }
         }
+
+        Ok(())
+    }
+
+    /// ## Description
+    /// Decays bond market debt linearly over debt decay period.
+    /// Debt is reset if dynamic discount is disabled
+    /// ## Params
+    /// * **current_block** is a field of type [`u64`]
+    pub fn decay_debt(&mut self, current_block: u64) {
+        let passed_blocks = current_block.saturating_sub(self.last_debt_decay_block);
+        self.debt = decay_debt(self.debt, &self.discount_control, passed_blocks);
+        self.last_debt_decay_block = current_block;
     }
 }
 
@@ -206,8 +664,46 @@ pub struct ClaimInfo {
     pub bond_type: BondType,
     /// amount to claim
     pub amount: Uint128,
-    /// block at which amount can be claimed
+    /// block at which amount is fully vested
     pub claimable_at: Expiration,
+    /// block at which linear vesting started,
+    /// if value is set to none
+    /// than whole amount is vested at claimable_at block
+    #[serde(default)]
+    pub vesting_start_block: Option<u64>,
+    /// already claimed amount
+    #[serde(default)]
+    pub claimed_amount: Uint128,
+}
+
+impl ClaimInfo {
+    /// ## Description
+    /// Returns amount vested linearly between vesting start block and claimable_at block
+    /// ## Params
+    /// * **block** is an object of type [`BlockInfo`]
+    pub fn vested_amount(&self, block: &BlockInfo) -> Uint128 {
+        if self.claimable_at.is_expired(block) {
+            return self.amount;
+        }
+
+        match (self.vesting_start_block, &self.claimable_at) {
+            (Some(start_block), Expiration::AtHeight(end_block)) if *end_block > start_block => {
+                self.amount.multiply_ratio(
+                    block.height.saturating_sub(start_block),
+                    end_block - start_block,
+                )
+            }
+            _ => Uint128::zero(),
+        }
+    }
+
+    /// ## Description
+    /// Returns vested amount which is not claimed yet
+    /// ## Params
+    /// * **block** is an object of type [`BlockInfo`]
+    pub fn claimable_amount(&self, block: &BlockInfo) -> StdResult<Uint128> {
+        Ok(self.vested_amount(block).checked_sub(self.claimed_amount)?)
+    }
 }
 
 /// ## Description
@@ -289,3 +785,268 @@ pub fn load_claims(storage: &dyn Storage, account: &CanonicalAddr) -> StdResult<
         .may_load(storage, account.as_slice())
         .map(|res| res.unwrap_or_default())
 }
+
+/// ## Description
+/// Returns bro amount of specified bond type paid out in specified epoch
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **bond_type** is an object of type [`BondType`]
+///
+/// * **epoch** is a field of type [`u64`]
+pub fn load_epoch_bonded(
+    storage: &dyn Storage,
+    bond_type: &BondType,
+    epoch: u64,
+) -> StdResult<Uint128> {
+    Ok(EPOCH_BONDED
+        .may_load(storage, &bond_type.to_string())?
+        .unwrap_or_default()
+        .amount_at(epoch))
+}
+
+/// ## Description
+/// Adds bro payout to amounts paid out in specified epoch for bond type and bonder.
+/// Returns [`ContractError`] if epoch capacity or address cap is exceeded
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **account** is an object of type [`CanonicalAddr`]
+///
+/// * **bond_type** is an object of type [`BondType`]
+///
+/// * **limits** is an object of type [`EpochLimits`]
+///
+/// * **epoch** is a field of type [`u64`]
+///
+/// * **bro_payout** is an object of type [`Uint128`]
+pub fn record_epoch_bond(
+    storage: &mut dyn Storage,
+    account: &CanonicalAddr,
+    bond_type: &BondType,
+    limits: &EpochLimits,
+    epoch: u64,
+    bro_payout: Uint128,
+) -> Result<(), ContractError> {
+    let bond_type = bond_type.to_string();
+
+    let epoch_bonded = EPOCH_BONDED
+        .may_load(storage, &bond_type)?
+        .unwrap_or_default()
+        .amount_at(epoch)
+        .checked_add(bro_payout)?;
+    if let Some(epoch_capacity) = limits.epoch_capacity {
+        if epoch_bonded > epoch_capacity {
+            return Err(ContractError::EpochBondingCapacityExceeded {});
+        }
+    }
+
+    let address_epoch_bonded = ADDRESS_EPOCH_BONDED
+        .may_load(storage, (account.as_slice(), &bond_type))?
+        .unwrap_or_default()
+        .amount_at(epoch)
+        .checked_add(bro_payout)?;
+    if let Some(address_cap) = limits.address_cap {
+        if address_epoch_bonded > address_cap {
+            return Err(ContractError::AddressBondingCapExceeded {});
+        }
+    }
+
+    EPOCH_BONDED.save(
+        storage,
+        &bond_type,
+        &EpochBondedAmount {
+            epoch,
+            amount: epoch_bonded,
+        },
+    )?;
+    ADDRESS_EPOCH_BONDED.save(
+        storage,
+        (account.as_slice(), &bond_type),
+        &EpochBondedAmount {
+            epoch,
+            amount: address_epoch_bonded,
+        },
+    )?;
+
+    Ok(())
+}
+
+/// ## Description
+/// Saves new bond market in [`BOND_MARKETS`] map and returns its id
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **market** is an object of type [`BondMarket`]
+pub fn register_bond_market(storage: &mut dyn Storage, market: &BondMarket) -> StdResult<u64> {
+    let market_id = LAST_BOND_MARKET_ID.may_load(storage)?.unwrap_or_default() + 1;
+    BOND_MARKETS.save(storage, U64Key::from(market_id), market)?;
+    LAST_BOND_MARKET_ID.save(storage, &market_id)?;
+
+    Ok(market_id)
+}
+
+/// ## Description
+/// Saves changes of [`BondMarket`] struct in [`BOND_MARKETS`] map for specified market id
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **market_id** is a field of type [`u64`]
+///
+/// * **market** is an object of type [`BondMarket`]
+pub fn store_bond_market(
+    storage: &mut dyn Storage,
+    market_id: u64,
+    market: &BondMarket,
+) -> StdResult<()> {
+    BOND_MARKETS.save(storage, U64Key::from(market_id), market)
+}
+
+/// ## Description
+/// Returns bond market object of type [`BondMarket`] by specified market id from map [`BOND_MARKETS`]
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **market_id** is a field of type [`u64`]
+pub fn load_bond_market(storage: &dyn Storage, market_id: u64) -> StdResult<BondMarket> {
+    BOND_MARKETS.load(storage, U64Key::from(market_id))
+}
+
+/// ## Description
+/// Returns the empty vector if does not found data to read, otherwise returns the vector that
+/// contains bond markets with objects of type [`BondMarket`] in ascending order of market id
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **start_after** is an [`Option`] field of type [`u64`]. Sets the market id to start reading after
+///
+/// * **limit** is an [`Option`] field of type [`u32`]. Sets the limit to reading
+pub fn read_bond_markets(
+    storage: &dyn Storage,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, BondMarket)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|market_id| Bound::exclusive(U64Key::from(market_id)));
+
+    BOND_MARKETS
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, market) = item?;
+            Ok((u64_from_key(&k)?, market))
+        })
+        .collect()
+}
+
+/// ## Description
+/// Adds bond to lifetime totals of specified bonder
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **account** is an object of type [`CanonicalAddr`]
+///
+/// * **bond_type** is an object of type [`BondType`]
+///
+/// * **bond_amount** is an object of type [`Uint128`]
+///
+/// * **bro_payout** is an object of type [`Uint128`]
+pub fn record_bonder_bond(
+    storage: &mut dyn Storage,
+    account: &CanonicalAddr,
+    bond_type: &BondType,
+    bond_amount: Uint128,
+    bro_payout: Uint128,
+) -> StdResult<()> {
+    let mut bonder = load_bonder(storage, account)?;
+    match bond_type {
+        BondType::UstBond => bonder.ust_bonded = bonder.ust_bonded.checked_add(bond_amount)?,
+        BondType::LpBond => bonder.lp_bonded = bonder.lp_bonded.checked_add(bond_amount)?,
+        BondType::MarketBond { market_id } => {
+            match bonder
+                .market_bonded
+                .iter_mut()
+                .find(|market_bonded| market_bonded.market_id == *market_id)
+            {
+                Some(market_bonded) => {
+                    market_bonded.amount = market_bonded.amount.checked_add(bond_amount)?
+                }
+                None => bonder.market_bonded.push(MarketBondedAmount {
+                    market_id: *market_id,
+                    amount: bond_amount,
+                }),
+            }
+        }
+    }
+
+    bonder.bro_payout = bonder.bro_payout.checked_add(bro_payout)?;
+    bonder.bonds_count += 1;
+    BONDERS.save(storage, account.as_slice(), &bonder)
+}
+
+/// ## Description
+/// Adds claimed amount to lifetime totals of specified bonder
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **account** is an object of type [`CanonicalAddr`]
+///
+/// * **amount** is an object of type [`Uint128`]
+pub fn record_bonder_claim(
+    storage: &mut dyn Storage,
+    account: &CanonicalAddr,
+    amount: Uint128,
+) -> StdResult<()> {
+    let mut bonder = load_bonder(storage, account)?;
+    bonder.bro_claimed = bonder.bro_claimed.checked_add(amount)?;
+    BONDERS.save(storage, account.as_slice(), &bonder)
+}
+
+/// ## Description
+/// Returns lifetime totals of specified bonder, default object if bonder is not found
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **account** is an object of type [`CanonicalAddr`]
+pub fn load_bonder(storage: &dyn Storage, account: &CanonicalAddr) -> StdResult<BonderInfo> {
+    BONDERS
+        .may_load(storage, account.as_slice())
+        .map(|res| res.unwrap_or_default())
+}
+
+/// ## Description
+/// Returns the empty vector if does not found data to read, otherwise returns the vector that
+/// contains bonders with objects of type [`BonderInfo`] in ascending order of bonder address
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **start_after** is an [`Option`] field of type [`CanonicalAddr`]. Sets the bonder address to start reading after
+///
+/// * **limit** is an [`Option`] field of type [`u32`]. Sets the limit to reading
+pub fn read_bonders(
+    storage: &dyn Storage,
+    start_after: Option<CanonicalAddr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(CanonicalAddr, BonderInfo)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|account| Bound::exclusive(account.as_slice()));
+
+    BONDERS
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, bonder) = item?;
+            Ok((CanonicalAddr::from(k), bonder))
+        })
+        .collect()
+}
+
+fn u64_from_key(key: &[u8]) -> StdResult<u64> {
+    Ok(u64::from_be_bytes(
+        key.try_into()
+            .map_err(|_| StdError::generic_err("invalid u64 key"))?,
+    ))
+}
+
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
@@ -1,25 +1,36 @@
 use std::str::FromStr;
 
-use crate::contract::{execute, instantiate, query};
+use crate::contract::{execute, instantiate, migrate, query};
 use crate::error::ContractError;
-use crate::state::{load_claims, store_claims, BondType, ClaimInfo};
-use cosmwasm_std::testing::{mock_env, mock_info};
+use crate::state::{load_claims, load_state, store_claims, store_state, BondType, ClaimInfo};
+use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    from_binary, to_binary, Attribute, BankMsg, Coin, CosmosMsg, Decimal, StdError, SubMsg,
-    Uint128, WasmMsg,
+    from_binary, to_binary, Addr, Attribute, BankMsg, Binary, Coin, CosmosMsg, Decimal, Deps, Env,
+    StdError, SubMsg, Uint128, WasmMsg,
 };
+use cw2::set_contract_version;
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, Expiration};
 use services::bonding::SimulateExchangeResponse;
 
+use astroport::{
+    asset::AssetInfo,
+    generator::{Cw20HookMsg as GeneratorCw20HookMsg, ExecuteMsg as GeneratorExecuteMsg},
+};
+
 use crate::mock_querier::{
-    mock_dependencies, MOCK_ASTRO_FACTORY_ADDR, MOCK_BRO_TOKEN_ADDR, MOCK_BRO_UST_PAIR_ADDR,
-    MOCK_LP_TOKEN_ADDR, MOCK_ORACLE_ADDR, MOCK_STAKING_ADDR,
+    mock_dependencies, MOCK_ASTRO_FACTORY_ADDR, MOCK_ASTRO_TOKEN_ADDR, MOCK_BRO_TOKEN_ADDR,
+    MOCK_BRO_UST_PAIR_ADDR, MOCK_EPOCH_MANAGER_ADDR, MOCK_GENERATOR_ADDR, MOCK_LP_TOKEN_ADDR,
+    MOCK_ORACLE_ADDR, MOCK_PROXY_REWARD_TOKEN_ADDR, MOCK_STAKING_ADDR,
 };
 
 use services::{
     bonding::{
-        BondingModeMsg, ClaimInfoResponse, ClaimsResponse, ConfigResponse, Cw20HookMsg, ExecuteMsg,
-        InstantiateMsg, QueryMsg, StateResponse,
+        BondLockMsg, BondMarketAssetMsg, BondMarketCapacityResponse, BondMarketResponse,
+        BondMarketsResponse, BondTypeCapacityResponse, BonderResponse, BondersResponse,
+        BondingCapacityResponse, BondingLimitsMsg, BondingModeMsg, ClaimInfoResponse,
+        ClaimsResponse, ConfigResponse, Cw20HookMsg, DiscountControl, EpochLimits, ExecuteMsg,
+        InstantiateMsg, LpStakingMsg, MarketBondedAmount, MigrateMsg, PriceSourceMsg, QueryMsg,
+        StateResponse,
     },
     oracle::ExecuteMsg as OracleExecuteMsg,
     ownership_proposal::OwnershipProposalResponse,
@@ -45,6 +56,17 @@ use services::{
 ///     liquidity_token: Addr::unchecked(MOCK_LP_TOKEN_ADDR),
 ///     pair_type: astroport::factory::PairType::Xyk {},
 /// }
+///
+/// epoch-manager contract:
+/// mock address: epoch_manager
+///
+/// * **EpochManagerQueryMsg::EpochInfo {}** returns:
+///
+/// EpochInfoResponse {
+///     epoch: 100,
+///     blocks_per_year: 1000,
+///     bbro_emission_rate: Decimal::one(),
+/// }
 
 #[test]
 fn proper_initialization() {
@@ -67,6 +89,10 @@ fn proper_initialization() {
             lp_bonding_discount: Decimal::from_str("1.05").unwrap(),
             vesting_period_blocks: 10,
         },
+        discount_control: None,
+        bonding_limits: None,
+        bond_lock: None,
+        lp_staking: None,
     };
 
     let info = mock_info("addr0001", &[]);
@@ -143,6 +169,10 @@ fn proper_initialization() {
                 lp_bonding_discount: Decimal::from_str("0.05").unwrap(),
                 vesting_period_blocks: 10,
             },
+            discount_control: None,
+            bonding_limits: None,
+            bond_lock: None,
+            lp_staking: None,
         },
     );
 
@@ -154,6 +184,13 @@ fn proper_initialization() {
         StateResponse {
             ust_bonding_balance: Uint128::zero(),
             lp_bonding_balance: Uint128::zero(),
+            ust_bonding_debt: Uint128::zero(),
+            lp_bonding_debt: Uint128::zero(),
+            total_ust_bonded: Uint128::zero(),
+            total_lp_bonded: Uint128::zero(),
+            total_bro_payout: Uint128::zero(),
+            total_bro_claimed: Uint128::zero(),
+            bonds_count: 0,
         },
     );
 
@@ -172,6 +209,10 @@ fn proper_initialization() {
             staking_contract: MOCK_STAKING_ADDR.to_string(),
             epochs_locked: 800,
         },
+        discount_control: None,
+        bonding_limits: None,
+        bond_lock: None,
+        lp_staking: None,
     };
 
     let info = mock_info("addr0001", &[]);
@@ -181,6 +222,28 @@ fn proper_initialization() {
         _ => panic!("DO NOT ENTER HERE!"),
     }
 
+    // error: bond lock staking contract differs from community bonding mode staking contract
+    let msg = InstantiateMsg {
+        bonding_mode: BondingModeMsg::Community {
+            staking_contract: MOCK_STAKING_ADDR.to_string(),
+            epochs_locked: 500,
+        },
+        bond_lock: Some(BondLockMsg {
+            staking_contract: "staking0001".to_string(),
+            max_lock_discount: Decimal::from_str("0.5").unwrap(),
+        }),
+        ..msg
+    };
+    let info = mock_info("addr0001", &[]);
+    let res = instantiate(deps.as_mut(), mock_env(), info, msg);
+    match res {
+        Err(ContractError::Std(StdError::GenericErr { msg, .. })) => assert_eq!(
+            msg,
+            "bond_lock staking_contract must be the same as community bonding mode staking_contract"
+        ),
+        _ => panic!("DO NOT ENTER HERE!"),
+    }
+
     // proper initialization
     let msg = InstantiateMsg {
         owner: "owner".to_string(),
@@ -195,6 +258,10 @@ fn proper_initialization() {
             staking_contract: MOCK_STAKING_ADDR.to_string(),
             epochs_locked: 500,
         },
+        discount_control: None,
+        bonding_limits: None,
+        bond_lock: None,
+        lp_staking: None,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -218,6 +285,10 @@ fn proper_initialization() {
                 staking_contract: MOCK_STAKING_ADDR.to_string(),
                 epochs_locked: 500,
             },
+            discount_control: None,
+            bonding_limits: None,
+            bond_lock: None,
+            lp_staking: None,
         },
     );
 
@@ -229,6 +300,13 @@ fn proper_initialization() {
         StateResponse {
             ust_bonding_balance: Uint128::zero(),
             lp_bonding_balance: Uint128::zero(),
+            ust_bonding_debt: Uint128::zero(),
+            lp_bonding_debt: Uint128::zero(),
+            total_ust_bonded: Uint128::zero(),
+            total_lp_bonded: Uint128::zero(),
+            total_bro_payout: Uint128::zero(),
+            total_bro_claimed: Uint128::zero(),
+            bonds_count: 0,
         },
     );
 }
@@ -251,6 +329,10 @@ fn distribute_reward() {
             lp_bonding_discount: Decimal::from_str("0.05").unwrap(),
             vesting_period_blocks: 10,
         },
+        discount_control: None,
+        bonding_limits: None,
+        bond_lock: None,
+        lp_staking: None,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -296,6 +378,13 @@ fn distribute_reward() {
         StateResponse {
             ust_bonding_balance: Uint128::from(60u128),
             lp_bonding_balance: Uint128::from(40u128),
+            ust_bonding_debt: Uint128::zero(),
+            lp_bonding_debt: Uint128::zero(),
+            total_ust_bonded: Uint128::zero(),
+            total_lp_bonded: Uint128::zero(),
+            total_bro_payout: Uint128::zero(),
+            total_bro_claimed: Uint128::zero(),
+            bonds_count: 0,
         },
     );
 
@@ -314,6 +403,10 @@ fn distribute_reward() {
             staking_contract: MOCK_STAKING_ADDR.to_string(),
             epochs_locked: 10,
         },
+        discount_control: None,
+        bonding_limits: None,
+        bond_lock: None,
+        lp_staking: None,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -336,6 +429,13 @@ fn distribute_reward() {
         StateResponse {
             ust_bonding_balance: Uint128::from(100u128),
             lp_bonding_balance: Uint128::zero(),
+            ust_bonding_debt: Uint128::zero(),
+            lp_bonding_debt: Uint128::zero(),
+            total_ust_bonded: Uint128::zero(),
+            total_lp_bonded: Uint128::zero(),
+            total_bro_payout: Uint128::zero(),
+            total_bro_claimed: Uint128::zero(),
+            bonds_count: 0,
         },
     );
 }
@@ -374,6 +474,10 @@ fn lp_bond() {
             lp_bonding_discount: Decimal::from_str("0.05").unwrap(),
             vesting_period_blocks: 10,
         },
+        discount_control: None,
+        bonding_limits: None,
+        bond_lock: None,
+        lp_staking: None,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -392,7 +496,11 @@ fn lp_bond() {
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: "addr0000".to_string(),
         amount: Uint128::from(1_000_000000u128),
-        msg: to_binary(&Cw20HookMsg::LpBond {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::LpBond {
+            min_bro_payout: None,
+            lock_epochs: None,
+        })
+        .unwrap(),
     });
 
     let info = mock_info("addr0001", &[]);
@@ -406,7 +514,11 @@ fn lp_bond() {
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: "addr0000".to_string(),
         amount: Uint128::from(10_000000u128),
-        msg: to_binary(&Cw20HookMsg::LpBond {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::LpBond {
+            min_bro_payout: None,
+            lock_epochs: None,
+        })
+        .unwrap(),
     });
     let info = mock_info(MOCK_LP_TOKEN_ADDR, &[]);
     let res = execute(deps.as_mut(), mock_env(), info, msg.clone());
@@ -419,7 +531,11 @@ fn lp_bond() {
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: "addr0000".to_string(),
         amount: Uint128::from(1_000_000000u128),
-        msg: to_binary(&Cw20HookMsg::LpBond {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::LpBond {
+            min_bro_payout: None,
+            lock_epochs: None,
+        })
+        .unwrap(),
     });
     let info = mock_info(MOCK_LP_TOKEN_ADDR, &[]);
     let res = execute(deps.as_mut(), mock_env(), info, msg.clone());
@@ -437,6 +553,10 @@ fn lp_bond() {
         astroport_factory: None,
         oracle_contract: None,
         ust_bonding_discount: None,
+        discount_control: None,
+        bonding_limits: None,
+        bond_lock: None,
+        lp_staking: None,
     };
     let info = mock_info("owner", &[]);
     let _res = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap();
@@ -449,6 +569,7 @@ fn lp_bond() {
                 mock_env(),
                 QueryMsg::SimulateLpBond {
                     lp_amount: Uint128::from(10_000000u128),
+                    lock_epochs: None,
                 }
             )
             .unwrap()
@@ -457,6 +578,7 @@ fn lp_bond() {
         SimulateExchangeResponse {
             bro_payout: Uint128::from(2_100000u128),
             can_be_exchanged: true,
+            discount: Decimal::from_str("0.05").unwrap(),
         },
     );
 
@@ -464,7 +586,11 @@ fn lp_bond() {
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: "addr0000".to_string(),
         amount: Uint128::from(10_000000u128),
-        msg: to_binary(&Cw20HookMsg::LpBond {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::LpBond {
+            min_bro_payout: None,
+            lock_epochs: None,
+        })
+        .unwrap(),
     });
 
     let info = mock_info(MOCK_LP_TOKEN_ADDR, &[]);
@@ -502,6 +628,13 @@ fn lp_bond() {
         StateResponse {
             ust_bonding_balance: Uint128::from(60_000000u128),
             lp_bonding_balance: Uint128::from(37_900000u128),
+            ust_bonding_debt: Uint128::zero(),
+            lp_bonding_debt: Uint128::zero(),
+            total_ust_bonded: Uint128::zero(),
+            total_lp_bonded: Uint128::from(10_000000u128),
+            total_bro_payout: Uint128::from(2_100000u128),
+            total_bro_claimed: Uint128::zero(),
+            bonds_count: 1,
         },
     );
 
@@ -522,6 +655,10 @@ fn lp_bond() {
                 bond_type: "lp_bond".to_string(),
                 amount: Uint128::from(2_100000u128),
                 claimable_at: Expiration::AtHeight(12_345 + 10),
+                vested_amount: Uint128::zero(),
+                claimed_amount: Uint128::zero(),
+                claimable_amount: Uint128::zero(),
+                remaining_amount: Uint128::from(2_100000u128),
             }],
         },
     );
@@ -540,6 +677,10 @@ fn lp_bond() {
             staking_contract: MOCK_STAKING_ADDR.to_string(),
             epochs_locked: 10,
         },
+        discount_control: None,
+        bonding_limits: None,
+        bond_lock: None,
+        lp_staking: None,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -549,7 +690,11 @@ fn lp_bond() {
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: "addr0000".to_string(),
         amount: Uint128::from(10_000000u128),
-        msg: to_binary(&Cw20HookMsg::LpBond {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::LpBond {
+            min_bro_payout: None,
+            lock_epochs: None,
+        })
+        .unwrap(),
     });
     let info = mock_info(MOCK_LP_TOKEN_ADDR, &[]);
     let res = execute(deps.as_mut(), mock_env(), info, msg.clone());
@@ -593,6 +738,10 @@ fn ust_bond_normal_mode() {
             lp_bonding_discount: Decimal::from_str("0.05").unwrap(),
             vesting_period_blocks: 10,
         },
+        discount_control: None,
+        bonding_limits: None,
+        bond_lock: None,
+        lp_staking: None,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -608,7 +757,10 @@ fn ust_bond_normal_mode() {
     let _res = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap();
 
     // error: invalid funds input
-    let msg = ExecuteMsg::UstBond {};
+    let msg = ExecuteMsg::UstBond {
+        min_bro_payout: None,
+        lock_epochs: None,
+    };
     let info = mock_info(
         "addr0000",
         &[
@@ -655,7 +807,10 @@ fn ust_bond_normal_mode() {
     }
 
     // error: bond payout is low
-    let msg = ExecuteMsg::UstBond {};
+    let msg = ExecuteMsg::UstBond {
+        min_bro_payout: None,
+        lock_epochs: None,
+    };
     let info = mock_info(
         "addr0000",
         &[Coin {
@@ -670,7 +825,10 @@ fn ust_bond_normal_mode() {
     }
 
     // error: not enough balance for bond payout
-    let msg = ExecuteMsg::UstBond {};
+    let msg = ExecuteMsg::UstBond {
+        min_bro_payout: None,
+        lock_epochs: None,
+    };
     let info = mock_info(
         "addr0000",
         &[Coin {
@@ -693,6 +851,10 @@ fn ust_bond_normal_mode() {
         astroport_factory: None,
         oracle_contract: None,
         ust_bonding_discount: None,
+        discount_control: None,
+        bonding_limits: None,
+        bond_lock: None,
+        lp_staking: None,
     };
     let info = mock_info("owner", &[]);
     let _res = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap();
@@ -705,6 +867,7 @@ fn ust_bond_normal_mode() {
                 mock_env(),
                 QueryMsg::SimulateUstBond {
                     uusd_amount: Uint128::from(50_000000u128),
+                    lock_epochs: None,
                 }
             )
             .unwrap()
@@ -713,11 +876,15 @@ fn ust_bond_normal_mode() {
         SimulateExchangeResponse {
             bro_payout: Uint128::from(5_500000u128),
             can_be_exchanged: true,
+            discount: Decimal::from_str("0.1").unwrap(),
         },
     );
 
     // perform bond
-    let msg = ExecuteMsg::UstBond {};
+    let msg = ExecuteMsg::UstBond {
+        min_bro_payout: None,
+        lock_epochs: None,
+    };
     let info = mock_info(
         "addr0000",
         &[Coin {
@@ -757,6 +924,13 @@ fn ust_bond_normal_mode() {
         StateResponse {
             ust_bonding_balance: Uint128::from(54_500000u128),
             lp_bonding_balance: Uint128::from(40_000000u128),
+            ust_bonding_debt: Uint128::zero(),
+            lp_bonding_debt: Uint128::zero(),
+            total_ust_bonded: Uint128::from(50_000000u128),
+            total_lp_bonded: Uint128::zero(),
+            total_bro_payout: Uint128::from(5_500000u128),
+            total_bro_claimed: Uint128::zero(),
+            bonds_count: 1,
         },
     );
 
@@ -777,6 +951,10 @@ fn ust_bond_normal_mode() {
                 bond_type: "ust_bond".to_string(),
                 amount: Uint128::from(5_500000u128),
                 claimable_at: Expiration::AtHeight(12_345 + 10),
+                vested_amount: Uint128::zero(),
+                claimed_amount: Uint128::zero(),
+                claimable_amount: Uint128::zero(),
+                remaining_amount: Uint128::from(5_500000u128),
             }],
         },
     );
@@ -814,6 +992,10 @@ fn ust_bond_community_mode() {
             staking_contract: MOCK_STAKING_ADDR.to_string(),
             epochs_locked: 100,
         },
+        discount_control: None,
+        bonding_limits: None,
+        bond_lock: None,
+        lp_staking: None,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -829,7 +1011,10 @@ fn ust_bond_community_mode() {
     let _res = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap();
 
     // perform bond
-    let msg = ExecuteMsg::UstBond {};
+    let msg = ExecuteMsg::UstBond {
+        min_bro_payout: None,
+        lock_epochs: None,
+    };
     let info = mock_info(
         "addr0000",
         &[Coin {
@@ -888,6 +1073,13 @@ fn ust_bond_community_mode() {
         StateResponse {
             ust_bonding_balance: Uint128::from(94_500000u128),
             lp_bonding_balance: Uint128::zero(),
+            ust_bonding_debt: Uint128::zero(),
+            lp_bonding_debt: Uint128::zero(),
+            total_ust_bonded: Uint128::from(50_000000u128),
+            total_lp_bonded: Uint128::zero(),
+            total_bro_payout: Uint128::from(5_500000u128),
+            total_bro_claimed: Uint128::zero(),
+            bonds_count: 1,
         },
     );
 
@@ -913,6 +1105,7 @@ fn ust_bond_community_mode() {
             mock_env(),
             QueryMsg::SimulateLpBond {
                 lp_amount: Uint128::from(10_000000u128),
+                lock_epochs: None,
             }
         )
         .unwrap_err(),
@@ -939,23 +1132,31 @@ fn claim() {
             lp_bonding_discount: Decimal::from_str("0.05").unwrap(),
             vesting_period_blocks: 10,
         },
+        discount_control: None,
+        bonding_limits: None,
+        bond_lock: None,
+        lp_staking: None,
     };
 
     let info = mock_info("addr0000", &[]);
     let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
 
-    // store claims
+    // store claims created before linear vesting, whole amount is vested at claimable_at block
     let addr_raw = deps.as_mut().api.addr_canonicalize("addr0000").unwrap();
     let mut claims = load_claims(deps.as_mut().storage, &addr_raw).unwrap();
     claims.push(ClaimInfo {
         bond_type: BondType::UstBond,
         amount: Uint128::from(10_000000u128),
         claimable_at: Expiration::AtHeight(12_355),
+        vesting_start_block: None,
+        claimed_amount: Uint128::zero(),
     });
     claims.push(ClaimInfo {
         bond_type: BondType::LpBond,
         amount: Uint128::from(20_000000u128),
         claimable_at: Expiration::AtHeight(12_370),
+        vesting_start_block: None,
+        claimed_amount: Uint128::zero(),
     });
     store_claims(deps.as_mut().storage, &addr_raw, &claims).unwrap();
 
@@ -1009,6 +1210,10 @@ fn claim() {
                 bond_type: "lp_bond".to_string(),
                 amount: Uint128::from(20_000000u128),
                 claimable_at: Expiration::AtHeight(12_370),
+                vested_amount: Uint128::zero(),
+                claimed_amount: Uint128::zero(),
+                claimable_amount: Uint128::zero(),
+                remaining_amount: Uint128::from(20_000000u128),
             }],
         },
     );
@@ -1069,6 +1274,10 @@ fn update_config() {
             lp_bonding_discount: Decimal::from_str("0.05").unwrap(),
             vesting_period_blocks: 10,
         },
+        discount_control: None,
+        bonding_limits: None,
+        bond_lock: None,
+        lp_staking: None,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -1082,6 +1291,10 @@ fn update_config() {
         oracle_contract: Some("new_oracle".to_string()),
         ust_bonding_discount: Some(Decimal::from_str("1.1").unwrap()),
         min_bro_payout: Some(Uint128::from(2u128)),
+        discount_control: None,
+        bonding_limits: None,
+        bond_lock: None,
+        lp_staking: None,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -1112,6 +1325,10 @@ fn update_config() {
         oracle_contract: Some("new_oracle".to_string()),
         ust_bonding_discount: Some(Decimal::from_str("0.11").unwrap()),
         min_bro_payout: Some(Uint128::from(2u128)),
+        discount_control: None,
+        bonding_limits: None,
+        bond_lock: None,
+        lp_staking: None,
     };
 
     let info = mock_info("owner", &[]);
@@ -1162,7 +1379,11 @@ fn update_config() {
                 lp_token: MOCK_LP_TOKEN_ADDR.to_string(),
                 lp_bonding_discount: Decimal::from_str("0.05").unwrap(),
                 vesting_period_blocks: 10,
-            }
+            },
+            discount_control: None,
+            bonding_limits: None,
+            bond_lock: None,
+            lp_staking: None,
         },
     );
 }
@@ -1187,6 +1408,10 @@ fn update_bonding_mode_config() {
             lp_bonding_discount: Decimal::from_str("0.05").unwrap(),
             vesting_period_blocks: 10,
         },
+        discount_control: None,
+        bonding_limits: None,
+        bond_lock: None,
+        lp_staking: None,
     };
     let info = mock_info("addr0000", &[]);
     let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -1323,7 +1548,11 @@ fn update_bonding_mode_config() {
                 lp_token: "new_lp_token".to_string(),
                 lp_bonding_discount: Decimal::from_str("0.06").unwrap(),
                 vesting_period_blocks: 11,
-            }
+            },
+            discount_control: None,
+            bonding_limits: None,
+            bond_lock: None,
+            lp_staking: None,
         },
     );
 
@@ -1341,6 +1570,10 @@ fn update_bonding_mode_config() {
             staking_contract: MOCK_STAKING_ADDR.to_string(),
             epochs_locked: 10,
         },
+        discount_control: None,
+        bonding_limits: None,
+        bond_lock: None,
+        lp_staking: None,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -1401,7 +1634,11 @@ fn update_bonding_mode_config() {
             bonding_mode: BondingModeMsg::Community {
                 staking_contract: MOCK_STAKING_ADDR.to_string(),
                 epochs_locked: 11,
-            }
+            },
+            discount_control: None,
+            bonding_limits: None,
+            bond_lock: None,
+            lp_staking: None,
         },
     );
 }
@@ -1425,6 +1662,10 @@ fn update_owner() {
             lp_bonding_discount: Decimal::from_str("0.05").unwrap(),
             vesting_period_blocks: 10,
         },
+        discount_control: None,
+        bonding_limits: None,
+        bond_lock: None,
+        lp_staking: None,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -1476,7 +1717,2093 @@ fn update_owner() {
                 lp_token: MOCK_LP_TOKEN_ADDR.to_string(),
                 lp_bonding_discount: Decimal::from_str("0.05").unwrap(),
                 vesting_period_blocks: 10,
-            }
+            },
+            discount_control: None,
+            bonding_limits: None,
+            bond_lock: None,
+            lp_staking: None,
+        },
+    );
+}
+
+#[test]
+fn dynamic_discount() {
+    let mut deps = mock_dependencies(&[]);
+
+    let mut msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        bro_token: MOCK_BRO_TOKEN_ADDR.to_string(),
+        rewards_pool_contract: "rewards".to_string(),
+        treasury_contract: "treasury".to_string(),
+        astroport_factory: MOCK_ASTRO_FACTORY_ADDR.to_string(),
+        oracle_contract: MOCK_ORACLE_ADDR.to_string(),
+        ust_bonding_discount: Decimal::from_str("0.1").unwrap(),
+        min_bro_payout: Uint128::from(1u128),
+        bonding_mode: BondingModeMsg::Normal {
+            ust_bonding_reward_ratio: Decimal::from_str("0.6").unwrap(),
+            lp_token: MOCK_LP_TOKEN_ADDR.to_string(),
+            lp_bonding_discount: Decimal::from_str("0.05").unwrap(),
+            vesting_period_blocks: 10,
+        },
+        discount_control: Some(DiscountControl {
+            min_discount: Decimal::from_str("0.06").unwrap(),
+            control_variable: Decimal::from_str("0.1").unwrap(),
+            debt_decay_blocks: 100,
+        }),
+        bonding_limits: None,
+        bond_lock: None,
+        lp_staking: None,
+    };
+
+    // error: min discount is higher than lp bonding discount
+    let info = mock_info("addr0000", &[]);
+    let res = instantiate(deps.as_mut(), mock_env(), info, msg.clone());
+    match res {
+        Err(ContractError::Std(StdError::GenericErr { msg, .. })) => assert_eq!(
+            msg,
+            "min_discount must be less than or equal to lp_bonding_discount".to_string()
+        ),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    msg.discount_control = Some(DiscountControl {
+        min_discount: Decimal::from_str("0.02").unwrap(),
+        control_variable: Decimal::from_str("0.1").unwrap(),
+        debt_decay_blocks: 100,
+    });
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // distribute rewards
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "rewards".to_string(),
+        amount: Uint128::from(100_000000u128),
+        msg: to_binary(&Cw20HookMsg::DistributeReward {}).unwrap(),
+    });
+    let info = mock_info(MOCK_BRO_TOKEN_ADDR, &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let simulate_ust_bond = |deps: Deps, env: Env| {
+        from_binary::<SimulateExchangeResponse>(
+            &query(
+                deps,
+                env,
+                QueryMsg::SimulateUstBond {
+                    uusd_amount: Uint128::from(50_000000u128),
+                    lock_epochs: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    };
+
+    // max discount is applied without bonded debt
+    assert_eq!(
+        simulate_ust_bond(deps.as_ref(), mock_env()),
+        SimulateExchangeResponse {
+            bro_payout: Uint128::from(5_500000u128),
+            can_be_exchanged: true,
+            discount: Decimal::from_str("0.1").unwrap(),
+        },
+    );
+
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(100_000000u128),
+        }],
+    );
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::UstBond {
+            min_bro_payout: None,
+            lock_epochs: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            Attribute::new("action", "ust_bond"),
+            Attribute::new("sender", "addr0000"),
+            Attribute::new("bro_payout", "11000000"),
+            Attribute::new("discount", "0.1"),
+        ]
+    );
+
+    // discount decreases by control variable multiplied by debt ratio: 0.1 * 11 / 60
+    assert_eq!(
+        simulate_ust_bond(deps.as_ref(), mock_env()),
+        SimulateExchangeResponse {
+            bro_payout: Uint128::from(5_408333u128),
+            can_be_exchanged: true,
+            discount: Decimal::from_str("0.081666666666666667").unwrap(),
+        },
+    );
+
+    // half of debt is decayed
+    let mut env = mock_env();
+    env.block.height += 50;
+    assert_eq!(
+        from_binary::<StateResponse>(
+            &query(deps.as_ref(), env.clone(), QueryMsg::State {}).unwrap()
+        )
+        .unwrap(),
+        StateResponse {
+            ust_bonding_balance: Uint128::from(49_000000u128),
+            lp_bonding_balance: Uint128::from(40_000000u128),
+            ust_bonding_debt: Uint128::from(5_500000u128),
+            lp_bonding_debt: Uint128::zero(),
+            total_ust_bonded: Uint128::from(100_000000u128),
+            total_lp_bonded: Uint128::zero(),
+            total_bro_payout: Uint128::from(11_000000u128),
+            total_bro_claimed: Uint128::zero(),
+            bonds_count: 1,
+        },
+    );
+
+    // discount is bounded by min discount
+    let msg = ExecuteMsg::UpdateConfig {
+        rewards_pool_contract: None,
+        treasury_contract: None,
+        astroport_factory: None,
+        oracle_contract: None,
+        ust_bonding_discount: None,
+        min_bro_payout: None,
+        discount_control: Some(Box::new(DiscountControl {
+            min_discount: Decimal::from_str("0.02").unwrap(),
+            control_variable: Decimal::from_str("10").unwrap(),
+            debt_decay_blocks: 100,
+        })),
+        bonding_limits: None,
+        bond_lock: None,
+        lp_staking: None,
+    };
+    let info = mock_info("owner", &[]);
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        simulate_ust_bond(deps.as_ref(), env.clone()).discount,
+        Decimal::from_str("0.02").unwrap(),
+    );
+
+    // debt is fully decayed
+    env.block.height += 50;
+    assert_eq!(
+        simulate_ust_bond(deps.as_ref(), env.clone()).discount,
+        Decimal::from_str("0.1").unwrap(),
+    );
+
+    // debt decay starts from migration block when migrating from version without it
+    set_contract_version(deps.as_mut().storage, "brotocol-bonding-v1", "1.1.0").unwrap();
+    let mut state = load_state(deps.as_ref().storage).unwrap();
+    state.last_debt_decay_block = 0;
+    store_state(deps.as_mut().storage, &state).unwrap();
+
+    let msg = MigrateMsg {
+        params: Binary::default(),
+    };
+    let _res = migrate(deps.as_mut(), env.clone(), msg).unwrap();
+    assert_eq!(
+        load_state(deps.as_ref().storage)
+            .unwrap()
+            .last_debt_decay_block,
+        env.block.height
+    );
+}
+
+#[test]
+fn claim_linear_vesting() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        bro_token: MOCK_BRO_TOKEN_ADDR.to_string(),
+        rewards_pool_contract: "rewards".to_string(),
+        treasury_contract: "treasury".to_string(),
+        astroport_factory: MOCK_ASTRO_FACTORY_ADDR.to_string(),
+        oracle_contract: MOCK_ORACLE_ADDR.to_string(),
+        ust_bonding_discount: Decimal::from_str("0.1").unwrap(),
+        min_bro_payout: Uint128::from(1u128),
+        bonding_mode: BondingModeMsg::Normal {
+            ust_bonding_reward_ratio: Decimal::from_str("0.6").unwrap(),
+            lp_token: MOCK_LP_TOKEN_ADDR.to_string(),
+            lp_bonding_discount: Decimal::from_str("0.05").unwrap(),
+            vesting_period_blocks: 10,
+        },
+        discount_control: None,
+        bonding_limits: None,
+        bond_lock: None,
+        lp_staking: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // distribute rewards
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "rewards".to_string(),
+        amount: Uint128::from(100_000000u128),
+        msg: to_binary(&Cw20HookMsg::DistributeReward {}).unwrap(),
+    });
+    let info = mock_info(MOCK_BRO_TOKEN_ADDR, &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // bond with 5.5 BRO payout vested from 12345 to 12355 block
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(50_000000u128),
+        }],
+    );
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::UstBond {
+            min_bro_payout: None,
+            lock_epochs: None,
+        },
+    )
+    .unwrap();
+
+    let query_claims = |deps: Deps, env: Env| {
+        from_binary::<ClaimsResponse>(
+            &query(
+                deps,
+                env,
+                QueryMsg::Claims {
+                    address: "addr0000".to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    };
+
+    // claim vested portion
+    let mut env = mock_env();
+    env.block.height = 12349;
+
+    let info = mock_info("addr0000", &[]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::Claim {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_BRO_TOKEN_ADDR.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "addr0000".to_string(),
+                amount: Uint128::from(2_200000u128),
+            })
+            .unwrap()
+        }))]
+    );
+
+    // error: vested amount is already claimed
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::Claim {},
+    );
+    match res {
+        Err(ContractError::NothingToClaim {}) => (),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    env.block.height = 12352;
+    assert_eq!(
+        query_claims(deps.as_ref(), env.clone()),
+        ClaimsResponse {
+            claims: vec![ClaimInfoResponse {
+                bond_type: "ust_bond".to_string(),
+                amount: Uint128::from(5_500000u128),
+                claimable_at: Expiration::AtHeight(12_355),
+                vested_amount: Uint128::from(3_850000u128),
+                claimed_amount: Uint128::from(2_200000u128),
+                claimable_amount: Uint128::from(1_650000u128),
+                remaining_amount: Uint128::from(1_650000u128),
+            }],
         },
     );
+
+    // claim the rest after vesting period
+    env.block.height = 12360;
+    let res = execute(deps.as_mut(), env.clone(), info, ExecuteMsg::Claim {}).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            Attribute::new("action", "claim"),
+            Attribute::new("sender", "addr0000"),
+            Attribute::new("amount", "3300000"),
+        ]
+    );
+    assert_eq!(
+        query_claims(deps.as_ref(), env),
+        ClaimsResponse { claims: vec![] },
+    );
+}
+
+#[test]
+fn bond_markets() {
+    // setup initial balances for UST-BRO pool
+    let mut deps = mock_dependencies(&[(
+        MOCK_BRO_UST_PAIR_ADDR,
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000_000000u128),
+        }],
+    )]);
+
+    deps.querier.with_token_balances(&[(
+        &MOCK_BRO_TOKEN_ADDR.to_string(),
+        &[(
+            &MOCK_BRO_UST_PAIR_ADDR.to_string(),
+            &Uint128::from(100_000000u128),
+        )],
+    )]);
+
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        bro_token: MOCK_BRO_TOKEN_ADDR.to_string(),
+        rewards_pool_contract: "rewards".to_string(),
+        treasury_contract: "treasury".to_string(),
+        astroport_factory: MOCK_ASTRO_FACTORY_ADDR.to_string(),
+        oracle_contract: MOCK_ORACLE_ADDR.to_string(),
+        ust_bonding_discount: Decimal::from_str("0.1").unwrap(),
+        min_bro_payout: Uint128::from(1u128),
+        bonding_mode: BondingModeMsg::Normal {
+            ust_bonding_reward_ratio: Decimal::from_str("0.6").unwrap(),
+            lp_token: MOCK_LP_TOKEN_ADDR.to_string(),
+            lp_bonding_discount: Decimal::from_str("0.05").unwrap(),
+            vesting_period_blocks: 10,
+        },
+        discount_control: None,
+        bonding_limits: None,
+        bond_lock: None,
+        lp_staking: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // error: unauthorized
+    let msg = ExecuteMsg::RegisterBondMarket {
+        asset: BondMarketAssetMsg::NativeToken {
+            denom: "uluna".to_string(),
+        },
+        discount: Decimal::from_str("0.1").unwrap(),
+        vesting_period_blocks: 10,
+        price_source: PriceSourceMsg::Oracle {
+            oracle_contract: MOCK_ORACLE_ADDR.to_string(),
+        },
+        discount_control: None,
+        epoch_limits: None,
+    };
+    let info = mock_info("addr0000", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg.clone());
+    match res {
+        Err(ContractError::Unauthorized {}) => assert_eq!(true, true),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    // register native token market
+    let info = mock_info("owner", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            Attribute::new("action", "register_bond_market"),
+            Attribute::new("market_id", "1"),
+            Attribute::new("asset", "uluna"),
+        ]
+    );
+
+    // register cw20 token market
+    let msg = ExecuteMsg::RegisterBondMarket {
+        asset: BondMarketAssetMsg::Token {
+            contract_addr: "token0000".to_string(),
+        },
+        discount: Decimal::from_str("0.2").unwrap(),
+        vesting_period_blocks: 20,
+        price_source: PriceSourceMsg::Fixed {
+            bro_per_asset: Decimal::from_str("0.5").unwrap(),
+        },
+        discount_control: Some(DiscountControl {
+            min_discount: Decimal::from_str("0.05").unwrap(),
+            control_variable: Decimal::from_str("0.1").unwrap(),
+            debt_decay_blocks: 10,
+        }),
+        epoch_limits: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    // register lp token market
+    let msg = ExecuteMsg::RegisterBondMarket {
+        asset: BondMarketAssetMsg::LpToken {
+            paired_asset: AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+        },
+        discount: Decimal::from_str("0.05").unwrap(),
+        vesting_period_blocks: 10,
+        price_source: PriceSourceMsg::Oracle {
+            oracle_contract: MOCK_ORACLE_ADDR.to_string(),
+        },
+        discount_control: None,
+        epoch_limits: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    assert_eq!(
+        from_binary::<BondMarketResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::BondMarket { market_id: 3 }
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        BondMarketResponse {
+            market_id: 3,
+            asset_info: AssetInfo::Token {
+                contract_addr: Addr::unchecked(MOCK_LP_TOKEN_ADDR),
+            },
+            pair_contract: Some(MOCK_BRO_UST_PAIR_ADDR.to_string()),
+            paired_asset: Some(AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            }),
+            discount: Decimal::from_str("0.05").unwrap(),
+            capacity: Uint128::zero(),
+            vesting_period_blocks: 10,
+            price_source: PriceSourceMsg::Oracle {
+                oracle_contract: MOCK_ORACLE_ADDR.to_string(),
+            },
+            enabled: true,
+            discount_control: None,
+            debt: Uint128::zero(),
+            epoch_limits: None,
+            total_bonded: Uint128::zero(),
+        },
+    );
+
+    // error: bonding limits are not configured
+    let msg = ExecuteMsg::RegisterBondMarket {
+        asset: BondMarketAssetMsg::NativeToken {
+            denom: "uluna".to_string(),
+        },
+        discount: Decimal::from_str("0.1").unwrap(),
+        vesting_period_blocks: 10,
+        price_source: PriceSourceMsg::Fixed {
+            bro_per_asset: Decimal::one(),
+        },
+        discount_control: None,
+        epoch_limits: Some(EpochLimits {
+            epoch_capacity: Some(Uint128::from(10_000000u128)),
+            address_cap: None,
+        }),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    match res {
+        Err(ContractError::BondingLimitsDisabled {}) => assert_eq!(true, true),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    // error: bro token can't be bonded
+    let msg = ExecuteMsg::RegisterBondMarket {
+        asset: BondMarketAssetMsg::Token {
+            contract_addr: MOCK_BRO_TOKEN_ADDR.to_string(),
+        },
+        discount: Decimal::from_str("0.1").unwrap(),
+        vesting_period_blocks: 10,
+        price_source: PriceSourceMsg::Fixed {
+            bro_per_asset: Decimal::one(),
+        },
+        discount_control: None,
+        epoch_limits: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    match res {
+        Err(ContractError::InvalidBondMarketAsset {}) => assert_eq!(true, true),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    // error: bond market has no capacity
+    let msg = ExecuteMsg::Bond {
+        market_id: 1,
+        min_bro_payout: None,
+    };
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uluna".to_string(),
+            amount: Uint128::from(50_000000u128),
+        }],
+    );
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone());
+    match res {
+        Err(ContractError::NotEnoughForBondPayout {}) => assert_eq!(true, true),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    // error: only owner can fund bond market
+    let fund_msg = |sender: &str, market_id: u64, amount: u128| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: sender.to_string(),
+            amount: Uint128::from(amount),
+            msg: to_binary(&Cw20HookMsg::FundBondMarket { market_id }).unwrap(),
+        })
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_BRO_TOKEN_ADDR, &[]),
+        fund_msg("addr0000", 1, 100_000000),
+    );
+    match res {
+        Err(ContractError::Unauthorized {}) => assert_eq!(true, true),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    for market_id in 1..=3 {
+        let _res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MOCK_BRO_TOKEN_ADDR, &[]),
+            fund_msg("owner", market_id, 10_000000),
+        )
+        .unwrap();
+    }
+
+    // error: invalid denom
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(
+            "addr0000",
+            &[Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(50_000000u128),
+            }],
+        ),
+        msg.clone(),
+    );
+    match res {
+        Err(ContractError::InvalidFundsInput {}) => assert_eq!(true, true),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    // bond native token with 5.5 BRO payout
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "treasury".to_string(),
+                amount: vec![Coin {
+                    denom: "uluna".to_string(),
+                    amount: Uint128::from(50_000000u128),
+                }],
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_ORACLE_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&OracleExecuteMsg::UpdatePrice {}).unwrap(),
+            })),
+        ]
+    );
+
+    // error: unauthorized cw20 token
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(10_000000u128),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            market_id: 2,
+            min_bro_payout: None,
+        })
+        .unwrap(),
+    });
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("token0001", &[]),
+        msg.clone(),
+    );
+    match res {
+        Err(ContractError::Unauthorized {}) => assert_eq!(true, true),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    // bond cw20 token with 6 BRO payout
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("token0000", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "token0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "treasury".to_string(),
+                amount: Uint128::from(10_000000u128),
+            })
+            .unwrap(),
+        }))]
+    );
+
+    // bond market debt decreases discount by 0.1 * 6 / (6 + 4)
+    let simulate_cw20_bond = |deps: Deps, env: Env| {
+        from_binary::<SimulateExchangeResponse>(
+            &query(
+                deps,
+                env,
+                QueryMsg::SimulateBond {
+                    market_id: 2,
+                    amount: Uint128::from(2_000000u128),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    };
+    assert_eq!(
+        simulate_cw20_bond(deps.as_ref(), mock_env()),
+        SimulateExchangeResponse {
+            bro_payout: Uint128::from(1_140000u128),
+            can_be_exchanged: true,
+            discount: Decimal::from_str("0.14").unwrap(),
+        },
+    );
+
+    // bond market debt is fully decayed after debt decay period
+    let mut env = mock_env();
+    env.block.height += 10;
+    assert_eq!(
+        simulate_cw20_bond(deps.as_ref(), env.clone()),
+        SimulateExchangeResponse {
+            bro_payout: Uint128::from(1_200000u128),
+            can_be_exchanged: true,
+            discount: Decimal::from_str("0.2").unwrap(),
+        },
+    );
+
+    let market = from_binary::<BondMarketResponse>(
+        &query(deps.as_ref(), env, QueryMsg::BondMarket { market_id: 2 }).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(market.debt, Uint128::zero());
+
+    // simulate lp token bond
+    assert_eq!(
+        from_binary::<SimulateExchangeResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::SimulateBond {
+                    market_id: 3,
+                    amount: Uint128::from(10_000000u128),
+                }
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        SimulateExchangeResponse {
+            bro_payout: Uint128::from(2_100000u128),
+            can_be_exchanged: true,
+            discount: Decimal::from_str("0.05").unwrap(),
+        },
+    );
+
+    // disable cw20 token market
+    let update_msg = ExecuteMsg::UpdateBondMarket {
+        market_id: 2,
+        discount: None,
+        vesting_period_blocks: None,
+        price_source: None,
+        enabled: Some(false),
+        discount_control: None,
+        epoch_limits: None,
+    };
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        update_msg,
+    )
+    .unwrap();
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info("token0000", &[]), msg);
+    match res {
+        Err(ContractError::BondMarketDisabled {}) => assert_eq!(true, true),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    let markets = from_binary::<BondMarketsResponse>(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::BondMarkets {
+                start_after: Some(1),
+                limit: Some(1),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(markets.markets.len(), 1);
+    assert_eq!(markets.markets[0].market_id, 2);
+    assert_eq!(markets.markets[0].capacity, Uint128::from(4_000000u128));
+    assert_eq!(markets.markets[0].debt, Uint128::from(6_000000u128));
+    assert_eq!(
+        markets.markets[0].total_bonded,
+        Uint128::from(10_000000u128)
+    );
+
+    let bonder = from_binary::<BonderResponse>(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Bonder {
+                address: "addr0000".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        bonder.market_bonded,
+        vec![
+            MarketBondedAmount {
+                market_id: 1,
+                amount: Uint128::from(50_000000u128),
+            },
+            MarketBondedAmount {
+                market_id: 2,
+                amount: Uint128::from(10_000000u128),
+            },
+        ]
+    );
+    assert_eq!(bonder.bro_payout, Uint128::from(11_500000u128));
+    assert_eq!(bonder.bonds_count, 2);
+    assert!(!markets.markets[0].enabled);
+
+    assert_eq!(
+        from_binary::<ClaimsResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Claims {
+                    address: "addr0000".to_string(),
+                },
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        ClaimsResponse {
+            claims: vec![
+                ClaimInfoResponse {
+                    bond_type: "market_bond_1".to_string(),
+                    amount: Uint128::from(5_500000u128),
+                    claimable_at: Expiration::AtHeight(12_355),
+                    vested_amount: Uint128::zero(),
+                    claimed_amount: Uint128::zero(),
+                    claimable_amount: Uint128::zero(),
+                    remaining_amount: Uint128::from(5_500000u128),
+                },
+                ClaimInfoResponse {
+                    bond_type: "market_bond_2".to_string(),
+                    amount: Uint128::from(6_000000u128),
+                    claimable_at: Expiration::AtHeight(12_365),
+                    vested_amount: Uint128::zero(),
+                    claimed_amount: Uint128::zero(),
+                    claimable_amount: Uint128::zero(),
+                    remaining_amount: Uint128::from(6_000000u128),
+                },
+            ],
+        },
+    );
+
+    // error: only owner can withdraw bond market capacity
+    let withdraw_msg = |amount: Option<u128>| ExecuteMsg::WithdrawBondMarketCapacity {
+        market_id: 2,
+        amount: amount.map(Uint128::from),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        withdraw_msg(None),
+    );
+    match res {
+        Err(ContractError::Unauthorized {}) => assert_eq!(true, true),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    // error: amount exceeds market capacity
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        withdraw_msg(Some(4_000001)),
+    );
+    match res {
+        Err(ContractError::InvalidCapacityWithdrawAmount {}) => assert_eq!(true, true),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        withdraw_msg(Some(1_000000)),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_BRO_TOKEN_ADDR.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "treasury".to_string(),
+                amount: Uint128::from(1_000000u128),
+            })
+            .unwrap(),
+        }))]
+    );
+
+    // withdraw remaining capacity of disabled market
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        withdraw_msg(None),
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            Attribute::new("action", "withdraw_bond_market_capacity"),
+            Attribute::new("market_id", "2"),
+            Attribute::new("amount", "3000000"),
+            Attribute::new("capacity", "0"),
+        ]
+    );
+
+    // error: nothing to withdraw
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        withdraw_msg(None),
+    );
+    match res {
+        Err(ContractError::InvalidCapacityWithdrawAmount {}) => assert_eq!(true, true),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    let market = from_binary::<BondMarketResponse>(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::BondMarket { market_id: 2 },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(market.capacity, Uint128::zero());
+    assert_eq!(market.debt, Uint128::from(6_000000u128));
+}
+
+#[test]
+fn bonding_limits() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        bro_token: MOCK_BRO_TOKEN_ADDR.to_string(),
+        rewards_pool_contract: "rewards".to_string(),
+        treasury_contract: "treasury".to_string(),
+        astroport_factory: MOCK_ASTRO_FACTORY_ADDR.to_string(),
+        oracle_contract: MOCK_ORACLE_ADDR.to_string(),
+        ust_bonding_discount: Decimal::from_str("0.1").unwrap(),
+        min_bro_payout: Uint128::from(1u128),
+        bonding_mode: BondingModeMsg::Normal {
+            ust_bonding_reward_ratio: Decimal::from_str("0.6").unwrap(),
+            lp_token: MOCK_LP_TOKEN_ADDR.to_string(),
+            lp_bonding_discount: Decimal::from_str("0.05").unwrap(),
+            vesting_period_blocks: 10,
+        },
+        discount_control: None,
+        bonding_limits: Some(BondingLimitsMsg {
+            epoch_manager_contract: MOCK_EPOCH_MANAGER_ADDR.to_string(),
+            ust_bond: EpochLimits {
+                epoch_capacity: Some(Uint128::from(12_000000u128)),
+                address_cap: Some(Uint128::from(6_000000u128)),
+            },
+            lp_bond: EpochLimits {
+                epoch_capacity: None,
+                address_cap: None,
+            },
+        }),
+        bond_lock: None,
+        lp_staking: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // distribute rewards
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "rewards".to_string(),
+        amount: Uint128::from(100_000000u128),
+        msg: to_binary(&Cw20HookMsg::DistributeReward {}).unwrap(),
+    });
+    let info = mock_info(MOCK_BRO_TOKEN_ADDR, &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let bond_info = |sender: &str| {
+        mock_info(
+            sender,
+            &[Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(50_000000u128),
+            }],
+        )
+    };
+
+    // bond with 5.5 BRO payout
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        bond_info("addr0000"),
+        ExecuteMsg::UstBond {
+            min_bro_payout: None,
+            lock_epochs: None,
+        },
+    )
+    .unwrap();
+
+    // error: address cap exceeded
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        bond_info("addr0000"),
+        ExecuteMsg::UstBond {
+            min_bro_payout: None,
+            lock_epochs: None,
+        },
+    );
+    match res {
+        Err(ContractError::AddressBondingCapExceeded {}) => assert_eq!(true, true),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        bond_info("addr0001"),
+        ExecuteMsg::UstBond {
+            min_bro_payout: None,
+            lock_epochs: None,
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        from_binary::<BondingCapacityResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::BondingCapacity {
+                    start_after: None,
+                    limit: None,
+                }
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        BondingCapacityResponse {
+            epoch: Some(123),
+            epoch_end_block: Some(12400),
+            ust_bond: BondTypeCapacityResponse {
+                epoch_capacity: Some(Uint128::from(12_000000u128)),
+                address_cap: Some(Uint128::from(6_000000u128)),
+                bonded_amount: Uint128::from(11_000000u128),
+                available_amount: Uint128::from(1_000000u128),
+            },
+            lp_bond: BondTypeCapacityResponse {
+                epoch_capacity: None,
+                address_cap: None,
+                bonded_amount: Uint128::zero(),
+                available_amount: Uint128::from(40_000000u128),
+            },
+            bond_markets: vec![],
+        },
+    );
+
+    // error: epoch capacity exceeded
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        bond_info("addr0002"),
+        ExecuteMsg::UstBond {
+            min_bro_payout: None,
+            lock_epochs: None,
+        },
+    );
+    match res {
+        Err(ContractError::EpochBondingCapacityExceeded {}) => assert_eq!(true, true),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    // limits are reset in next epoch
+    let mut env = mock_env();
+    env.block.height = 12400;
+    let _res = execute(
+        deps.as_mut(),
+        env.clone(),
+        bond_info("addr0000"),
+        ExecuteMsg::UstBond {
+            min_bro_payout: None,
+            lock_epochs: None,
+        },
+    )
+    .unwrap();
+
+    let capacity = from_binary::<BondingCapacityResponse>(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::BondingCapacity {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(capacity.epoch, Some(124));
+    assert_eq!(capacity.ust_bond.bonded_amount, Uint128::from(5_500000u128));
+    assert_eq!(
+        capacity.ust_bond.available_amount,
+        Uint128::from(6_500000u128)
+    );
+
+    // register cw20 token market with per epoch limits
+    let msg = ExecuteMsg::RegisterBondMarket {
+        asset: BondMarketAssetMsg::Token {
+            contract_addr: "token0000".to_string(),
+        },
+        discount: Decimal::from_str("0.1").unwrap(),
+        vesting_period_blocks: 10,
+        price_source: PriceSourceMsg::Fixed {
+            bro_per_asset: Decimal::one(),
+        },
+        discount_control: None,
+        epoch_limits: Some(EpochLimits {
+            epoch_capacity: Some(Uint128::from(3_000000u128)),
+            address_cap: Some(Uint128::from(2_000000u128)),
+        }),
+    };
+    let _res = execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "owner".to_string(),
+        amount: Uint128::from(10_000000u128),
+        msg: to_binary(&Cw20HookMsg::FundBondMarket { market_id: 1 }).unwrap(),
+    });
+    let info = mock_info(MOCK_BRO_TOKEN_ADDR, &[]);
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    // bond with 1.1 BRO payout
+    let market_bond_msg = |sender: &str| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: sender.to_string(),
+            amount: Uint128::from(1_000000u128),
+            msg: to_binary(&Cw20HookMsg::Bond {
+                market_id: 1,
+                min_bro_payout: None,
+            })
+            .unwrap(),
+        })
+    };
+    let info = mock_info("token0000", &[]);
+    let _res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        market_bond_msg("addr0000"),
+    )
+    .unwrap();
+
+    // error: market address cap exceeded
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        market_bond_msg("addr0000"),
+    );
+    match res {
+        Err(ContractError::AddressBondingCapExceeded {}) => assert_eq!(true, true),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    let _res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        market_bond_msg("addr0001"),
+    )
+    .unwrap();
+
+    // error: market epoch capacity exceeded
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        market_bond_msg("addr0002"),
+    );
+    match res {
+        Err(ContractError::EpochBondingCapacityExceeded {}) => assert_eq!(true, true),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    let capacity = from_binary::<BondingCapacityResponse>(
+        &query(
+            deps.as_ref(),
+            env,
+            QueryMsg::BondingCapacity {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        capacity.bond_markets,
+        vec![BondMarketCapacityResponse {
+            market_id: 1,
+            capacity: BondTypeCapacityResponse {
+                epoch_capacity: Some(Uint128::from(3_000000u128)),
+                address_cap: Some(Uint128::from(2_000000u128)),
+                bonded_amount: Uint128::from(2_200000u128),
+                available_amount: Uint128::from(800000u128),
+            },
+        }],
+    );
+}
+
+#[test]
+fn bond_slippage_protection() {
+    // setup initial balances for UST-BRO pool
+    let mut deps = mock_dependencies(&[(
+        MOCK_BRO_UST_PAIR_ADDR,
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000_000000u128),
+        }],
+    )]);
+
+    deps.querier.with_token_balances(&[(
+        &MOCK_BRO_TOKEN_ADDR.to_string(),
+        &[(
+            &MOCK_BRO_UST_PAIR_ADDR.to_string(),
+            &Uint128::from(100_000000u128),
+        )],
+    )]);
+
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        bro_token: MOCK_BRO_TOKEN_ADDR.to_string(),
+        rewards_pool_contract: "rewards".to_string(),
+        treasury_contract: "treasury".to_string(),
+        astroport_factory: MOCK_ASTRO_FACTORY_ADDR.to_string(),
+        oracle_contract: MOCK_ORACLE_ADDR.to_string(),
+        ust_bonding_discount: Decimal::from_str("0.1").unwrap(),
+        min_bro_payout: Uint128::from(1u128),
+        bonding_mode: BondingModeMsg::Normal {
+            ust_bonding_reward_ratio: Decimal::from_str("0.6").unwrap(),
+            lp_token: MOCK_LP_TOKEN_ADDR.to_string(),
+            lp_bonding_discount: Decimal::from_str("0.05").unwrap(),
+            vesting_period_blocks: 10,
+        },
+        discount_control: None,
+        bonding_limits: None,
+        bond_lock: None,
+        lp_staking: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // distribute rewards
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "rewards".to_string(),
+        amount: Uint128::from(100_000000u128),
+        msg: to_binary(&Cw20HookMsg::DistributeReward {}).unwrap(),
+    });
+    let info = mock_info(MOCK_BRO_TOKEN_ADDR, &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // error: ust bond payout of 5.5 BRO is lower than expected
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(50_000000u128),
+        }],
+    );
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::UstBond {
+            min_bro_payout: Some(Uint128::from(5_500001u128)),
+            lock_epochs: None,
+        },
+    );
+    match res {
+        Err(ContractError::BondSlippageExceeded {}) => assert_eq!(true, true),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::UstBond {
+            min_bro_payout: Some(Uint128::from(5_500000u128)),
+            lock_epochs: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(res.attributes[2], Attribute::new("bro_payout", "5500000"));
+
+    // error: lp bond payout of 2.1 BRO is lower than expected
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(10_000000u128),
+        msg: to_binary(&Cw20HookMsg::LpBond {
+            min_bro_payout: Some(Uint128::from(2_200000u128)),
+            lock_epochs: None,
+        })
+        .unwrap(),
+    });
+    let info = mock_info(MOCK_LP_TOKEN_ADDR, &[]);
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    match res {
+        Err(ContractError::BondSlippageExceeded {}) => assert_eq!(true, true),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(10_000000u128),
+        msg: to_binary(&Cw20HookMsg::LpBond {
+            min_bro_payout: Some(Uint128::from(2_100000u128)),
+            lock_epochs: None,
+        })
+        .unwrap(),
+    });
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+}
+
+#[test]
+fn bond_lock() {
+    // setup initial balances for UST-BRO pool
+    let mut deps = mock_dependencies(&[(
+        MOCK_BRO_UST_PAIR_ADDR,
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000_000000u128),
+        }],
+    )]);
+
+    deps.querier.with_token_balances(&[(
+        &MOCK_BRO_TOKEN_ADDR.to_string(),
+        &[(
+            &MOCK_BRO_UST_PAIR_ADDR.to_string(),
+            &Uint128::from(100_000000u128),
+        )],
+    )]);
+
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        bro_token: MOCK_BRO_TOKEN_ADDR.to_string(),
+        rewards_pool_contract: "rewards".to_string(),
+        treasury_contract: "treasury".to_string(),
+        astroport_factory: MOCK_ASTRO_FACTORY_ADDR.to_string(),
+        oracle_contract: MOCK_ORACLE_ADDR.to_string(),
+        ust_bonding_discount: Decimal::from_str("0.1").unwrap(),
+        min_bro_payout: Uint128::from(1u128),
+        bonding_mode: BondingModeMsg::Normal {
+            ust_bonding_reward_ratio: Decimal::from_str("0.6").unwrap(),
+            lp_token: MOCK_LP_TOKEN_ADDR.to_string(),
+            lp_bonding_discount: Decimal::from_str("0.05").unwrap(),
+            vesting_period_blocks: 10,
+        },
+        discount_control: None,
+        bonding_limits: None,
+        bond_lock: None,
+        lp_staking: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // distribute rewards
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "rewards".to_string(),
+        amount: Uint128::from(100_000000u128),
+        msg: to_binary(&Cw20HookMsg::DistributeReward {}).unwrap(),
+    });
+    let info = mock_info(MOCK_BRO_TOKEN_ADDR, &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // error: bond lock is not configured
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(50_000000u128),
+        }],
+    );
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::UstBond {
+            min_bro_payout: None,
+            lock_epochs: Some(145),
+        },
+    );
+    match res {
+        Err(ContractError::BondLockDisabled {}) => assert_eq!(true, true),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    // enable bond lock
+    let msg = ExecuteMsg::UpdateConfig {
+        min_bro_payout: None,
+        rewards_pool_contract: None,
+        treasury_contract: None,
+        astroport_factory: None,
+        oracle_contract: None,
+        ust_bonding_discount: None,
+        discount_control: None,
+        bonding_limits: None,
+        bond_lock: Some(Box::new(BondLockMsg {
+            staking_contract: MOCK_STAKING_ADDR.to_string(),
+            max_lock_discount: Decimal::from_str("0.5").unwrap(),
+        })),
+        lp_staking: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    // error: lockup period is less than min lockup period of staking contract
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::UstBond {
+            min_bro_payout: None,
+            lock_epochs: Some(1),
+        },
+    );
+    match res {
+        Err(ContractError::InvalidLockupPeriod {}) => assert_eq!(true, true),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    // lock discount: 0.5 * 145 / 725 = 0.1
+    assert_eq!(
+        from_binary::<SimulateExchangeResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::SimulateUstBond {
+                    uusd_amount: Uint128::from(50_000000u128),
+                    lock_epochs: Some(145),
+                }
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        SimulateExchangeResponse {
+            bro_payout: Uint128::from(6_000000u128),
+            can_be_exchanged: true,
+            discount: Decimal::from_str("0.2").unwrap(),
+        }
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::UstBond {
+            min_bro_payout: None,
+            lock_epochs: Some(145),
+        },
+    )
+    .unwrap();
+
+    let stake_bonded_tokens_msg = res.messages.get(2).expect("no message");
+    assert_eq!(
+        stake_bonded_tokens_msg,
+        &SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_BRO_TOKEN_ADDR.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: MOCK_STAKING_ADDR.to_string(),
+                amount: Uint128::from(6_000000u128),
+                msg: to_binary(&StakingCw20HookMsg::CommunityBondLock {
+                    sender: "addr0000".to_string(),
+                    epochs_locked: 145,
+                })
+                .unwrap(),
+            })
+            .unwrap(),
+        }))
+    );
+
+    // locked bond payout is not added to claims
+    let addr_raw = deps.as_mut().api.addr_canonicalize("addr0000").unwrap();
+    let claims = load_claims(deps.as_mut().storage, &addr_raw).unwrap();
+    assert!(claims.is_empty());
+}
+
+#[test]
+fn bonder_totals() {
+    // setup initial balances for UST-BRO pool
+    let mut deps = mock_dependencies(&[(
+        MOCK_BRO_UST_PAIR_ADDR,
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000_000000u128),
+        }],
+    )]);
+
+    deps.querier.with_token_balances(&[(
+        &MOCK_BRO_TOKEN_ADDR.to_string(),
+        &[(
+            &MOCK_BRO_UST_PAIR_ADDR.to_string(),
+            &Uint128::from(100_000000u128),
+        )],
+    )]);
+
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        bro_token: MOCK_BRO_TOKEN_ADDR.to_string(),
+        rewards_pool_contract: "rewards".to_string(),
+        treasury_contract: "treasury".to_string(),
+        astroport_factory: MOCK_ASTRO_FACTORY_ADDR.to_string(),
+        oracle_contract: MOCK_ORACLE_ADDR.to_string(),
+        ust_bonding_discount: Decimal::from_str("0.1").unwrap(),
+        min_bro_payout: Uint128::from(1u128),
+        bonding_mode: BondingModeMsg::Normal {
+            ust_bonding_reward_ratio: Decimal::from_str("0.6").unwrap(),
+            lp_token: MOCK_LP_TOKEN_ADDR.to_string(),
+            lp_bonding_discount: Decimal::from_str("0.05").unwrap(),
+            vesting_period_blocks: 10,
+        },
+        discount_control: None,
+        bonding_limits: None,
+        bond_lock: None,
+        lp_staking: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // distribute rewards
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "rewards".to_string(),
+        amount: Uint128::from(100_000000u128),
+        msg: to_binary(&Cw20HookMsg::DistributeReward {}).unwrap(),
+    });
+    let info = mock_info(MOCK_BRO_TOKEN_ADDR, &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // ust bonds by two bonders, 5.5 BRO payout each
+    for bonder in ["addr0000", "addr0001"] {
+        let info = mock_info(
+            bonder,
+            &[Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(50_000000u128),
+            }],
+        );
+        let msg = ExecuteMsg::UstBond {
+            min_bro_payout: None,
+            lock_epochs: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    }
+
+    // lp bond, 2.1 BRO payout
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(10_000000u128),
+        msg: to_binary(&Cw20HookMsg::LpBond {
+            min_bro_payout: None,
+            lock_epochs: None,
+        })
+        .unwrap(),
+    });
+    let info = mock_info(MOCK_LP_TOKEN_ADDR, &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // claim whole vested amount, claims are removed but totals are kept
+    let mut env = mock_env();
+    env.block.height += 10;
+    let info = mock_info("addr0000", &[]);
+    let _res = execute(deps.as_mut(), env.clone(), info, ExecuteMsg::Claim {}).unwrap();
+
+    let claims = from_binary::<ClaimsResponse>(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Claims {
+                address: "addr0000".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(claims.claims.is_empty());
+
+    let state =
+        from_binary::<StateResponse>(&query(deps.as_ref(), env, QueryMsg::State {}).unwrap())
+            .unwrap();
+    assert_eq!(state.total_ust_bonded, Uint128::from(100_000000u128));
+    assert_eq!(state.total_lp_bonded, Uint128::from(10_000000u128));
+    assert_eq!(state.total_bro_payout, Uint128::from(13_100000u128));
+    assert_eq!(state.total_bro_claimed, Uint128::from(7_600000u128));
+    assert_eq!(state.bonds_count, 3);
+
+    let bonder0 = BonderResponse {
+        address: "addr0000".to_string(),
+        ust_bonded: Uint128::from(50_000000u128),
+        lp_bonded: Uint128::from(10_000000u128),
+        market_bonded: vec![],
+        bro_payout: Uint128::from(7_600000u128),
+        bro_claimed: Uint128::from(7_600000u128),
+        bonds_count: 2,
+    };
+    let bonder1 = BonderResponse {
+        address: "addr0001".to_string(),
+        ust_bonded: Uint128::from(50_000000u128),
+        lp_bonded: Uint128::zero(),
+        market_bonded: vec![],
+        bro_payout: Uint128::from(5_500000u128),
+        bro_claimed: Uint128::zero(),
+        bonds_count: 1,
+    };
+
+    assert_eq!(
+        from_binary::<BonderResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Bonder {
+                    address: "addr0000".to_string(),
+                },
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        bonder0
+    );
+
+    let bonders = from_binary::<BondersResponse>(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Bonders {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+    .bonders;
+    assert_eq!(bonders.len(), 2);
+    assert!(bonders.contains(&bonder0));
+    assert!(bonders.contains(&bonder1));
+
+    // paginate
+    assert_eq!(
+        from_binary::<BondersResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Bonders {
+                    start_after: None,
+                    limit: Some(1),
+                },
+            )
+            .unwrap()
+        )
+        .unwrap()
+        .bonders,
+        vec![bonders[0].clone()]
+    );
+    assert_eq!(
+        from_binary::<BondersResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Bonders {
+                    start_after: Some(bonders[0].address.clone()),
+                    limit: None,
+                },
+            )
+            .unwrap()
+        )
+        .unwrap()
+        .bonders,
+        vec![bonders[1].clone()]
+    );
+}
+
+#[test]
+fn lp_staking() {
+    // setup initial balances for UST-BRO pool
+    let mut deps = mock_dependencies(&[(
+        MOCK_BRO_UST_PAIR_ADDR,
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000_000000u128),
+        }],
+    )]);
+
+    deps.querier.with_token_balances(&[(
+        &MOCK_BRO_TOKEN_ADDR.to_string(),
+        &[(
+            &MOCK_BRO_UST_PAIR_ADDR.to_string(),
+            &Uint128::from(100_000000u128),
+        )],
+    )]);
+
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        bro_token: MOCK_BRO_TOKEN_ADDR.to_string(),
+        rewards_pool_contract: "rewards".to_string(),
+        treasury_contract: "treasury".to_string(),
+        astroport_factory: MOCK_ASTRO_FACTORY_ADDR.to_string(),
+        oracle_contract: MOCK_ORACLE_ADDR.to_string(),
+        ust_bonding_discount: Decimal::from_str("0.1").unwrap(),
+        min_bro_payout: Uint128::from(1u128),
+        bonding_mode: BondingModeMsg::Normal {
+            ust_bonding_reward_ratio: Decimal::from_str("0.6").unwrap(),
+            lp_token: MOCK_LP_TOKEN_ADDR.to_string(),
+            lp_bonding_discount: Decimal::from_str("0.05").unwrap(),
+            vesting_period_blocks: 10,
+        },
+        discount_control: None,
+        bonding_limits: None,
+        bond_lock: None,
+        lp_staking: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // distribute rewards
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "rewards".to_string(),
+        amount: Uint128::from(100_000000u128),
+        msg: to_binary(&Cw20HookMsg::DistributeReward {}).unwrap(),
+    });
+    let info = mock_info(MOCK_BRO_TOKEN_ADDR, &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // error: lp staking is not configured
+    let info = mock_info("addr0000", &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::HarvestLpRewards {},
+    );
+    match res {
+        Err(ContractError::LpStakingDisabled {}) => assert_eq!(true, true),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    // enable lp staking
+    let msg = ExecuteMsg::UpdateConfig {
+        min_bro_payout: None,
+        rewards_pool_contract: None,
+        treasury_contract: None,
+        astroport_factory: None,
+        oracle_contract: None,
+        ust_bonding_discount: None,
+        discount_control: None,
+        bonding_limits: None,
+        bond_lock: None,
+        lp_staking: Some(Box::new(LpStakingMsg {
+            generator_contract: MOCK_GENERATOR_ADDR.to_string(),
+        })),
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    // bonded lp tokens are staked in generator
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(10_000000u128),
+        msg: to_binary(&Cw20HookMsg::LpBond {
+            min_bro_payout: None,
+            lock_epochs: None,
+        })
+        .unwrap(),
+    });
+    let info = mock_info(MOCK_LP_TOKEN_ADDR, &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let stake_lp_msg = res.messages.first().expect("no message");
+    assert_eq!(
+        stake_lp_msg,
+        &SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_LP_TOKEN_ADDR.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: MOCK_GENERATOR_ADDR.to_string(),
+                amount: Uint128::from(10_000000u128),
+                msg: to_binary(&GeneratorCw20HookMsg::Deposit {}).unwrap(),
+            })
+            .unwrap(),
+        }))
+    );
+
+    let reward_transfer_msgs = vec![
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_ASTRO_TOKEN_ADDR.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "treasury".to_string(),
+                amount: Uint128::from(10_000000u128),
+            })
+            .unwrap(),
+        })),
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_PROXY_REWARD_TOKEN_ADDR.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "treasury".to_string(),
+                amount: Uint128::from(5_000000u128),
+            })
+            .unwrap(),
+        })),
+    ];
+
+    // harvest rewards to treasury
+    let info = mock_info("addr0000", &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::HarvestLpRewards {},
+    )
+    .unwrap();
+
+    let mut expected_msgs = vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: MOCK_GENERATOR_ADDR.to_string(),
+        funds: vec![],
+        msg: to_binary(&GeneratorExecuteMsg::Withdraw {
+            lp_token: Addr::unchecked(MOCK_LP_TOKEN_ADDR),
+            amount: Uint128::zero(),
+        })
+        .unwrap(),
+    }))];
+    expected_msgs.extend(reward_transfer_msgs.clone());
+    assert_eq!(res.messages, expected_msgs);
+
+    // generator pays pending rewards to the contract on lp tokens deposit
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0001".to_string(),
+        amount: Uint128::from(1_000000u128),
+        msg: to_binary(&Cw20HookMsg::LpBond {
+            min_bro_payout: None,
+            lock_epochs: None,
+        })
+        .unwrap(),
+    });
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_LP_TOKEN_ADDR, &[]),
+        msg,
+    )
+    .unwrap();
+
+    deps.querier.with_token_balances(&[
+        (
+            &MOCK_BRO_TOKEN_ADDR.to_string(),
+            &[(
+                &MOCK_BRO_UST_PAIR_ADDR.to_string(),
+                &Uint128::from(100_000000u128),
+            )],
+        ),
+        (
+            &MOCK_ASTRO_TOKEN_ADDR.to_string(),
+            &[(
+                &MOCK_CONTRACT_ADDR.to_string(),
+                &Uint128::from(10_000000u128),
+            )],
+        ),
+        (
+            &MOCK_PROXY_REWARD_TOKEN_ADDR.to_string(),
+            &[(
+                &MOCK_CONTRACT_ADDR.to_string(),
+                &Uint128::from(5_000000u128),
+            )],
+        ),
+    ]);
+
+    // rewards received on deposit are harvested along with pending rewards
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::HarvestLpRewards {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[1..],
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_ASTRO_TOKEN_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "treasury".to_string(),
+                    amount: Uint128::from(20_000000u128),
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_PROXY_REWARD_TOKEN_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "treasury".to_string(),
+                    amount: Uint128::from(10_000000u128),
+                })
+                .unwrap(),
+            })),
+        ]
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            Attribute::new("action", "harvest_lp_rewards"),
+            Attribute::new("reward_amount", "20000000"),
+            Attribute::new("proxy_reward_amount", "10000000"),
+        ]
+    );
+
+    // reward token balances are transferred to treasury
+    deps.querier.with_token_balances(&[(
+        &MOCK_BRO_TOKEN_ADDR.to_string(),
+        &[(
+            &MOCK_BRO_UST_PAIR_ADDR.to_string(),
+            &Uint128::from(100_000000u128),
+        )],
+    )]);
+
+    // error: unauthorized
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::UnstakeLp {
+            amount: Uint128::from(10_000000u128),
+        },
+    );
+    match res {
+        Err(ContractError::Unauthorized {}) => assert_eq!(true, true),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    // unstake lp tokens to treasury
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UnstakeLp {
+            amount: Uint128::from(10_000000u128),
+        },
+    )
+    .unwrap();
+
+    let mut expected_msgs = vec![
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_GENERATOR_ADDR.to_string(),
+            funds: vec![],
+            msg: to_binary(&GeneratorExecuteMsg::Withdraw {
+                lp_token: Addr::unchecked(MOCK_LP_TOKEN_ADDR),
+                amount: Uint128::from(10_000000u128),
+            })
+            .unwrap(),
+        })),
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_LP_TOKEN_ADDR.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "treasury".to_string(),
+                amount: Uint128::from(10_000000u128),
+            })
+            .unwrap(),
+        })),
+    ];
+    expected_msgs.extend(reward_transfer_msgs.clone());
+    assert_eq!(res.messages, expected_msgs);
+
+    // whole deposit is withdrawn to treasury before switching lp token or generator
+    let withdraw_deposit_msgs = |generator: &str, lp_token: &str| {
+        let mut msgs = vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: generator.to_string(),
+                funds: vec![],
+                msg: to_binary(&GeneratorExecuteMsg::Withdraw {
+                    lp_token: Addr::unchecked(lp_token),
+                    amount: Uint128::from(20_000000u128),
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: lp_token.to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "treasury".to_string(),
+                    amount: Uint128::from(20_000000u128),
+                })
+                .unwrap(),
+            })),
+        ];
+        msgs.extend(reward_transfer_msgs.clone());
+        msgs
+    };
+    deps.querier
+        .with_generator_deposit(Uint128::from(20_000000u128));
+
+    // switch lp token
+    let msg = ExecuteMsg::UpdateBondingModeConfig {
+        ust_bonding_reward_ratio_normal: None,
+        lp_token_normal: Some("lptoken0001".to_string()),
+        lp_bonding_discount_normal: None,
+        vesting_period_blocks_normal: None,
+        staking_contract_community: None,
+        epochs_locked_community: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        withdraw_deposit_msgs(MOCK_GENERATOR_ADDR, MOCK_LP_TOKEN_ADDR)
+    );
+
+    // error: unauthorized
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::DisableLpStaking {},
+    );
+    match res {
+        Err(ContractError::Unauthorized {}) => assert_eq!(true, true),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    // disable lp staking
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::DisableLpStaking {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        withdraw_deposit_msgs(MOCK_GENERATOR_ADDR, "lptoken0001")
+    );
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.lp_staking, None);
+
+    // error: lp staking is disabled
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::HarvestLpRewards {},
+    );
+    match res {
+        Err(ContractError::LpStakingDisabled {}) => assert_eq!(true, true),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    // enable lp staking again, nothing to withdraw
+    let update_generator_msg = |generator: &str| ExecuteMsg::UpdateConfig {
+        min_bro_payout: None,
+        rewards_pool_contract: None,
+        treasury_contract: None,
+        astroport_factory: None,
+        oracle_contract: None,
+        ust_bonding_discount: None,
+        discount_control: None,
+        bonding_limits: None,
+        bond_lock: None,
+        lp_staking: Some(Box::new(LpStakingMsg {
+            generator_contract: generator.to_string(),
+        })),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        update_generator_msg(MOCK_GENERATOR_ADDR),
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![]);
+
+    // switch generator
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        update_generator_msg("generator0001"),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        withdraw_deposit_msgs(MOCK_GENERATOR_ADDR, "lptoken0001")
+    );
+
+    // same generator, nothing to withdraw
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        update_generator_msg("generator0001"),
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![]);
 }
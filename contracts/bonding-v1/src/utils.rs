@@ -1,31 +1,62 @@
-use cosmwasm_std::{Addr, Coin, Decimal, QuerierWrapper, StdResult, Uint128};
+use cosmwasm_std::{Addr, Api, Coin, Decimal, QuerierWrapper, StdError, StdResult, Uint128};
 use std::str::FromStr;
 
-use crate::ContractError;
+use crate::{
+    state::{BondLock, BondMarket, PriceSource},
+    ContractError,
+};
+
+use services::{
+    bonding::DiscountControl,
+    querier::{query_epoch_info, query_oracle_price, query_pools, query_staking_config},
+};
 
 use astroport::{
     asset::{Asset, AssetInfo},
     querier::query_supply,
 };
 
+const DECIMAL_FRACTIONAL: u128 = 1_000_000_000_000_000_000;
+
 /// ## Description
-/// Extracts ust amount from provided info.funds input.
+/// Extracts native token amount of specified denom from provided info.funds input.
 /// Otherwise returns [`ContractError`]
 /// ## Params
 /// * **funds** is an object of type [`&[Coin]`]
-pub fn extract_native_token(funds: &[Coin]) -> Result<Asset, ContractError> {
-    if funds.len() != 1 || funds[0].denom != "uusd" || funds[0].amount.is_zero() {
+///
+/// * **denom** is an object of type [`&str`]
+pub fn extract_native_token(funds: &[Coin], denom: &str) -> Result<Asset, ContractError> {
+    if funds.len() != 1 || funds[0].denom != denom || funds[0].amount.is_zero() {
         return Err(ContractError::InvalidFundsInput {});
     }
 
     Ok(Asset {
         info: AssetInfo::NativeToken {
-            denom: "uusd".to_string(),
+            denom: denom.to_string(),
         },
         amount: funds[0].amount,
     })
 }
 
+/// ## Description
+/// Verifies that bond payout is not lower than minimum payout expected by bonder.
+/// Returns [`ContractError`] if it is
+/// ## Params
+/// * **bro_payout** is an object of type [`Uint128`]
+///
+/// * **min_bro_payout** is an [`Option`] of type [`Uint128`]
+pub fn assert_slippage(
+    bro_payout: Uint128,
+    min_bro_payout: Option<Uint128>,
+) -> Result<(), ContractError> {
+    match min_bro_payout {
+        Some(min_bro_payout) if bro_payout < min_bro_payout => {
+            Err(ContractError::BondSlippageExceeded {})
+        }
+        _ => Ok(()),
+    }
+}
+
 /// ## Description
 /// Applies bonding discount for provided token amount
 /// and returns result in the [`Uint128`] object
@@ -39,6 +70,84 @@ pub fn apply_discount(discount_ratio: Decimal, amount: Uint128) -> StdResult<Uin
     Ok(payout)
 }
 
+/// ## Description
+/// Returns bonding discount decreased by bond control variable multiplied by debt ratio
+/// and bounded by min discount in the [`Decimal`] object.
+/// Max discount is returned if dynamic discount is disabled
+/// ## Params
+/// * **max_discount** is an object of type [`Decimal`]
+///
+/// * **discount_control** is an [`Option`] of type [`DiscountControl`]
+///
+/// * **debt** is an object of type [`Uint128`]
+///
+/// * **bonding_balance** is an object of type [`Uint128`]
+pub fn compute_discount(
+    max_discount: Decimal,
+    discount_control: &Option<DiscountControl>,
+    debt: Uint128,
+    bonding_balance: Uint128,
+) -> StdResult<Decimal> {
+    let discount_control = match discount_control {
+        Some(discount_control) => discount_control,
+        None => return Ok(max_discount),
+    };
+
+    let total = debt.checked_add(bonding_balance)?;
+    if total.is_zero() {
+        return Ok(max_discount);
+    }
+
+    // control variable multiplied by debt ratio
+    let discount_decrease = Decimal::from_ratio(debt * discount_control.control_variable, total);
+    if discount_decrease >= max_discount - discount_control.min_discount {
+        return Ok(discount_control.min_discount);
+    }
+
+    Ok(max_discount - discount_decrease)
+}
+
+/// ## Description
+/// Returns staking contract address and extra discount for locking bond payout
+/// for specified amount of epochs. Extra discount grows linearly with lockup period
+/// and reaches max lock discount at max lockup period of staking contract
+/// ## Params
+/// * **querier** is an object of type [`QuerierWrapper`]
+///
+/// * **api** is an object of type [`Api`]
+///
+/// * **bond_lock** is an [`Option`] of type [`BondLock`]
+///
+/// * **lock_epochs** is a field of type [`u64`]
+pub fn query_bond_lock_discount(
+    querier: &QuerierWrapper,
+    api: &dyn Api,
+    bond_lock: &Option<BondLock>,
+    lock_epochs: u64,
+) -> Result<(Addr, Decimal), ContractError> {
+    let bond_lock = bond_lock
+        .as_ref()
+        .ok_or(ContractError::BondLockDisabled {})?;
+    let staking_contract = api.addr_humanize(&bond_lock.staking_contract)?;
+
+    let lockup_config = query_staking_config(querier, staking_contract.clone())?.lockup_config;
+    if lock_epochs < lockup_config.min_lockup_period_epochs
+        || lock_epochs > lockup_config.max_lockup_period_epochs
+    {
+        return Err(ContractError::InvalidLockupPeriod {});
+    }
+
+    // max lock discount in decimal fractional units multiplied by lockup period ratio
+    let lock_discount = Decimal::from_ratio(
+        (Uint128::from(DECIMAL_FRACTIONAL) * bond_lock.max_lock_discount)
+            .checked_mul(Uint128::from(lock_epochs))?,
+        Uint128::from(DECIMAL_FRACTIONAL)
+            .checked_mul(Uint128::from(lockup_config.max_lockup_period_epochs))?,
+    );
+
+    Ok((staking_contract, lock_discount))
+}
+
 /// ## Description
 /// Returns the share of assets in the [`Uint128`] object
 /// ## Params
@@ -63,3 +172,129 @@ pub fn get_share_in_assets(
 
     Ok((bro_pool.amount * share_ratio, ust_pool.amount * share_ratio))
 }
+
+/// ## Description
+/// Returns bro amount for specified amount of bond market asset in the [`Uint128`] object.
+/// Lp token amount is valued as bro share plus paired asset share priced in bro
+/// ## Params
+/// * **querier** is an object of type [`QuerierWrapper`]
+///
+/// * **api** is an object of type [`Api`]
+///
+/// * **astroport_factory** is an object of type [`Addr`]
+///
+/// * **bro_token** is an object of type [`Addr`]
+///
+/// * **market** is an object of type [`BondMarket`]
+///
+/// * **amount** is an object of type [`Uint128`]
+pub fn query_bond_market_bro_amount(
+    querier: &QuerierWrapper,
+    api: &dyn Api,
+    astroport_factory: Addr,
+    bro_token: Addr,
+    market: &BondMarket,
+    amount: Uint128,
+) -> StdResult<Uint128> {
+    let lp_pair = match &market.lp_pair {
+        Some(lp_pair) => lp_pair,
+        None => {
+            return query_price_in_bro(
+                querier,
+                api,
+                &market.price_source,
+                market.asset_info.clone(),
+                amount,
+            )
+        }
+    };
+
+    let lp_token = match &market.asset_info {
+        AssetInfo::Token { contract_addr } => contract_addr.clone(),
+        AssetInfo::NativeToken { .. } => {
+            return Err(StdError::generic_err("lp token must be a cw20 token"))
+        }
+    };
+
+    let bro_info = AssetInfo::Token {
+        contract_addr: bro_token,
+    };
+    let pools = query_pools(
+        querier,
+        astroport_factory,
+        &[bro_info.clone(), lp_pair.paired_asset.clone()],
+    )?;
+    let (bro_pool, paired_pool) = if pools[0].info == bro_info {
+        (&pools[0], &pools[1])
+    } else {
+        (&pools[1], &pools[0])
+    };
+
+    let (bro_share, paired_share) =
+        get_share_in_assets(querier, bro_pool, paired_pool, amount, lp_token)?;
+
+    let paired_share_in_bro = query_price_in_bro(
+        querier,
+        api,
+        &market.price_source,
+        paired_pool.info.clone(),
+        paired_share,
+    )?;
+
+    Ok(bro_share.checked_add(paired_share_in_bro)?)
+}
+
+/// ## Description
+/// Returns bro amount for specified asset amount using provided price source
+/// ## Params
+/// * **querier** is an object of type [`QuerierWrapper`]
+///
+/// * **api** is an object of type [`Api`]
+///
+/// * **price_source** is an object of type [`PriceSource`]
+///
+/// * **asset_info** is an object of type [`AssetInfo`]
+///
+/// * **amount** is an object of type [`Uint128`]
+fn query_price_in_bro(
+    querier: &QuerierWrapper,
+    api: &dyn Api,
+    price_source: &PriceSource,
+    asset_info: AssetInfo,
+    amount: Uint128,
+) -> StdResult<Uint128> {
+    match price_source {
+        PriceSource::Oracle { oracle_contract } => Ok(query_oracle_price(
+            querier,
+            api.addr_humanize(oracle_contract)?,
+            asset_info,
+            amount,
+        )?
+        .amount),
+        PriceSource::Fixed { bro_per_asset } => Ok(amount * *bro_per_asset),
+    }
+}
+
+/// ## Description
+/// Returns current epoch and block at which it ends using epoch-manager epoch length
+/// ## Params
+/// * **querier** is an object of type [`QuerierWrapper`]
+///
+/// * **epoch_manager_contract** is an object of type [`Addr`]
+///
+/// * **current_block** is a field of type [`u64`]
+pub fn query_current_epoch(
+    querier: &QuerierWrapper,
+    epoch_manager_contract: Addr,
+    current_block: u64,
+) -> StdResult<(u64, u64)> {
+    let epoch_blocks = query_epoch_info(querier, epoch_manager_contract)?.epoch;
+    if epoch_blocks == 0 {
+        return Err(StdError::generic_err(
+            "epoch length must be greater than zero",
+        ));
+    }
+
+    let epoch = current_block / epoch_blocks;
+    Ok((epoch, (epoch + 1) * epoch_blocks))
+}
@@ -6,9 +6,13 @@ use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 use services::{
     ownership_proposal::OwnershipProposalResponse,
     staking::{
-        ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, LockupConfigResponse,
-        LockupInfoResponse, MigrateMsg, QueryMsg, StakerInfoResponse, StateResponse,
-        WithdrawalInfoResponse, WithdrawalsResponse,
+        BoostInfoResponse, ClaimPermissionsResponse, ConfigResponse, Cw20HookMsg,
+        EstimatedAprResponse, ExecuteMsg, InstantiateMsg, LockupConfigResponse, LockupInfoResponse,
+        LockupStatsResponse, LockupsMigrationProgressResponse, MigrateMsg, PendingRewardsResponse,
+        QueryMsg, RewardAssetsResponse, RewardHistoryResponse, SimulateLockupResponse,
+        SimulateRewardsResponse, StakerHistoryResponse, StakerInfoResponse, StakersResponse,
+        StateResponse, TotalVotingPowerResponse, VotingPowerResponse, WithdrawalInfoResponse,
+        WithdrawalsResponse,
     },
 };
 
@@ -28,6 +32,20 @@ fn main() {
     export_schema(&schema_for!(LockupInfoResponse), &out_dir);
     export_schema(&schema_for!(StateResponse), &out_dir);
     export_schema(&schema_for!(StakerInfoResponse), &out_dir);
+    export_schema(&schema_for!(SimulateRewardsResponse), &out_dir);
+    export_schema(&schema_for!(SimulateLockupResponse), &out_dir);
+    export_schema(&schema_for!(BoostInfoResponse), &out_dir);
+    export_schema(&schema_for!(ClaimPermissionsResponse), &out_dir);
+    export_schema(&schema_for!(StakersResponse), &out_dir);
+    export_schema(&schema_for!(RewardHistoryResponse), &out_dir);
+    export_schema(&schema_for!(StakerHistoryResponse), &out_dir);
+    export_schema(&schema_for!(EstimatedAprResponse), &out_dir);
+    export_schema(&schema_for!(LockupStatsResponse), &out_dir);
+    export_schema(&schema_for!(LockupsMigrationProgressResponse), &out_dir);
+    export_schema(&schema_for!(RewardAssetsResponse), &out_dir);
+    export_schema(&schema_for!(PendingRewardsResponse), &out_dir);
+    export_schema(&schema_for!(VotingPowerResponse), &out_dir);
+    export_schema(&schema_for!(TotalVotingPowerResponse), &out_dir);
     export_schema(&schema_for!(WithdrawalsResponse), &out_dir);
     export_schema(&schema_for!(WithdrawalInfoResponse), &out_dir);
     export_schema(&schema_for!(OwnershipProposalResponse), &out_dir);
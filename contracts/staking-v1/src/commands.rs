@@ -1,42 +1,70 @@
+use astroport::asset::{Asset, AssetInfo};
 use cosmwasm_std::{
-    to_binary, Addr, Attribute, CosmosMsg, Decimal, DepsMut, Env, MessageInfo, Response, StdError,
-    Uint128, WasmMsg,
+    to_binary, Addr, Attribute, CanonicalAddr, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo,
+    Order, Response, StdError, StdResult, Storage, Uint128, WasmMsg,
 };
 use cw20::{Cw20ExecuteMsg, Expiration};
+use cw_storage_plus::Bound;
 
 use crate::{
     error::ContractError,
+    migration::{
+        load_lockups_migration_progress, read_stakers_v120, store_lockups_migration_progress,
+    },
     state::{
-        load_config, load_state, load_withdrawals, read_staker_info, remove_staker_info,
-        store_config, store_staker_info, store_state, store_withdrawals, WithdrawalInfo,
+        add_asset_rewards, load_config, load_state, load_total_working_balance, load_withdrawals,
+        read_claim_operator, read_reward_asset, read_reward_assets, read_reward_recipient,
+        read_staker_info, remove_claim_operator, remove_staker_info, store_claim_operator,
+        store_config, store_migrated_staker_info, store_reward_asset, store_reward_recipient,
+        store_reward_snapshot, store_staker_history_entry, store_staker_info, store_state,
+        store_withdrawals, sync_state, take_asset_rewards, update_voting_weight_blocks,
+        BoostConfig, Config, RewardAssetInfo, StakerHistoryEntry, StakerInfo, State,
+        WithdrawalInfo,
     },
 };
 
 use services::{
-    bbro_minter::ExecuteMsg as BbroMintMsg, querier::query_epoch_info, staking::StakeType,
+    bbro_minter::ExecuteMsg as BbroMintMsg,
+    querier::{query_epoch_info, query_token_balance, query_token_supply},
+    staking::{
+        BoostParams, EarlyUnlockPenalty, EarlyUnlockPenaltyType, InstantUnstake,
+        InstantUnstakeFeeDestination, StakeType, UnstakeType,
+    },
 };
 
 /// ## Description
-/// Distributes received reward.
+/// Maximum amount of registered reward assets
+const MAX_REWARD_ASSETS: usize = 10;
+
+/// ## Description
+/// Maximum and default amount of stakers migrated to epoch lockups in one batch
+const MAX_LOCKUPS_MIGRATION_LIMIT: u32 = 30;
+const DEFAULT_LOCKUPS_MIGRATION_LIMIT: u32 = 10;
+
+/// ## Description
+/// Distributes received reward by starting new reward stream over the next epoch.
 /// Returns [`Response`] with specified attributes and messages if operation was successful,
 /// otherwise returns [`ContractError`]
 /// ## Params
 /// * **deps** is an object of type [`DepsMut`]
 ///
+/// * **env** is an object of type [`Env`]
+///
 /// * **reward_amount** is an object of type [`Uint128`]
 ///
 /// * **distributed_at_block** is a field of type [`u64`]
 pub fn distribute_reward(
     deps: DepsMut,
+    env: Env,
     reward_amount: Uint128,
     distributed_at_block: u64,
 ) -> Result<Response, ContractError> {
     let config = load_config(deps.storage)?;
-    let mut state = load_state(deps.storage)?;
+    let total_working_balance = load_total_working_balance(deps.storage)?;
 
-    // because total_stake_amount is zero and we cannot distribute received rewards
+    // because total_working_balance is zero and we cannot distribute received rewards
     // we send it back to rewards pool
-    if state.total_stake_amount.is_zero() {
+    if total_working_balance.is_zero() {
         return Ok(Response::new()
             .add_messages(vec![CosmosMsg::Wasm(WasmMsg::Execute {
                 contract_addr: deps.api.addr_humanize(&config.bro_token)?.to_string(),
@@ -55,11 +83,21 @@ pub fn distribute_reward(
             ]));
     }
 
-    state.last_distribution_block = distributed_at_block;
-    state.global_reward_index =
-        state.global_reward_index + Decimal::from_ratio(reward_amount, state.total_stake_amount);
+    let epoch_info = query_epoch_info(
+        &deps.querier,
+        deps.api.addr_humanize(&config.epoch_manager_contract)?,
+    )?;
+
+    // received reward is streamed linearly over the next epoch
+    let mut state = sync_state(deps.storage, env.block.height)?;
+    state.add_reward_stream(
+        reward_amount,
+        env.block.height,
+        std::cmp::max(epoch_info.epoch, 1),
+    );
 
     store_state(deps.storage, &state)?;
+    store_reward_snapshot(deps.storage, distributed_at_block, &state, reward_amount)?;
 
     Ok(Response::new().add_attributes(vec![
         ("action", "distribute_reward"),
@@ -91,7 +129,7 @@ pub fn stake(
     let sender_raw = deps.api.addr_canonicalize(&sender_addr.to_string())?;
 
     let config = load_config(deps.storage)?;
-    let mut state = load_state(deps.storage)?;
+    let mut state = sync_state(deps.storage, env.block.height)?;
     let mut staker_info = read_staker_info(deps.storage, &sender_raw, env.block.height)?;
 
     if amount < config.min_staking_amount {
@@ -105,6 +143,14 @@ pub fn stake(
 
     staker_info.compute_normal_bbro_reward(&epoch_info, env.block.height)?;
     staker_info.compute_bro_reward(&state)?;
+    if staker_info.auto_compound {
+        staker_info.compound_bro_reward(&mut state)?;
+    }
+
+    let lockup_epochs = match stake_type {
+        StakeType::Unlocked {} => None,
+        StakeType::Locked { epochs_locked } => Some(epochs_locked),
+    };
 
     let msgs: Vec<CosmosMsg> = match stake_type {
         StakeType::Unlocked {} => {
@@ -140,12 +186,35 @@ pub fn stake(
         }
     };
 
-    staker_info.unlock_expired_lockups(&env.block, &epoch_info, config.prev_epoch_blocks)?;
-    store_staker_info(deps.storage, &sender_raw, &staker_info)?;
+    staker_info.unlock_expired_lockups(&env.block, &epoch_info)?;
 
     // increase total stake amount
     state.total_stake_amount = state.total_stake_amount.checked_add(amount)?;
+
+    checkpoint_boost(
+        deps.as_ref(),
+        &config,
+        &state,
+        &sender_raw,
+        &mut staker_info,
+    )?;
+    store_staker_info(
+        deps.storage,
+        &sender_raw,
+        &staker_info,
+        env.block.height,
+        epoch_info.epoch,
+    )?;
     store_state(deps.storage, &state)?;
+    record_staker_action(
+        deps.storage,
+        &config,
+        &sender_raw,
+        env.block.height,
+        "stake",
+        amount,
+        lockup_epochs,
+    )?;
 
     Ok(Response::new().add_messages(msgs).add_attributes(vec![
         ("action", "stake"),
@@ -179,7 +248,7 @@ pub fn community_bond_lock(
     let sender_raw = deps.api.addr_canonicalize(&sender)?;
 
     let config = load_config(deps.storage)?;
-    let mut state = load_state(deps.storage)?;
+    let mut state = sync_state(deps.storage, env.block.height)?;
     let mut staker_info = read_staker_info(deps.storage, &sender_raw, env.block.height)?;
 
     if amount < config.min_staking_amount {
@@ -193,6 +262,9 @@ pub fn community_bond_lock(
 
     staker_info.compute_normal_bbro_reward(&epoch_info, env.block.height)?;
     staker_info.compute_bro_reward(&state)?;
+    if staker_info.auto_compound {
+        staker_info.compound_bro_reward(&mut state)?;
+    }
 
     if !config.lockup_config.valid_lockup_period(epochs_locked) {
         return Err(ContractError::InvalidLockupPeriod {});
@@ -202,13 +274,35 @@ pub fn community_bond_lock(
         staker_info.compute_premium_bbro_reward(&config.lockup_config, epochs_locked, amount);
 
     staker_info.add_lockup(env.block.height, amount, epochs_locked)?;
-    staker_info.unlock_expired_lockups(&env.block, &epoch_info, config.prev_epoch_blocks)?;
-
-    store_staker_info(deps.storage, &sender_raw, &staker_info)?;
+    staker_info.unlock_expired_lockups(&env.block, &epoch_info)?;
 
     // increase total stake amount
     state.total_stake_amount = state.total_stake_amount.checked_add(amount)?;
+
+    checkpoint_boost(
+        deps.as_ref(),
+        &config,
+        &state,
+        &sender_raw,
+        &mut staker_info,
+    )?;
+    store_staker_info(
+        deps.storage,
+        &sender_raw,
+        &staker_info,
+        env.block.height,
+        epoch_info.epoch,
+    )?;
     store_state(deps.storage, &state)?;
+    record_staker_action(
+        deps.storage,
+        &config,
+        &sender_raw,
+        env.block.height,
+        "community_bond_stake",
+        amount,
+        Some(epochs_locked),
+    )?;
 
     Ok(Response::new()
         .add_messages(vec![CosmosMsg::Wasm(WasmMsg::Execute {
@@ -264,7 +358,7 @@ pub fn lockup_staked(
         deps.api.addr_humanize(&config.epoch_manager_contract)?,
     )?;
 
-    staker_info.unlock_expired_lockups(&env.block, &epoch_info, config.prev_epoch_blocks)?;
+    staker_info.unlock_expired_lockups(&env.block, &epoch_info)?;
     if staker_info.unlocked_stake_amount < amount {
         return Err(ContractError::ForbiddenToLockupMoreThanUnlocked {});
     }
@@ -282,7 +376,22 @@ pub fn lockup_staked(
 
     staker_info.add_lockup(env.block.height, amount, epochs_locked)?;
     staker_info.unlocked_stake_amount = staker_info.unlocked_stake_amount.checked_sub(amount)?;
-    store_staker_info(deps.storage, &sender_raw, &staker_info)?;
+    store_staker_info(
+        deps.storage,
+        &sender_raw,
+        &staker_info,
+        env.block.height,
+        epoch_info.epoch,
+    )?;
+    record_staker_action(
+        deps.storage,
+        &config,
+        &sender_raw,
+        env.block.height,
+        "lockup_staked",
+        amount,
+        Some(epochs_locked),
+    )?;
 
     Ok(Response::new()
         .add_messages(vec![CosmosMsg::Wasm(WasmMsg::Execute {
@@ -308,7 +417,8 @@ pub fn lockup_staked(
 }
 
 /// ## Description
-/// Unstake staked amount of tokens. Tokens will be claimable only after passing the unstaking period.
+/// Locks lockup again starting from current block for its remaining epochs
+/// plus specified amount of additional epochs.
 /// Returns [`Response`] with specified attributes and messages if operation was successful,
 /// otherwise returns [`ContractError`]
 /// ## Params
@@ -318,17 +428,19 @@ pub fn lockup_staked(
 ///
 /// * **info** is an object of type [`MessageInfo`]
 ///
-/// * **amount** is an object of type [`Uint128`]
-pub fn unstake(
+/// * **lockup_index** is a field of type [`u64`]
+///
+/// * **additional_epochs** is a field of type [`u64`]
+pub fn extend_lockup(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    amount: Uint128,
+    lockup_index: u64,
+    additional_epochs: u64,
 ) -> Result<Response, ContractError> {
     let config = load_config(deps.storage)?;
-    let mut state = load_state(deps.storage)?;
 
-    let sender_addr_raw = deps.api.addr_canonicalize(&info.sender.to_string())?;
+    let sender_addr_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
     let mut staker_info = read_staker_info(deps.storage, &sender_addr_raw, env.block.height)?;
 
     let epoch_info = query_epoch_info(
@@ -336,95 +448,79 @@ pub fn unstake(
         deps.api.addr_humanize(&config.epoch_manager_contract)?,
     )?;
 
-    staker_info.unlock_expired_lockups(&env.block, &epoch_info, config.prev_epoch_blocks)?;
-    if staker_info.unlocked_stake_amount < amount {
-        return Err(ContractError::ForbiddenToUnstakeMoreThanUnlocked {});
-    }
-
-    staker_info.compute_normal_bbro_reward(&epoch_info, env.block.height)?;
-    staker_info.compute_bro_reward(&state)?;
+    staker_info.unlock_expired_lockups(&env.block, &epoch_info)?;
 
-    // decrease stake amount
-    state.total_stake_amount = state.total_stake_amount.checked_sub(amount)?;
-    staker_info.unlocked_stake_amount = staker_info.unlocked_stake_amount.checked_sub(amount)?;
+    let lockup_index = lockup_index as usize;
+    let lockup = staker_info
+        .lockups
+        .get(lockup_index)
+        .cloned()
+        .ok_or(ContractError::LockupNotFound {})?;
 
-    if staker_info.can_be_removed()? {
-        remove_staker_info(deps.storage, &sender_addr_raw);
-    } else {
-        store_staker_info(deps.storage, &sender_addr_raw, &staker_info)?;
+    let remaining_epochs = lockup.remaining_epochs(env.block.height, epoch_info.epoch);
+    let epochs_locked = remaining_epochs + additional_epochs;
+    if additional_epochs == 0 || !config.lockup_config.valid_lockup_period(epochs_locked) {
+        return Err(ContractError::InvalidLockupPeriod {});
     }
 
-    store_state(deps.storage, &state)?;
-
-    // create withdrawal info
-    let claimable_at = Expiration::AtHeight(env.block.height + config.unstake_period_blocks);
-    let mut staker_withdrawals = load_withdrawals(deps.storage, &sender_addr_raw)?;
-    staker_withdrawals.push(WithdrawalInfo {
-        amount,
-        claimable_at,
-    });
-
-    store_withdrawals(deps.storage, &sender_addr_raw, &staker_withdrawals)?;
-
-    Ok(Response::new().add_attributes(vec![
-        ("action", "unstake"),
-        ("staker", &info.sender.to_string()),
-        ("amount", &amount.to_string()),
-    ]))
-}
-
-/// ## Description
-/// Withdraw the amount of tokens that have already passed the unstaking period.
-/// Returns [`Response`] with specified attributes and messages if operation was successful,
-/// otherwise returns [`ContractError`]
-/// ## Params
-/// * **deps** is an object of type [`DepsMut`]
-///
-/// * **env** is an object of type [`Env`]
-///
-/// * **info** is an object of type [`MessageInfo`]
-pub fn withdraw(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
-    let config = load_config(deps.storage)?;
-    let sender_addr_raw = deps.api.addr_canonicalize(&info.sender.to_string())?;
-
-    let mut amount = Uint128::zero();
-    // if withdrawal passed unstaking period add claimable amount and remove it from withdrawals list
-    let staker_withdrawals: Vec<WithdrawalInfo> = load_withdrawals(deps.storage, &sender_addr_raw)?
-        .into_iter()
-        .filter(|c| {
-            if c.claimable_at.is_expired(&env.block) {
-                amount += c.amount;
-                false
-            } else {
-                true
-            }
-        })
-        .collect();
+    // premium reward for remaining epochs was already minted when lockup was created
+    let bbro_premium_lockup_reward = staker_info
+        .compute_premium_bbro_reward(&config.lockup_config, epochs_locked, lockup.amount)
+        .saturating_sub(staker_info.compute_premium_bbro_reward(
+            &config.lockup_config,
+            remaining_epochs,
+            lockup.amount,
+        ));
 
-    if amount.is_zero() {
-        return Err(ContractError::NothingToClaim {});
+    if bbro_premium_lockup_reward.is_zero() {
+        return Err(ContractError::LockupPremiumRewardIsZero {});
     }
 
-    store_withdrawals(deps.storage, &sender_addr_raw, &staker_withdrawals)?;
+    staker_info.relock(lockup_index, env.block.height, epochs_locked)?;
+    store_staker_info(
+        deps.storage,
+        &sender_addr_raw,
+        &staker_info,
+        env.block.height,
+        epoch_info.epoch,
+    )?;
+    record_staker_action(
+        deps.storage,
+        &config,
+        &sender_addr_raw,
+        env.block.height,
+        "extend_lockup",
+        lockup.amount,
+        Some(epochs_locked),
+    )?;
 
     Ok(Response::new()
         .add_messages(vec![CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: deps.api.addr_humanize(&config.bro_token)?.to_string(),
+            contract_addr: deps
+                .api
+                .addr_humanize(&config.bbro_minter_contract)?
+                .to_string(),
             funds: vec![],
-            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            msg: to_binary(&BbroMintMsg::Mint {
                 recipient: info.sender.to_string(),
-                amount,
+                amount: bbro_premium_lockup_reward,
             })?,
         })])
         .add_attributes(vec![
-            ("action", "withdraw"),
-            ("staker", &info.sender.to_string()),
-            ("amount", &amount.to_string()),
+            ("action", "extend_lockup"),
+            ("staker", info.sender.as_str()),
+            ("lockup_amount", &lockup.amount.to_string()),
+            ("epochs_locked", &epochs_locked.to_string()),
+            (
+                "bbro_premium_lockup_reward",
+                &bbro_premium_lockup_reward.to_string(),
+            ),
         ]))
 }
 
 /// ## Description
-/// Claim available bro reward amount.
+/// Merges specified lockups into single lockup locked starting from current block
+/// for the longest remaining period among them.
 /// Returns [`Response`] with specified attributes and messages if operation was successful,
 /// otherwise returns [`ContractError`]
 /// ## Params
@@ -433,56 +529,121 @@ pub fn withdraw(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response,
 /// * **env** is an object of type [`Env`]
 ///
 /// * **info** is an object of type [`MessageInfo`]
-pub fn claim_bro_rewards(
+///
+/// * **indices** is a [`Vec`] of type [`u64`]
+pub fn merge_lockups(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
+    indices: Vec<u64>,
 ) -> Result<Response, ContractError> {
     let config = load_config(deps.storage)?;
-    let state = load_state(deps.storage)?;
 
-    let sender_addr_raw = deps.api.addr_canonicalize(&info.sender.to_string())?;
+    let sender_addr_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
     let mut staker_info = read_staker_info(deps.storage, &sender_addr_raw, env.block.height)?;
 
-    staker_info.compute_bro_reward(&state)?;
-
-    let amount = staker_info.pending_bro_reward;
-    if amount == Uint128::zero() {
-        return Err(ContractError::NothingToClaim {});
-    }
-
     let epoch_info = query_epoch_info(
         &deps.querier,
         deps.api.addr_humanize(&config.epoch_manager_contract)?,
     )?;
 
-    staker_info.pending_bro_reward = Uint128::zero();
-    staker_info.unlock_expired_lockups(&env.block, &epoch_info, config.prev_epoch_blocks)?;
+    staker_info.unlock_expired_lockups(&env.block, &epoch_info)?;
 
-    if staker_info.can_be_removed()? {
-        remove_staker_info(deps.storage, &sender_addr_raw);
-    } else {
-        store_staker_info(deps.storage, &sender_addr_raw, &staker_info)?;
+    let mut indices: Vec<usize> = indices.into_iter().map(|i| i as usize).collect();
+    indices.sort_unstable();
+    indices.dedup();
+
+    if indices.len() < 2 {
+        return Err(ContractError::InvalidLockupsToMerge {});
     }
 
-    Ok(Response::new()
-        .add_messages(vec![CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: deps.api.addr_humanize(&config.bro_token)?.to_string(),
+    let mut lockups: Vec<(Uint128, u64)> = vec![];
+    for index in indices.iter() {
+        let lockup = staker_info
+            .lockups
+            .get(*index)
+            .ok_or(ContractError::LockupNotFound {})?;
+
+        lockups.push((
+            lockup.amount,
+            lockup.remaining_epochs(env.block.height, epoch_info.epoch),
+        ));
+    }
+
+    let epochs_locked = lockups
+        .iter()
+        .map(|(_, remaining_epochs)| *remaining_epochs)
+        .max()
+        .unwrap_or_default();
+
+    if !config.lockup_config.valid_lockup_period(epochs_locked) {
+        return Err(ContractError::InvalidLockupPeriod {});
+    }
+
+    // premium reward is minted only for the difference between merged and remaining periods
+    let mut bbro_premium_lockup_reward = Uint128::zero();
+    let mut lockup_amount = Uint128::zero();
+    for (amount, remaining_epochs) in lockups {
+        let extra_premium = staker_info
+            .compute_premium_bbro_reward(&config.lockup_config, epochs_locked, amount)
+            .saturating_sub(staker_info.compute_premium_bbro_reward(
+                &config.lockup_config,
+                remaining_epochs,
+                amount,
+            ));
+
+        bbro_premium_lockup_reward = bbro_premium_lockup_reward.checked_add(extra_premium)?;
+        lockup_amount = lockup_amount.checked_add(amount)?;
+    }
+
+    staker_info.merge_lockups(&indices, env.block.height, epochs_locked)?;
+    store_staker_info(
+        deps.storage,
+        &sender_addr_raw,
+        &staker_info,
+        env.block.height,
+        epoch_info.epoch,
+    )?;
+    record_staker_action(
+        deps.storage,
+        &config,
+        &sender_addr_raw,
+        env.block.height,
+        "merge_lockups",
+        lockup_amount,
+        Some(epochs_locked),
+    )?;
+
+    let mut msgs: Vec<CosmosMsg> = vec![];
+    if !bbro_premium_lockup_reward.is_zero() {
+        msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: deps
+                .api
+                .addr_humanize(&config.bbro_minter_contract)?
+                .to_string(),
             funds: vec![],
-            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            msg: to_binary(&BbroMintMsg::Mint {
                 recipient: info.sender.to_string(),
-                amount,
+                amount: bbro_premium_lockup_reward,
             })?,
-        })])
-        .add_attributes(vec![
-            ("action", "claim_bro_rewards"),
-            ("staker", &info.sender.to_string()),
-            ("amount", &amount.to_string()),
-        ]))
+        }));
+    }
+
+    Ok(Response::new().add_messages(msgs).add_attributes(vec![
+        ("action", "merge_lockups"),
+        ("staker", info.sender.as_str()),
+        ("lockup_amount", &lockup_amount.to_string()),
+        ("epochs_locked", &epochs_locked.to_string()),
+        (
+            "bbro_premium_lockup_reward",
+            &bbro_premium_lockup_reward.to_string(),
+        ),
+    ]))
 }
 
 /// ## Description
-/// Claim available bbro reward amount.
+/// Moves specified amount of lockup to unlocked staked amount before lockup expiration.
+/// Staker pays a penalty either by burning bbro or with bro fee sent to the rewards pool.
 /// Returns [`Response`] with specified attributes and messages if operation was successful,
 /// otherwise returns [`ContractError`]
 /// ## Params
@@ -491,14 +652,26 @@ pub fn claim_bro_rewards(
 /// * **env** is an object of type [`Env`]
 ///
 /// * **info** is an object of type [`MessageInfo`]
-pub fn claim_bbro_rewards(
+///
+/// * **lockup_index** is a field of type [`u64`]
+///
+/// * **amount** is an object of type [`Uint128`]
+pub fn early_unlock(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
+    lockup_index: u64,
+    amount: Uint128,
 ) -> Result<Response, ContractError> {
     let config = load_config(deps.storage)?;
+    let mut state = sync_state(deps.storage, env.block.height)?;
 
-    let sender_addr_raw = deps.api.addr_canonicalize(&info.sender.to_string())?;
+    let penalty_type = match &config.lockup_config.early_unlock_penalty {
+        Some(EarlyUnlockPenalty { penalty_type, .. }) => penalty_type.clone(),
+        None => return Err(ContractError::EarlyUnlockIsNotEnabled {}),
+    };
+
+    let sender_addr_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
     let mut staker_info = read_staker_info(deps.storage, &sender_addr_raw, env.block.height)?;
 
     let epoch_info = query_epoch_info(
@@ -507,72 +680,1778 @@ pub fn claim_bbro_rewards(
     )?;
 
     staker_info.compute_normal_bbro_reward(&epoch_info, env.block.height)?;
+    staker_info.compute_bro_reward(&state)?;
+    if staker_info.auto_compound {
+        staker_info.compound_bro_reward(&mut state)?;
+    }
+    staker_info.unlock_expired_lockups(&env.block, &epoch_info)?;
 
-    let bbro_reward = staker_info.pending_bbro_reward;
-    if bbro_reward.is_zero() {
-        return Err(ContractError::NothingToClaim {});
+    let lockup_index = lockup_index as usize;
+    let lockup = staker_info
+        .lockups
+        .get(lockup_index)
+        .cloned()
+        .ok_or(ContractError::LockupNotFound {})?;
+
+    if amount.is_zero() || amount > lockup.amount {
+        return Err(ContractError::InvalidEarlyUnlockAmount {});
     }
 
-    staker_info.pending_bbro_reward = Uint128::zero();
-    staker_info.unlock_expired_lockups(&env.block, &epoch_info, config.prev_epoch_blocks)?;
-    store_staker_info(deps.storage, &sender_addr_raw, &staker_info)?;
+    let locked_at_block = lockup.locked_at_block;
+    let epochs_locked = lockup.epochs_locked;
+
+    let lockup_blocks = epochs_locked * epoch_info.epoch;
+    let remaining_blocks = (locked_at_block + lockup_blocks).saturating_sub(env.block.height);
+    let penalty_rate = config
+        .lockup_config
+        .early_unlock_penalty_rate(lockup_blocks, remaining_blocks)
+        .ok_or(ContractError::EarlyUnlockIsNotEnabled {})?;
+
+    staker_info.early_unlock(lockup_index, amount)?;
+
+    let mut msgs: Vec<CosmosMsg> = vec![];
+    let mut attributes: Vec<Attribute> = vec![
+        Attribute::new("action", "early_unlock"),
+        Attribute::new("staker", info.sender.as_str()),
+        Attribute::new("amount", amount.to_string()),
+        Attribute::new("penalty_rate", penalty_rate.to_string()),
+    ];
+
+    match penalty_type {
+        EarlyUnlockPenaltyType::BurnBbro {} => {
+            let bbro_premium_lockup_reward = staker_info.compute_premium_bbro_reward(
+                &config.lockup_config,
+                epochs_locked,
+                amount,
+            );
+            let bbro_burn_amount = bbro_premium_lockup_reward * penalty_rate;
+
+            if !bbro_burn_amount.is_zero() {
+                msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: deps
+                        .api
+                        .addr_humanize(&config.bbro_minter_contract)?
+                        .to_string(),
+                    funds: vec![],
+                    msg: to_binary(&BbroMintMsg::Burn {
+                        owner: info.sender.to_string(),
+                        amount: bbro_burn_amount,
+                    })?,
+                }));
+            }
+
+            attributes.push(Attribute::new(
+                "bbro_burn_amount",
+                bbro_burn_amount.to_string(),
+            ));
+        }
+        EarlyUnlockPenaltyType::BroFee {} => {
+            let bro_fee_amount = amount * penalty_rate;
+
+            if !bro_fee_amount.is_zero() {
+                // fee is taken from unlocked amount and leaves the staking balance
+                staker_info.unlocked_stake_amount = staker_info
+                    .unlocked_stake_amount
+                    .checked_sub(bro_fee_amount)?;
+                state.total_stake_amount = state.total_stake_amount.checked_sub(bro_fee_amount)?;
+
+                msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: deps.api.addr_humanize(&config.bro_token)?.to_string(),
+                    funds: vec![],
+                    msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: deps
+                            .api
+                            .addr_humanize(&config.rewards_pool_contract)?
+                            .to_string(),
+                        amount: bro_fee_amount,
+                    })?,
+                }));
+            }
+
+            attributes.push(Attribute::new("bro_fee_amount", bro_fee_amount.to_string()));
+        }
+    }
+
+    checkpoint_boost(
+        deps.as_ref(),
+        &config,
+        &state,
+        &sender_addr_raw,
+        &mut staker_info,
+    )?;
+
+    if staker_info.can_be_removed()? {
+        remove_staker_info(deps.storage, &sender_addr_raw, env.block.height)?;
+    } else {
+        store_staker_info(
+            deps.storage,
+            &sender_addr_raw,
+            &staker_info,
+            env.block.height,
+            epoch_info.epoch,
+        )?;
+    }
+
+    store_state(deps.storage, &state)?;
+    record_staker_action(
+        deps.storage,
+        &config,
+        &sender_addr_raw,
+        env.block.height,
+        "early_unlock",
+        amount,
+        None,
+    )?;
 
     Ok(Response::new()
-        .add_messages(vec![CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: deps
-                .api
-                .addr_humanize(&config.bbro_minter_contract)?
-                .to_string(),
-            funds: vec![],
-            msg: to_binary(&BbroMintMsg::Mint {
-                recipient: info.sender.to_string(),
-                amount: bbro_reward,
-            })?,
-        })])
-        .add_attributes(vec![
-            ("action", "claim_bbro_rewards"),
-            ("staker", &info.sender.to_string()),
-            ("bbro_reward", &bbro_reward.to_string()),
-        ]))
+        .add_messages(msgs)
+        .add_attributes(attributes))
 }
 
 /// ## Description
-/// Updates contract settings.
+/// Unstake staked amount of tokens. With delayed unstake tokens will be claimable only after passing the unstaking period.
+/// With instant unstake tokens are sent immediately and instant unstake fee is charged.
 /// Returns [`Response`] with specified attributes and messages if operation was successful,
 /// otherwise returns [`ContractError`]
 /// ## Params
 /// * **deps** is an object of type [`DepsMut`]
 ///
-/// * **paused** is an [`Option`] of type [`bool`]
-///
-/// * **unstake_period_blocks** is an [`Option`] of type [`u64`]
-///
-/// * **min_staking_amount** is an [`Option`] of type [`Uint128`]
-///
-/// * **min_lockup_period_epochs** is an [`Option`] of type [`u64`]
-///
-/// * **max_lockup_period_epochs** is an [`Option`] of type [`u64`]
-///
-/// * **base_rate** is an [`Option`] of type [`Decimal`]
+/// * **env** is an object of type [`Env`]
 ///
-/// * **linear_growth** is an [`Option`] of type [`Decimal`]
+/// * **info** is an object of type [`MessageInfo`]
 ///
-/// * **exponential_growth** is an [`Option`] of type [`Decimal`]
+/// * **amount** is an object of type [`Uint128`]
 ///
-/// * **community_bonding_contract** is an [`Option`] of type [`String`]
-#[allow(clippy::too_many_arguments)]
-pub fn update_config(
+/// * **unstake_type** is an object of type [`UnstakeType`]
+pub fn unstake(
     deps: DepsMut,
-    paused: Option<bool>,
-    unstake_period_blocks: Option<u64>,
-    min_staking_amount: Option<Uint128>,
-    min_lockup_period_epochs: Option<u64>,
-    max_lockup_period_epochs: Option<u64>,
-    base_rate: Option<Decimal>,
-    linear_growth: Option<Decimal>,
-    exponential_growth: Option<Decimal>,
-    community_bonding_contract: Option<String>,
-) -> Result<Response, ContractError> {
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+    unstake_type: UnstakeType,
+) -> Result<Response, ContractError> {
+    let config = load_config(deps.storage)?;
+    let mut state = sync_state(deps.storage, env.block.height)?;
+
+    let instant_unstake = match (&unstake_type, &config.instant_unstake) {
+        (UnstakeType::Delayed {}, _) => None,
+        (UnstakeType::Instant {}, Some(instant_unstake)) => Some(instant_unstake.clone()),
+        (UnstakeType::Instant {}, None) => {
+            return Err(ContractError::InstantUnstakeIsNotEnabled {})
+        }
+    };
+
+    let sender_addr_raw = deps.api.addr_canonicalize(&info.sender.to_string())?;
+    let mut staker_info = read_staker_info(deps.storage, &sender_addr_raw, env.block.height)?;
+
+    let epoch_info = query_epoch_info(
+        &deps.querier,
+        deps.api.addr_humanize(&config.epoch_manager_contract)?,
+    )?;
+
+    staker_info.compute_normal_bbro_reward(&epoch_info, env.block.height)?;
+    staker_info.compute_bro_reward(&state)?;
+    if staker_info.auto_compound {
+        staker_info.compound_bro_reward(&mut state)?;
+    }
+
+    staker_info.unlock_expired_lockups(&env.block, &epoch_info)?;
+    if staker_info.unlocked_stake_amount < amount {
+        return Err(ContractError::ForbiddenToUnstakeMoreThanUnlocked {});
+    }
+
+    // decrease stake amount
+    state.total_stake_amount = state.total_stake_amount.checked_sub(amount)?;
+    staker_info.unlocked_stake_amount = staker_info.unlocked_stake_amount.checked_sub(amount)?;
+
+    checkpoint_boost(
+        deps.as_ref(),
+        &config,
+        &state,
+        &sender_addr_raw,
+        &mut staker_info,
+    )?;
+
+    if staker_info.can_be_removed()? {
+        remove_staker_info(deps.storage, &sender_addr_raw, env.block.height)?;
+    } else {
+        store_staker_info(
+            deps.storage,
+            &sender_addr_raw,
+            &staker_info,
+            env.block.height,
+            epoch_info.epoch,
+        )?;
+    }
+
+    let mut msgs: Vec<CosmosMsg> = vec![];
+    let (withdrawal_amount, fee_amount) = match instant_unstake {
+        None => {
+            let mut staker_withdrawals = load_withdrawals(deps.storage, &sender_addr_raw)?;
+            staker_withdrawals.push(WithdrawalInfo {
+                amount,
+                claimable_at: Expiration::AtHeight(env.block.height + config.unstake_period_blocks),
+            });
+            store_withdrawals(deps.storage, &sender_addr_raw, &staker_withdrawals)?;
+
+            (amount, Uint128::zero())
+        }
+        Some(instant_unstake) => {
+            let fee_amount = amount * instant_unstake.fee_rate;
+
+            if !fee_amount.is_zero() {
+                // fee is streamed to remaining stakers like regular bro reward,
+                // if there are no remaining stakers fee is sent to the rewards pool
+                let distribute_to_stakers = match instant_unstake.fee_destination {
+                    InstantUnstakeFeeDestination::RewardsPool {} => false,
+                    InstantUnstakeFeeDestination::Stakers {} => {
+                        !load_total_working_balance(deps.storage)?.is_zero()
+                    }
+                };
+
+                if distribute_to_stakers {
+                    state.add_reward_stream(
+                        fee_amount,
+                        env.block.height,
+                        std::cmp::max(epoch_info.epoch, 1),
+                    );
+                } else {
+                    msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                        contract_addr: deps.api.addr_humanize(&config.bro_token)?.to_string(),
+                        funds: vec![],
+                        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                            recipient: deps
+                                .api
+                                .addr_humanize(&config.rewards_pool_contract)?
+                                .to_string(),
+                            amount: fee_amount,
+                        })?,
+                    }));
+                }
+            }
+
+            // instantly unstaked amount is sent right away without creating withdrawal
+            let withdrawal_amount = amount.checked_sub(fee_amount)?;
+            if !withdrawal_amount.is_zero() {
+                let recipient = deps
+                    .api
+                    .addr_humanize(&read_reward_recipient(deps.storage, &sender_addr_raw)?)?;
+                msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: deps.api.addr_humanize(&config.bro_token)?.to_string(),
+                    funds: vec![],
+                    msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: recipient.to_string(),
+                        amount: withdrawal_amount,
+                    })?,
+                }));
+            }
+
+            (withdrawal_amount, fee_amount)
+        }
+    };
+
+    store_state(deps.storage, &state)?;
+
+    let (action, unstake_type_attr) = match unstake_type {
+        UnstakeType::Delayed {} => ("unstake", "delayed"),
+        UnstakeType::Instant {} => ("instant_unstake", "instant"),
+    };
+    record_staker_action(
+        deps.storage,
+        &config,
+        &sender_addr_raw,
+        env.block.height,
+        action,
+        amount,
+        None,
+    )?;
+
+    Ok(Response::new().add_messages(msgs).add_attributes(vec![
+        ("action", "unstake"),
+        ("staker", &info.sender.to_string()),
+        ("amount", &amount.to_string()),
+        ("unstake_type", unstake_type_attr),
+        ("fee_amount", &fee_amount.to_string()),
+        ("withdrawal_amount", &withdrawal_amount.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Moves staker's lockups by indices, unlocked stake if specified and pending rewards including reward assets to another address.
+/// Returns [`Response`] with specified attributes and messages if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **info** is an object of type [`MessageInfo`]
+///
+/// * **recipient** is a field of type [`String`]
+///
+/// * **lockup_indices** is a field of type [`Vec<u64>`]
+///
+/// * **include_unlocked** is a field of type [`bool`]
+pub fn transfer_position(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    lockup_indices: Vec<u64>,
+    include_unlocked: bool,
+) -> Result<Response, ContractError> {
+    let config = load_config(deps.storage)?;
+    let mut state = sync_state(deps.storage, env.block.height)?;
+
+    let sender_addr_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let recipient_raw = deps.api.addr_canonicalize(&recipient)?;
+    if recipient_raw == sender_addr_raw {
+        return Err(ContractError::InvalidTransferRecipient {});
+    }
+
+    let mut sender_info = read_staker_info(deps.storage, &sender_addr_raw, env.block.height)?;
+    let mut recipient_info = read_staker_info(deps.storage, &recipient_raw, env.block.height)?;
+
+    let epoch_info = query_epoch_info(
+        &deps.querier,
+        deps.api.addr_humanize(&config.epoch_manager_contract)?,
+    )?;
+
+    // settle rewards of both stakers before balances change
+    for staker_info in [&mut sender_info, &mut recipient_info] {
+        staker_info.compute_normal_bbro_reward(&epoch_info, env.block.height)?;
+        staker_info.compute_bro_reward(&state)?;
+        if staker_info.auto_compound {
+            staker_info.compound_bro_reward(&mut state)?;
+        }
+
+        staker_info.unlock_expired_lockups(&env.block, &epoch_info)?;
+    }
+
+    let mut lockup_indices: Vec<usize> = lockup_indices.into_iter().map(|i| i as usize).collect();
+    lockup_indices.sort_unstable();
+    lockup_indices.dedup();
+
+    if lockup_indices
+        .iter()
+        .any(|index| *index >= sender_info.lockups.len())
+    {
+        return Err(ContractError::LockupNotFound {});
+    }
+
+    let lockups = sender_info.take_lockups(&lockup_indices)?;
+    let locked_amount = lockups
+        .iter()
+        .try_fold(Uint128::zero(), |acc, l| acc.checked_add(l.amount))?;
+    recipient_info.append_lockups(lockups)?;
+
+    let unlocked_amount = if include_unlocked {
+        sender_info.unlocked_stake_amount
+    } else {
+        Uint128::zero()
+    };
+    sender_info.unlocked_stake_amount = sender_info
+        .unlocked_stake_amount
+        .checked_sub(unlocked_amount)?;
+    recipient_info.unlocked_stake_amount = recipient_info
+        .unlocked_stake_amount
+        .checked_add(unlocked_amount)?;
+
+    let bro_reward = sender_info.pending_bro_reward;
+    let bbro_reward = sender_info.pending_bbro_reward;
+    sender_info.pending_bro_reward = Uint128::zero();
+    sender_info.pending_bbro_reward = Uint128::zero();
+    recipient_info.pending_bro_reward =
+        recipient_info.pending_bro_reward.checked_add(bro_reward)?;
+    recipient_info.pending_bbro_reward = recipient_info
+        .pending_bbro_reward
+        .checked_add(bbro_reward)?;
+
+    // multipliers of both stakers depend on their new stake amounts
+    checkpoint_boost(
+        deps.as_ref(),
+        &config,
+        &state,
+        &sender_addr_raw,
+        &mut sender_info,
+    )?;
+    checkpoint_boost(
+        deps.as_ref(),
+        &config,
+        &state,
+        &recipient_raw,
+        &mut recipient_info,
+    )?;
+
+    if sender_info.can_be_removed()? {
+        remove_staker_info(deps.storage, &sender_addr_raw, env.block.height)?;
+    } else {
+        store_staker_info(
+            deps.storage,
+            &sender_addr_raw,
+            &sender_info,
+            env.block.height,
+            epoch_info.epoch,
+        )?;
+    }
+
+    store_staker_info(
+        deps.storage,
+        &recipient_raw,
+        &recipient_info,
+        env.block.height,
+        epoch_info.epoch,
+    )?;
+    store_state(deps.storage, &state)?;
+
+    // reward assets of both stakers are settled on staker info update
+    let asset_rewards = take_asset_rewards(deps.storage, &sender_addr_raw)?;
+    add_asset_rewards(deps.storage, &recipient_raw, &asset_rewards)?;
+
+    if locked_amount.is_zero()
+        && unlocked_amount.is_zero()
+        && bro_reward.is_zero()
+        && bbro_reward.is_zero()
+        && asset_rewards.is_empty()
+    {
+        return Err(ContractError::NothingToTransfer {});
+    }
+
+    let transferred_amount = locked_amount.checked_add(unlocked_amount)?;
+    if !transferred_amount.is_zero() {
+        record_staker_action(
+            deps.storage,
+            &config,
+            &sender_addr_raw,
+            env.block.height,
+            "transfer_position",
+            transferred_amount,
+            None,
+        )?;
+        record_staker_action(
+            deps.storage,
+            &config,
+            &recipient_raw,
+            env.block.height,
+            "receive_position",
+            transferred_amount,
+            None,
+        )?;
+    }
+
+    let mut attributes: Vec<Attribute> = vec![
+        Attribute::new("action", "transfer_position"),
+        Attribute::new("staker", info.sender.as_str()),
+        Attribute::new("recipient", &recipient),
+        Attribute::new("locked_amount", locked_amount.to_string()),
+        Attribute::new("unlocked_amount", unlocked_amount.to_string()),
+        Attribute::new("bro_reward", bro_reward.to_string()),
+        Attribute::new("bbro_reward", bbro_reward.to_string()),
+    ];
+    for (info, amount) in asset_rewards {
+        attributes.push(Attribute::new(
+            "asset_reward",
+            Asset { info, amount }.to_string(),
+        ));
+    }
+
+    Ok(Response::new().add_attributes(attributes))
+}
+
+/// ## Description
+/// Cancels pending withdrawal by specified index and stakes its amount back as unlocked stake.
+/// Returns [`Response`] with specified attributes and messages if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **info** is an object of type [`MessageInfo`]
+///
+/// * **index** is a field of type [`u64`]
+pub fn cancel_unstake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    index: u64,
+) -> Result<Response, ContractError> {
+    let sender_addr_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+
+    let mut staker_withdrawals = load_withdrawals(deps.storage, &sender_addr_raw)?;
+    if index as usize >= staker_withdrawals.len() {
+        return Err(ContractError::WithdrawalNotFound {});
+    }
+
+    let withdrawal = staker_withdrawals.remove(index as usize);
+    store_withdrawals(deps.storage, &sender_addr_raw, &staker_withdrawals)?;
+
+    restake(
+        deps,
+        env,
+        &sender_addr_raw,
+        withdrawal.amount,
+        "cancel_unstake",
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "cancel_unstake"),
+        ("staker", info.sender.as_str()),
+        ("amount", &withdrawal.amount.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Stakes back the amount of all staker's withdrawals as unlocked stake.
+/// Returns [`Response`] with specified attributes and messages if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **info** is an object of type [`MessageInfo`]
+pub fn restake_withdrawals(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let sender_addr_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+
+    let amount = load_withdrawals(deps.storage, &sender_addr_raw)?
+        .iter()
+        .try_fold(Uint128::zero(), |acc, w| acc.checked_add(w.amount))?;
+
+    if amount.is_zero() {
+        return Err(ContractError::NothingToRestake {});
+    }
+
+    store_withdrawals(deps.storage, &sender_addr_raw, &vec![])?;
+
+    restake(deps, env, &sender_addr_raw, amount, "restake_withdrawals")?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "restake_withdrawals"),
+        ("staker", info.sender.as_str()),
+        ("amount", &amount.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Settles staker rewards and adds specified amount to staker's unlocked stake and total stake amount.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **staker_raw** is an object of type [`CanonicalAddr`]
+///
+/// * **amount** is an object of type [`Uint128`]
+///
+/// * **action** is a field of type [`str`]. Action recorded in staker history
+fn restake(
+    deps: DepsMut,
+    env: Env,
+    staker_raw: &CanonicalAddr,
+    amount: Uint128,
+    action: &str,
+) -> Result<(), ContractError> {
+    let config = load_config(deps.storage)?;
+    let mut state = sync_state(deps.storage, env.block.height)?;
+    let mut staker_info = read_staker_info(deps.storage, staker_raw, env.block.height)?;
+
+    let epoch_info = query_epoch_info(
+        &deps.querier,
+        deps.api.addr_humanize(&config.epoch_manager_contract)?,
+    )?;
+
+    staker_info.compute_normal_bbro_reward(&epoch_info, env.block.height)?;
+    staker_info.compute_bro_reward(&state)?;
+    if staker_info.auto_compound {
+        staker_info.compound_bro_reward(&mut state)?;
+    }
+
+    staker_info.unlocked_stake_amount = staker_info.unlocked_stake_amount.checked_add(amount)?;
+    staker_info.unlock_expired_lockups(&env.block, &epoch_info)?;
+
+    // increase total stake amount
+    state.total_stake_amount = state.total_stake_amount.checked_add(amount)?;
+
+    checkpoint_boost(deps.as_ref(), &config, &state, staker_raw, &mut staker_info)?;
+    store_staker_info(
+        deps.storage,
+        staker_raw,
+        &staker_info,
+        env.block.height,
+        epoch_info.epoch,
+    )?;
+    store_state(deps.storage, &state)?;
+    record_staker_action(
+        deps.storage,
+        &config,
+        staker_raw,
+        env.block.height,
+        action,
+        amount,
+        None,
+    )?;
+
+    Ok(())
+}
+
+/// ## Description
+/// Withdraw the amount of tokens that have already passed the unstaking period.
+/// Returns [`Response`] with specified attributes and messages if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **info** is an object of type [`MessageInfo`]
+pub fn withdraw(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = load_config(deps.storage)?;
+    let sender_addr_raw = deps.api.addr_canonicalize(&info.sender.to_string())?;
+
+    let mut amount = Uint128::zero();
+    // if withdrawal passed unstaking period add claimable amount and remove it from withdrawals list
+    let staker_withdrawals: Vec<WithdrawalInfo> = load_withdrawals(deps.storage, &sender_addr_raw)?
+        .into_iter()
+        .filter(|c| {
+            if c.claimable_at.is_expired(&env.block) {
+                amount += c.amount;
+                false
+            } else {
+                true
+            }
+        })
+        .collect();
+
+    if amount.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+
+    store_withdrawals(deps.storage, &sender_addr_raw, &staker_withdrawals)?;
+
+    let recipient = deps
+        .api
+        .addr_humanize(&read_reward_recipient(deps.storage, &sender_addr_raw)?)?;
+
+    Ok(Response::new()
+        .add_messages(vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: deps.api.addr_humanize(&config.bro_token)?.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+        })])
+        .add_attributes(vec![
+            ("action", "withdraw"),
+            ("staker", info.sender.as_str()),
+            ("amount", &amount.to_string()),
+            ("recipient", recipient.as_str()),
+        ]))
+}
+
+/// ## Description
+/// Claim available bro reward amount.
+/// Returns [`Response`] with specified attributes and messages if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **info** is an object of type [`MessageInfo`]
+pub fn claim_bro_rewards(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = load_config(deps.storage)?;
+
+    let sender_addr_raw = deps.api.addr_canonicalize(&info.sender.to_string())?;
+    let recipient = deps
+        .api
+        .addr_humanize(&read_reward_recipient(deps.storage, &sender_addr_raw)?)?;
+
+    let (msgs, amount, _) = claim_rewards(
+        deps,
+        &env,
+        &config,
+        &sender_addr_raw,
+        &recipient,
+        false,
+        Uint128::zero(),
+    )?;
+    if amount.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+
+    Ok(Response::new().add_messages(msgs).add_attributes(vec![
+        ("action", "claim_bro_rewards"),
+        ("staker", info.sender.as_str()),
+        ("amount", &amount.to_string()),
+        ("recipient", recipient.as_str()),
+    ]))
+}
+
+/// ## Description
+/// Settles staker rewards, resets claimed pending amounts and stores updated staker info.
+/// Returns messages which send claimed bro reward along with specified withdraw amount
+/// and, if `claim_bbro` is set, claimed bbro reward to recipient.
+/// Also returns claimed bro and bbro reward amounts
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **config** is an object of type [`Config`]
+///
+/// * **staker_raw** is an object of type [`CanonicalAddr`]
+///
+/// * **recipient** is an object of type [`Addr`]
+///
+/// * **claim_bbro** is a field of type [`bool`]
+///
+/// * **withdraw_amount** is an object of type [`Uint128`]
+fn claim_rewards(
+    deps: DepsMut,
+    env: &Env,
+    config: &Config,
+    staker_raw: &CanonicalAddr,
+    recipient: &Addr,
+    claim_bbro: bool,
+    withdraw_amount: Uint128,
+) -> Result<(Vec<CosmosMsg>, Uint128, Uint128), ContractError> {
+    let state = sync_state(deps.storage, env.block.height)?;
+    let mut staker_info = read_staker_info(deps.storage, staker_raw, env.block.height)?;
+
+    let epoch_info = query_epoch_info(
+        &deps.querier,
+        deps.api.addr_humanize(&config.epoch_manager_contract)?,
+    )?;
+
+    if claim_bbro {
+        staker_info.compute_normal_bbro_reward(&epoch_info, env.block.height)?;
+    }
+    staker_info.compute_bro_reward(&state)?;
+
+    let bro_reward = staker_info.pending_bro_reward;
+    let bbro_reward = if claim_bbro {
+        staker_info.pending_bbro_reward
+    } else {
+        Uint128::zero()
+    };
+
+    staker_info.pending_bro_reward = Uint128::zero();
+    staker_info.pending_bbro_reward = staker_info.pending_bbro_reward.checked_sub(bbro_reward)?;
+    staker_info.unlock_expired_lockups(&env.block, &epoch_info)?;
+    checkpoint_boost(deps.as_ref(), config, &state, staker_raw, &mut staker_info)?;
+
+    if staker_info.can_be_removed()? {
+        remove_staker_info(deps.storage, staker_raw, env.block.height)?;
+    } else {
+        store_staker_info(
+            deps.storage,
+            staker_raw,
+            &staker_info,
+            env.block.height,
+            epoch_info.epoch,
+        )?;
+    }
+
+    let mut msgs: Vec<CosmosMsg> = vec![];
+    let bro_amount = bro_reward.checked_add(withdraw_amount)?;
+    if !bro_amount.is_zero() {
+        msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: deps.api.addr_humanize(&config.bro_token)?.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount: bro_amount,
+            })?,
+        }));
+    }
+
+    if !bbro_reward.is_zero() {
+        msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: deps
+                .api
+                .addr_humanize(&config.bbro_minter_contract)?
+                .to_string(),
+            funds: vec![],
+            msg: to_binary(&BbroMintMsg::Mint {
+                recipient: recipient.to_string(),
+                amount: bbro_reward,
+            })?,
+        }));
+    }
+
+    Ok((msgs, bro_reward, bbro_reward))
+}
+
+/// ## Description
+/// Claim available bbro reward amount.
+/// Returns [`Response`] with specified attributes and messages if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **info** is an object of type [`MessageInfo`]
+pub fn claim_bbro_rewards(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = load_config(deps.storage)?;
+
+    let sender_addr_raw = deps.api.addr_canonicalize(&info.sender.to_string())?;
+    let mut staker_info = read_staker_info(deps.storage, &sender_addr_raw, env.block.height)?;
+
+    let epoch_info = query_epoch_info(
+        &deps.querier,
+        deps.api.addr_humanize(&config.epoch_manager_contract)?,
+    )?;
+
+    staker_info.compute_normal_bbro_reward(&epoch_info, env.block.height)?;
+
+    let bbro_reward = staker_info.pending_bbro_reward;
+    if bbro_reward.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+
+    staker_info.pending_bbro_reward = Uint128::zero();
+    staker_info.unlock_expired_lockups(&env.block, &epoch_info)?;
+    store_staker_info(
+        deps.storage,
+        &sender_addr_raw,
+        &staker_info,
+        env.block.height,
+        epoch_info.epoch,
+    )?;
+
+    let recipient = deps
+        .api
+        .addr_humanize(&read_reward_recipient(deps.storage, &sender_addr_raw)?)?;
+
+    Ok(Response::new()
+        .add_messages(vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: deps
+                .api
+                .addr_humanize(&config.bbro_minter_contract)?
+                .to_string(),
+            funds: vec![],
+            msg: to_binary(&BbroMintMsg::Mint {
+                recipient: recipient.to_string(),
+                amount: bbro_reward,
+            })?,
+        })])
+        .add_attributes(vec![
+            ("action", "claim_bbro_rewards"),
+            ("staker", info.sender.as_str()),
+            ("bbro_reward", &bbro_reward.to_string()),
+            ("recipient", recipient.as_str()),
+        ]))
+}
+
+/// ## Description
+/// Claim available bro reward amount and pending amounts of all reward assets.
+/// Returns [`Response`] with specified attributes and messages if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **info** is an object of type [`MessageInfo`]
+pub fn claim_all_rewards(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = load_config(deps.storage)?;
+    let state = sync_state(deps.storage, env.block.height)?;
+
+    let sender_addr_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let mut staker_info = read_staker_info(deps.storage, &sender_addr_raw, env.block.height)?;
+
+    let epoch_info = query_epoch_info(
+        &deps.querier,
+        deps.api.addr_humanize(&config.epoch_manager_contract)?,
+    )?;
+
+    staker_info.compute_bro_reward(&state)?;
+    let bro_amount = staker_info.pending_bro_reward;
+
+    staker_info.pending_bro_reward = Uint128::zero();
+    staker_info.unlock_expired_lockups(&env.block, &epoch_info)?;
+    checkpoint_boost(
+        deps.as_ref(),
+        &config,
+        &state,
+        &sender_addr_raw,
+        &mut staker_info,
+    )?;
+
+    // reward assets are settled on staker info update
+    if staker_info.can_be_removed()? {
+        remove_staker_info(deps.storage, &sender_addr_raw, env.block.height)?;
+    } else {
+        store_staker_info(
+            deps.storage,
+            &sender_addr_raw,
+            &staker_info,
+            env.block.height,
+            epoch_info.epoch,
+        )?;
+    }
+
+    let mut rewards: Vec<Asset> = vec![];
+    if !bro_amount.is_zero() {
+        rewards.push(Asset {
+            info: AssetInfo::Token {
+                contract_addr: deps.api.addr_humanize(&config.bro_token)?,
+            },
+            amount: bro_amount,
+        });
+    }
+
+    for (asset_info, amount) in take_asset_rewards(deps.storage, &sender_addr_raw)? {
+        rewards.push(Asset {
+            info: asset_info,
+            amount,
+        });
+    }
+
+    if rewards.is_empty() {
+        return Err(ContractError::NothingToClaim {});
+    }
+
+    let recipient = deps
+        .api
+        .addr_humanize(&read_reward_recipient(deps.storage, &sender_addr_raw)?)?;
+
+    let mut msgs: Vec<CosmosMsg> = vec![];
+    let mut attributes: Vec<Attribute> = vec![
+        Attribute::new("action", "claim_all_rewards"),
+        Attribute::new("staker", info.sender.as_str()),
+    ];
+    for reward in rewards {
+        attributes.push(Attribute::new("reward", reward.to_string()));
+        msgs.push(reward.into_msg(&deps.querier, recipient.clone())?);
+    }
+    attributes.push(Attribute::new("recipient", recipient.as_str()));
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attributes(attributes))
+}
+
+/// ## Description
+/// Registers new reward asset distributed to stakers.
+/// Returns [`Response`] with specified attributes and messages if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **asset_info** is an object of type [`AssetInfo`]
+pub fn add_reward_asset(deps: DepsMut, asset_info: AssetInfo) -> Result<Response, ContractError> {
+    let config = load_config(deps.storage)?;
+
+    asset_info.check(deps.api)?;
+    if let AssetInfo::Token { contract_addr } = &asset_info {
+        if deps.api.addr_canonicalize(contract_addr.as_str())? == config.bro_token {
+            return Err(ContractError::InvalidRewardAsset {});
+        }
+    }
+
+    if read_reward_asset(deps.storage, &asset_info)?.is_some() {
+        return Err(ContractError::RewardAssetAlreadyExists {});
+    }
+
+    if read_reward_assets(deps.storage)?.len() >= MAX_REWARD_ASSETS {
+        return Err(ContractError::TooManyRewardAssets {});
+    }
+
+    store_reward_asset(
+        deps.storage,
+        &RewardAssetInfo {
+            asset_info: asset_info.clone(),
+            global_reward_index: Decimal::zero(),
+            total_distributed_amount: Uint128::zero(),
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "add_reward_asset"),
+        ("asset_info", &asset_info.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Distributes received amount of registered reward asset to stakers.
+/// Returns [`Response`] with specified attributes and messages if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **sender** is an object of type [`Addr`]
+///
+/// * **asset** is an object of type [`Asset`]
+pub fn fund_reward_asset(
+    deps: DepsMut,
+    sender: Addr,
+    asset: Asset,
+) -> Result<Response, ContractError> {
+    let state = load_state(deps.storage)?;
+
+    let mut reward_asset = read_reward_asset(deps.storage, &asset.info)?
+        .ok_or(ContractError::RewardAssetNotFound {})?;
+
+    if asset.amount.is_zero() {
+        return Err(ContractError::InvalidFundAmount {});
+    }
+
+    if state.total_stake_amount.is_zero() {
+        return Err(ContractError::NothingStaked {});
+    }
+
+    reward_asset.global_reward_index = reward_asset.global_reward_index
+        + Decimal::from_ratio(asset.amount, state.total_stake_amount);
+    reward_asset.total_distributed_amount = reward_asset
+        .total_distributed_amount
+        .checked_add(asset.amount)?;
+    store_reward_asset(deps.storage, &reward_asset)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "fund_reward_asset"),
+        ("sender", sender.as_str()),
+        ("asset", &asset.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Sets the address which receives staker's rewards and withdrawals.
+/// If recipient is not specified resets it to staker address.
+/// Returns [`Response`] with specified attributes and messages if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **info** is an object of type [`MessageInfo`]
+///
+/// * **recipient** is an [`Option`] field of type [`String`]
+pub fn set_reward_recipient(
+    deps: DepsMut,
+    info: MessageInfo,
+    recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    let sender_addr_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+
+    let recipient = recipient.unwrap_or_else(|| info.sender.to_string());
+    let recipient_raw = deps.api.addr_canonicalize(&recipient)?;
+
+    if recipient_raw == sender_addr_raw {
+        store_reward_recipient(deps.storage, &sender_addr_raw, None)?;
+    } else {
+        store_reward_recipient(deps.storage, &sender_addr_raw, Some(&recipient_raw))?;
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "set_reward_recipient"),
+        ("staker", info.sender.as_str()),
+        ("recipient", &recipient),
+    ]))
+}
+
+/// ## Description
+/// Allows operator to claim rewards and withdrawals on behalf of staker.
+/// Returns [`Response`] with specified attributes and messages if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **info** is an object of type [`MessageInfo`]
+///
+/// * **operator** is a field of type [`String`]
+///
+/// * **expires** is an [`Option`] field of type [`Expiration`]
+pub fn approve_claim_operator(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    operator: String,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let sender_addr_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let operator_raw = deps.api.addr_canonicalize(&operator)?;
+
+    if operator_raw == sender_addr_raw {
+        return Err(ContractError::CannotSetOwnAccount {});
+    }
+
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::InvalidExpiration {});
+    }
+
+    store_claim_operator(deps.storage, &sender_addr_raw, &operator_raw, &expires)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "approve_claim_operator"),
+        ("staker", info.sender.as_str()),
+        ("operator", &operator),
+        ("expires", &expires.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Revokes operator permission to claim on behalf of staker.
+/// Returns [`Response`] with specified attributes and messages if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **info** is an object of type [`MessageInfo`]
+///
+/// * **operator** is a field of type [`String`]
+pub fn revoke_claim_operator(
+    deps: DepsMut,
+    info: MessageInfo,
+    operator: String,
+) -> Result<Response, ContractError> {
+    let sender_addr_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let operator_raw = deps.api.addr_canonicalize(&operator)?;
+
+    remove_claim_operator(deps.storage, &sender_addr_raw, &operator_raw);
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "revoke_claim_operator"),
+        ("staker", info.sender.as_str()),
+        ("operator", &operator),
+    ]))
+}
+
+/// ## Description
+/// Claims available bro and bbro rewards and withdrawals that have already passed
+/// the unstaking period on behalf of staker. Funds are sent to staker's reward recipient.
+/// Returns [`Response`] with specified attributes and messages if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **info** is an object of type [`MessageInfo`]
+///
+/// * **staker** is a field of type [`String`]
+pub fn claim_for(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    staker: String,
+) -> Result<Response, ContractError> {
+    let staker_raw = deps.api.addr_canonicalize(&staker)?;
+    let operator_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+
+    match read_claim_operator(deps.storage, &staker_raw, &operator_raw)? {
+        Some(expires) if !expires.is_expired(&env.block) => (),
+        _ => return Err(ContractError::Unauthorized {}),
+    }
+
+    let config = load_config(deps.storage)?;
+
+    let mut withdraw_amount = Uint128::zero();
+    let staker_withdrawals: Vec<WithdrawalInfo> = load_withdrawals(deps.storage, &staker_raw)?
+        .into_iter()
+        .filter(|c| {
+            if c.claimable_at.is_expired(&env.block) {
+                withdraw_amount += c.amount;
+                false
+            } else {
+                true
+            }
+        })
+        .collect();
+
+    store_withdrawals(deps.storage, &staker_raw, &staker_withdrawals)?;
+
+    let recipient = deps
+        .api
+        .addr_humanize(&read_reward_recipient(deps.storage, &staker_raw)?)?;
+
+    let (msgs, bro_reward, bbro_reward) = claim_rewards(
+        deps,
+        &env,
+        &config,
+        &staker_raw,
+        &recipient,
+        true,
+        withdraw_amount,
+    )?;
+    if bro_reward.is_zero() && bbro_reward.is_zero() && withdraw_amount.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+
+    Ok(Response::new().add_messages(msgs).add_attributes(vec![
+        ("action", "claim_for"),
+        ("staker", &staker),
+        ("recipient", recipient.as_str()),
+        ("operator", info.sender.as_str()),
+        ("bro_reward", &bro_reward.to_string()),
+        ("bbro_reward", &bbro_reward.to_string()),
+        ("withdraw_amount", &withdraw_amount.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Restakes available bro reward amount as unlocked stake or,
+/// if `epochs_locked` is set, as a new lockup.
+/// Returns [`Response`] with specified attributes and messages if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **info** is an object of type [`MessageInfo`]
+///
+/// * **epochs_locked** is an [`Option`] of type [`u64`]
+pub fn compound_bro_rewards(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    epochs_locked: Option<u64>,
+) -> Result<Response, ContractError> {
+    let config = load_config(deps.storage)?;
+    let mut state = sync_state(deps.storage, env.block.height)?;
+
+    let sender_addr_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let mut staker_info = read_staker_info(deps.storage, &sender_addr_raw, env.block.height)?;
+
+    let epoch_info = query_epoch_info(
+        &deps.querier,
+        deps.api.addr_humanize(&config.epoch_manager_contract)?,
+    )?;
+
+    // bbro reward must be settled with stake amount before compounding
+    staker_info.compute_normal_bbro_reward(&epoch_info, env.block.height)?;
+    staker_info.compute_bro_reward(&state)?;
+
+    let amount = staker_info.compound_bro_reward(&mut state)?;
+    if amount.is_zero() {
+        return Err(ContractError::NothingToCompound {});
+    }
+
+    let mut msgs: Vec<CosmosMsg> = vec![];
+    let mut attributes: Vec<Attribute> = vec![
+        Attribute::new("action", "compound_bro_rewards"),
+        Attribute::new("staker", info.sender.as_str()),
+        Attribute::new("amount", amount.to_string()),
+    ];
+
+    if let Some(epochs_locked) = epochs_locked {
+        if !config.lockup_config.valid_lockup_period(epochs_locked) {
+            return Err(ContractError::InvalidLockupPeriod {});
+        }
+
+        let bbro_premium_lockup_reward =
+            staker_info.compute_premium_bbro_reward(&config.lockup_config, epochs_locked, amount);
+
+        if bbro_premium_lockup_reward.is_zero() {
+            return Err(ContractError::LockupPremiumRewardIsZero {});
+        }
+
+        staker_info.add_lockup(env.block.height, amount, epochs_locked)?;
+        staker_info.unlocked_stake_amount =
+            staker_info.unlocked_stake_amount.checked_sub(amount)?;
+
+        msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: deps
+                .api
+                .addr_humanize(&config.bbro_minter_contract)?
+                .to_string(),
+            funds: vec![],
+            msg: to_binary(&BbroMintMsg::Mint {
+                recipient: info.sender.to_string(),
+                amount: bbro_premium_lockup_reward,
+            })?,
+        }));
+
+        attributes.push(Attribute::new("epochs_locked", epochs_locked.to_string()));
+        attributes.push(Attribute::new(
+            "bbro_premium_lockup_reward",
+            bbro_premium_lockup_reward.to_string(),
+        ));
+    }
+
+    staker_info.unlock_expired_lockups(&env.block, &epoch_info)?;
+    checkpoint_boost(
+        deps.as_ref(),
+        &config,
+        &state,
+        &sender_addr_raw,
+        &mut staker_info,
+    )?;
+    store_staker_info(
+        deps.storage,
+        &sender_addr_raw,
+        &staker_info,
+        env.block.height,
+        epoch_info.epoch,
+    )?;
+    store_state(deps.storage, &state)?;
+    record_staker_action(
+        deps.storage,
+        &config,
+        &sender_addr_raw,
+        env.block.height,
+        "compound_bro_rewards",
+        amount,
+        epochs_locked,
+    )?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attributes(attributes))
+}
+
+/// ## Description
+/// Recalculates bbro boost multiplier for specified stakers.
+/// Stakers without staked amount are skipped.
+/// Returns [`Response`] with specified attributes and messages if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **stakers** is a [`Vec`] of type [`String`]
+pub fn update_boost(
+    deps: DepsMut,
+    env: Env,
+    stakers: Vec<String>,
+) -> Result<Response, ContractError> {
+    let config = load_config(deps.storage)?;
+    let state = sync_state(deps.storage, env.block.height)?;
+
+    if config.boost_config.is_none() {
+        return Err(ContractError::BoostIsNotEnabled {});
+    }
+
+    let epoch_info = query_epoch_info(
+        &deps.querier,
+        deps.api.addr_humanize(&config.epoch_manager_contract)?,
+    )?;
+
+    let mut updated_stakers: u64 = 0;
+    for staker in stakers {
+        let staker_raw = deps.api.addr_canonicalize(&staker)?;
+        let mut staker_info = read_staker_info(deps.storage, &staker_raw, env.block.height)?;
+
+        if staker_info.total_staked()?.is_zero() {
+            continue;
+        }
+
+        // bro reward must be settled with previous multiplier
+        staker_info.compute_bro_reward(&state)?;
+        checkpoint_boost(
+            deps.as_ref(),
+            &config,
+            &state,
+            &staker_raw,
+            &mut staker_info,
+        )?;
+        store_staker_info(
+            deps.storage,
+            &staker_raw,
+            &staker_info,
+            env.block.height,
+            epoch_info.epoch,
+        )?;
+
+        updated_stakers += 1;
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "update_boost"),
+        ("updated_stakers", &updated_stakers.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Recalculates staker boost multiplier using current bbro balance of staker
+/// if boost is enabled. Bro reward must be computed before calling it
+/// ## Params
+/// * **deps** is an object of type [`Deps`]
+///
+/// * **config** is an object of type [`Config`]
+///
+/// * **state** is an object of type [`State`]
+///
+/// * **staker_raw** is an object of type [`CanonicalAddr`]
+///
+/// * **staker_info** is an object of type [`StakerInfo`]
+fn checkpoint_boost(
+    deps: Deps,
+    config: &Config,
+    state: &State,
+    staker_raw: &CanonicalAddr,
+    staker_info: &mut StakerInfo,
+) -> StdResult<()> {
+    let boost_config = match &config.boost_config {
+        Some(boost_config) => boost_config,
+        None => return Ok(()),
+    };
+
+    let bbro_token = deps.api.addr_humanize(&boost_config.bbro_token)?;
+    let bbro_balance = query_token_balance(
+        &deps.querier,
+        bbro_token.clone(),
+        deps.api.addr_humanize(staker_raw)?,
+    )?;
+    let bbro_supply = query_token_supply(&deps.querier, bbro_token)?;
+
+    staker_info.update_boost_multiplier(
+        boost_config,
+        state.total_stake_amount,
+        bbro_balance,
+        bbro_supply,
+    )
+}
+
+/// ## Description
+/// Enables or disables automatic restaking of bro rewards for staker.
+/// Returns [`Response`] with specified attributes and messages if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **info** is an object of type [`MessageInfo`]
+///
+/// * **enabled** is a field of type [`bool`]
+pub fn set_auto_compound(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    let sender_addr_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let mut staker_info = read_staker_info(deps.storage, &sender_addr_raw, env.block.height)?;
+
+    if staker_info.total_staked()?.is_zero() {
+        return Err(StdError::generic_err("staker not found").into());
+    }
+
+    let config = load_config(deps.storage)?;
+    let epoch_info = query_epoch_info(
+        &deps.querier,
+        deps.api.addr_humanize(&config.epoch_manager_contract)?,
+    )?;
+
+    staker_info.auto_compound = enabled;
+    store_staker_info(
+        deps.storage,
+        &sender_addr_raw,
+        &staker_info,
+        env.block.height,
+        epoch_info.epoch,
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "set_auto_compound"),
+        ("staker", info.sender.as_str()),
+        ("enabled", &enabled.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Restakes available bro reward amount for specified stakers which enabled auto-compounding.
+/// Stakers without auto-compounding enabled are skipped.
+/// Returns [`Response`] with specified attributes and messages if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **stakers** is a [`Vec`] of type [`String`]
+pub fn auto_compound_bro_rewards(
+    deps: DepsMut,
+    env: Env,
+    stakers: Vec<String>,
+) -> Result<Response, ContractError> {
+    let config = load_config(deps.storage)?;
+    let mut state = sync_state(deps.storage, env.block.height)?;
+
+    let epoch_info = query_epoch_info(
+        &deps.querier,
+        deps.api.addr_humanize(&config.epoch_manager_contract)?,
+    )?;
+
+    let mut compounded_amount = Uint128::zero();
+    for staker in stakers {
+        let staker_raw = deps.api.addr_canonicalize(&staker)?;
+        let mut staker_info = read_staker_info(deps.storage, &staker_raw, env.block.height)?;
+
+        if !staker_info.auto_compound {
+            continue;
+        }
+
+        staker_info.compute_normal_bbro_reward(&epoch_info, env.block.height)?;
+        staker_info.compute_bro_reward(&state)?;
+
+        let amount = staker_info.compound_bro_reward(&mut state)?;
+        compounded_amount = compounded_amount.checked_add(amount)?;
+
+        staker_info.unlock_expired_lockups(&env.block, &epoch_info)?;
+        checkpoint_boost(
+            deps.as_ref(),
+            &config,
+            &state,
+            &staker_raw,
+            &mut staker_info,
+        )?;
+        store_staker_info(
+            deps.storage,
+            &staker_raw,
+            &staker_info,
+            env.block.height,
+            epoch_info.epoch,
+        )?;
+
+        if !amount.is_zero() {
+            record_staker_action(
+                deps.storage,
+                &config,
+                &staker_raw,
+                env.block.height,
+                "auto_compound_bro_rewards",
+                amount,
+                None,
+            )?;
+        }
+    }
+
+    store_state(deps.storage, &state)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "auto_compound_bro_rewards"),
+        ("compounded_amount", &compounded_amount.to_string()),
+    ]))
+}
+
+/// ## Description
+/// Withdraws whole staked amount and pending withdrawals immediately, forfeiting pending rewards.
+/// Forfeited bro reward is sent to the rewards pool.
+/// Returns [`Response`] with specified attributes and messages if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **info** is an object of type [`MessageInfo`]
+pub fn emergency_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = load_config(deps.storage)?;
+    if !config.emergency_mode {
+        return Err(ContractError::EmergencyModeIsNotEnabled {});
+    }
+
+    let mut state = sync_state(deps.storage, env.block.height)?;
+
+    let sender_addr_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let mut staker_info = read_staker_info(deps.storage, &sender_addr_raw, env.block.height)?;
+    staker_info.compute_bro_reward(&state)?;
+
+    let staked_amount = staker_info.total_staked()?;
+    let withdrawals_amount = load_withdrawals(deps.storage, &sender_addr_raw)?
+        .iter()
+        .try_fold(Uint128::zero(), |acc, w| acc.checked_add(w.amount))?;
+
+    let amount = staked_amount.checked_add(withdrawals_amount)?;
+    if amount.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+
+    state.total_stake_amount = state.total_stake_amount.checked_sub(staked_amount)?;
+    store_state(deps.storage, &state)?;
+
+    remove_staker_info(deps.storage, &sender_addr_raw, env.block.height)?;
+    store_withdrawals(deps.storage, &sender_addr_raw, &vec![])?;
+    // reward assets are settled on staker info removal
+    let forfeited_asset_rewards = take_asset_rewards(deps.storage, &sender_addr_raw)?;
+    record_staker_action(
+        deps.storage,
+        &config,
+        &sender_addr_raw,
+        env.block.height,
+        "emergency_withdraw",
+        amount,
+        None,
+    )?;
+
+    let bro_token = deps.api.addr_humanize(&config.bro_token)?.to_string();
+    let mut msgs: Vec<CosmosMsg> = vec![CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: bro_token.clone(),
+        funds: vec![],
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: info.sender.to_string(),
+            amount,
+        })?,
+    })];
+
+    // forfeited bro reward is returned to the rewards pool
+    let forfeited_bro_reward = staker_info.pending_bro_reward;
+    if !forfeited_bro_reward.is_zero() {
+        msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: bro_token,
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: deps
+                    .api
+                    .addr_humanize(&config.rewards_pool_contract)?
+                    .to_string(),
+                amount: forfeited_bro_reward,
+            })?,
+        }));
+    }
+
+    let mut attributes: Vec<Attribute> = vec![
+        Attribute::new("action", "emergency_withdraw"),
+        Attribute::new("staker", info.sender.as_str()),
+        Attribute::new("amount", amount.to_string()),
+        Attribute::new(
+            "unlocked_amount",
+            staker_info.unlocked_stake_amount.to_string(),
+        ),
+        Attribute::new("locked_amount", staker_info.locked_stake_amount.to_string()),
+        Attribute::new("withdrawals_amount", withdrawals_amount.to_string()),
+        Attribute::new("forfeited_bro_reward", forfeited_bro_reward.to_string()),
+    ];
+    for (info, amount) in forfeited_asset_rewards {
+        attributes.push(Attribute::new(
+            "forfeited_asset_reward",
+            Asset { info, amount }.to_string(),
+        ));
+    }
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attributes(attributes))
+}
+
+/// ## Description
+/// Enables emergency mode. Emergency mode cannot be disabled.
+/// Returns [`Response`] with specified attributes and messages if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+pub fn enable_emergency_mode(deps: DepsMut) -> Result<Response, ContractError> {
+    let mut config = load_config(deps.storage)?;
+    if config.emergency_mode {
+        return Err(ContractError::EmergencyModeIsEnabled {});
+    }
+
+    config.emergency_mode = true;
+    store_config(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "enable_emergency_mode"),
+        ("emergency_mode", "true"),
+    ]))
+}
+
+/// ## Description
+/// Replaces a list of contracts allowed to lock bond payouts of bonders
+/// in addition to community bonding contract.
+/// Returns [`Response`] with specified attributes and messages if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **bond_lock_contracts** is a [`Vec`] of type [`String`]
+pub fn update_bond_lock_contracts(
+    deps: DepsMut,
+    bond_lock_contracts: Vec<String>,
+) -> Result<Response, ContractError> {
+    let mut config = load_config(deps.storage)?;
+    config.bond_lock_contracts = bond_lock_contracts
+        .iter()
+        .map(|addr| deps.api.addr_canonicalize(addr))
+        .collect::<StdResult<Vec<CanonicalAddr>>>()?;
+    store_config(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "update_bond_lock_contracts"),
+        ("bond_lock_contracts", &bond_lock_contracts.join(",")),
+    ]))
+}
+
+/// ## Description
+/// Updates contract settings.
+/// Returns [`Response`] with specified attributes and messages if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **paused** is an [`Option`] of type [`bool`]
+///
+/// * **unstake_period_blocks** is an [`Option`] of type [`u64`]
+///
+/// * **min_staking_amount** is an [`Option`] of type [`Uint128`]
+///
+/// * **min_lockup_period_epochs** is an [`Option`] of type [`u64`]
+///
+/// * **max_lockup_period_epochs** is an [`Option`] of type [`u64`]
+///
+/// * **base_rate** is an [`Option`] of type [`Decimal`]
+///
+/// * **linear_growth** is an [`Option`] of type [`Decimal`]
+///
+/// * **exponential_growth** is an [`Option`] of type [`Decimal`]
+///
+/// * **community_bonding_contract** is an [`Option`] of type [`String`]
+///
+/// * **early_unlock_penalty** is an [`Option`] of type [`EarlyUnlockPenalty`]
+///
+/// * **boost** is an [`Option`] of type [`BoostParams`]
+///
+/// * **instant_unstake** is an [`Option`] of type [`InstantUnstake`]
+///
+/// * **staker_history_limit** is an [`Option`] of type [`u64`]
+#[allow(clippy::too_many_arguments)]
+pub fn update_config(
+    deps: DepsMut,
+    env: Env,
+    paused: Option<bool>,
+    unstake_period_blocks: Option<u64>,
+    min_staking_amount: Option<Uint128>,
+    min_lockup_period_epochs: Option<u64>,
+    max_lockup_period_epochs: Option<u64>,
+    base_rate: Option<Decimal>,
+    linear_growth: Option<Decimal>,
+    exponential_growth: Option<Decimal>,
+    community_bonding_contract: Option<String>,
+    early_unlock_penalty: Option<EarlyUnlockPenalty>,
+    boost: Option<BoostParams>,
+    instant_unstake: Option<InstantUnstake>,
+    staker_history_limit: Option<u64>,
+) -> Result<Response, ContractError> {
     let mut config = load_config(deps.storage)?;
 
     let mut attributes: Vec<Attribute> = vec![Attribute::new("action", "update_config")];
@@ -644,12 +2523,104 @@ pub fn update_config(
         ));
     }
 
+    if let Some(early_unlock_penalty) = early_unlock_penalty {
+        attributes.push(Attribute::new(
+            "early_unlock_penalty_changed",
+            format!(
+                "min_rate: {}, max_rate: {}",
+                early_unlock_penalty.min_rate, early_unlock_penalty.max_rate
+            ),
+        ));
+        config.lockup_config.early_unlock_penalty = Some(early_unlock_penalty);
+    }
+
+    if let Some(boost) = boost {
+        attributes.push(Attribute::new(
+            "boost_changed",
+            format!(
+                "bbro_token: {}, max_boost: {}",
+                boost.bbro_token, boost.max_boost
+            ),
+        ));
+        config.boost_config = Some(BoostConfig {
+            bbro_token: deps.api.addr_canonicalize(&boost.bbro_token)?,
+            max_boost: boost.max_boost,
+        });
+    }
+
+    if let Some(instant_unstake) = instant_unstake {
+        attributes.push(Attribute::new(
+            "instant_unstake_changed",
+            format!("fee_rate: {}", instant_unstake.fee_rate),
+        ));
+        config.instant_unstake = Some(instant_unstake);
+    }
+
+    if let Some(staker_history_limit) = staker_history_limit {
+        config.staker_history_limit = staker_history_limit;
+        attributes.push(Attribute::new(
+            "staker_history_limit_changed",
+            staker_history_limit.to_string(),
+        ));
+    }
+
     config.validate()?;
     store_config(deps.storage, &config)?;
 
+    // voting power at past blocks keeps previous voting weight
+    if max_lockup_period_epochs.is_some() {
+        let epoch_info = query_epoch_info(
+            &deps.querier,
+            deps.api.addr_humanize(&config.epoch_manager_contract)?,
+        )?;
+        update_voting_weight_blocks(
+            deps.storage,
+            config.lockup_config.voting_weight_blocks(epoch_info.epoch),
+            env.block.height,
+        )?;
+    }
+
     Ok(Response::new().add_attributes(attributes))
 }
 
+/// ## Description
+/// Records balance-changing action in staker history
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **config** is an object of type [`Config`]
+///
+/// * **staker** is an object of type [`CanonicalAddr`]
+///
+/// * **block** is a field of type [`u64`]
+///
+/// * **action** is a field of type [`str`]
+///
+/// * **amount** is an object of type [`Uint128`]
+///
+/// * **epochs_locked** is an [`Option`] of type [`u64`]
+fn record_staker_action(
+    storage: &mut dyn Storage,
+    config: &Config,
+    staker: &CanonicalAddr,
+    block: u64,
+    action: &str,
+    amount: Uint128,
+    epochs_locked: Option<u64>,
+) -> StdResult<()> {
+    store_staker_history_entry(
+        storage,
+        staker,
+        &StakerHistoryEntry {
+            block,
+            action: action.to_string(),
+            amount,
+            epochs_locked,
+        },
+        config.staker_history_limit,
+    )
+}
+
 pub fn update_staker_lockups(
     deps: DepsMut,
     env: Env,
@@ -670,9 +2641,88 @@ pub fn update_staker_lockups(
             deps.api.addr_humanize(&config.epoch_manager_contract)?,
         )?;
 
-        staker_info.unlock_expired_lockups(&env.block, &epoch_info, config.prev_epoch_blocks)?;
-        store_staker_info(deps.storage, &staker_raw, &staker_info)?;
+        staker_info.unlock_expired_lockups(&env.block, &epoch_info)?;
+        store_staker_info(
+            deps.storage,
+            &staker_raw,
+            &staker_info,
+            env.block.height,
+            epoch_info.epoch,
+        )?;
     }
 
     Ok(Response::new().add_attributes(vec![("action", "update_staker_lockups")]))
 }
+
+/// ## Description
+/// Converts deprecated lockups of the next batch of stakers to epoch lockups
+/// and stores their info using current lockups schema.
+/// Stakers are processed in ascending order starting after the last migrated staker.
+/// Migration is completed once there are no stakers left to process.
+/// Returns [`Response`] with specified attributes if operation was successful,
+/// otherwise returns [`ContractError`]
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **limit** is an [`Option`] field of type [`u32`]
+pub fn migrate_lockups_batch(
+    deps: DepsMut,
+    env: Env,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let mut progress = load_lockups_migration_progress(deps.storage)?;
+    if progress.completed {
+        return Err(ContractError::LockupsMigrationIsCompleted {});
+    }
+
+    let config = load_config(deps.storage)?;
+    let epoch_info = query_epoch_info(
+        &deps.querier,
+        deps.api.addr_humanize(&config.epoch_manager_contract)?,
+    )?;
+
+    let limit = limit
+        .unwrap_or(DEFAULT_LOCKUPS_MIGRATION_LIMIT)
+        .min(MAX_LOCKUPS_MIGRATION_LIMIT) as usize;
+    let start = progress
+        .last_migrated_staker
+        .as_ref()
+        .map(|staker| Bound::exclusive(staker.as_slice()));
+    let stakers = read_stakers_v120(deps.storage, start, None, Order::Ascending, Some(limit))?;
+
+    let mut converted_lockups: u64 = 0;
+    for (staker, info) in stakers.iter() {
+        converted_lockups += info.deprecated_lockups_count();
+
+        let staker_info = info
+            .clone()
+            .into_staker_info(env.block.height, config.prev_epoch_blocks)?;
+        store_migrated_staker_info(
+            deps.storage,
+            staker,
+            &staker_info,
+            env.block.height,
+            epoch_info.epoch,
+        )?;
+    }
+
+    if let Some((staker, _)) = stakers.last() {
+        progress.last_migrated_staker = Some(staker.clone());
+    }
+    progress.migrated_stakers += stakers.len() as u64;
+    progress.converted_lockups += converted_lockups;
+    progress.completed = stakers.len() < limit;
+    if progress.completed {
+        progress.completed_at_block = env.block.height;
+    }
+    store_lockups_migration_progress(deps.storage, &progress)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "migrate_lockups_batch"),
+        ("migrated_stakers", &stakers.len().to_string()),
+        ("converted_lockups", &converted_lockups.to_string()),
+        ("completed", &progress.completed.to_string()),
+    ]))
+}
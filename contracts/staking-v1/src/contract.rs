@@ -1,3 +1,4 @@
+use astroport::asset::{Asset, AssetInfo};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
@@ -10,9 +11,15 @@ use cw20::Cw20ReceiveMsg;
 use crate::{
     commands,
     error::ContractError,
-    migration::{load_config_v100, MigrationMsgV100},
+    migration::{
+        load_config_v100, store_lockups_migration_progress, LockupsMigrationProgress,
+        MigrationMsgV100,
+    },
     queries,
-    state::{load_config, store_config, store_state, update_owner, Config, LockupConfig, State},
+    state::{
+        load_config, load_state, reset_stakers_stats, store_config, store_state, update_owner,
+        BoostConfig, Config, LockupConfig, State,
+    },
 };
 
 use services::{
@@ -55,6 +62,15 @@ pub fn instantiate(
         None
     };
 
+    let boost_config = if let Some(boost) = msg.boost {
+        Some(BoostConfig {
+            bbro_token: deps.api.addr_canonicalize(&boost.bbro_token)?,
+            max_boost: boost.max_boost,
+        })
+    } else {
+        None
+    };
+
     let epoch_info = query_epoch_info(
         &deps.querier,
         deps.api.addr_validate(&msg.epoch_manager_contract)?,
@@ -63,11 +79,13 @@ pub fn instantiate(
     let config = Config {
         owner: deps.api.addr_canonicalize(&msg.owner)?,
         paused: false,
+        emergency_mode: false,
         bro_token: deps.api.addr_canonicalize(&msg.bro_token)?,
         rewards_pool_contract: deps.api.addr_canonicalize(&msg.rewards_pool_contract)?,
         bbro_minter_contract: deps.api.addr_canonicalize(&msg.bbro_minter_contract)?,
         epoch_manager_contract: deps.api.addr_canonicalize(&msg.epoch_manager_contract)?,
         community_bonding_contract,
+        bond_lock_contracts: vec![],
         unstake_period_blocks: msg.unstake_period_blocks,
         min_staking_amount: msg.min_staking_amount,
         lockup_config: LockupConfig {
@@ -76,8 +94,12 @@ pub fn instantiate(
             base_rate: msg.base_rate,
             linear_growth: msg.linear_growth,
             exponential_growth: msg.exponential_growth,
+            early_unlock_penalty: msg.early_unlock_penalty,
         },
         prev_epoch_blocks: epoch_info.epoch,
+        boost_config,
+        instant_unstake: msg.instant_unstake,
+        staker_history_limit: msg.staker_history_limit,
     };
 
     config.validate()?;
@@ -89,6 +111,17 @@ pub fn instantiate(
             global_reward_index: Decimal::zero(),
             total_stake_amount: Uint128::zero(),
             last_distribution_block: env.block.height,
+            reward_rate: Decimal::zero(),
+            period_finish: env.block.height,
+        },
+    )?;
+
+    // new contract doesn't have stakers with deprecated lockups
+    store_lockups_migration_progress(
+        deps.storage,
+        &LockupsMigrationProgress {
+            completed: true,
+            ..LockupsMigrationProgress::default()
         },
     )?;
 
@@ -116,14 +149,56 @@ pub fn instantiate(
 ///         epochs_locked,
 ///     }** Lockup unlocked staked amount
 ///
-/// * **ExecuteMsg::Unstake { amount }** Unstake staked amount of tokens
+/// * **ExecuteMsg::ExtendLockup { lockup_index, additional_epochs }** Extends lockup period
+///
+/// * **ExecuteMsg::MergeLockups { indices }** Merges specified lockups into single lockup
+///
+/// * **ExecuteMsg::EarlyUnlock { lockup_index, amount }** Unlocks lockup before expiration with a penalty
+///
+/// * **ExecuteMsg::Unstake { amount, unstake_type }** Unstake staked amount of tokens
 ///
 /// * **ExecuteMsg::Withdraw {}** Withdraw the amount of tokens that have already passed the unstaking period
 ///
+/// * **ExecuteMsg::SetRewardRecipient { recipient }** Sets the address which receives staker's rewards and withdrawals
+///
+/// * **ExecuteMsg::ApproveClaimOperator { operator, expires }** Allows operator to claim on behalf of staker
+///
+/// * **ExecuteMsg::RevokeClaimOperator { operator }** Revokes operator permission to claim on behalf of staker
+///
+/// * **ExecuteMsg::ClaimFor { staker }** Claims available rewards and withdrawals on behalf of staker
+///
+/// * **ExecuteMsg::TransferPosition { recipient, lockup_indices, include_unlocked }** Moves staker's position to another address
+///
+/// * **ExecuteMsg::CancelUnstake { index }** Cancels pending withdrawal and stakes its amount back
+///
+/// * **ExecuteMsg::RestakeWithdrawals {}** Stakes back the amount of all pending withdrawals
+///
 /// * **ExecuteMsg::ClaimBroRewards {}** Claim available bro reward amount
 ///
 /// * **ExecuteMsg::ClaimBbroRewards {}** Claim available bbro reward amount
 ///
+/// * **ExecuteMsg::ClaimAllRewards {}** Claim available bro reward amount and pending amounts of all reward assets
+///
+/// * **ExecuteMsg::CompoundBroRewards { epochs_locked }** Restakes available bro reward amount
+///
+/// * **ExecuteMsg::SetAutoCompound { enabled }** Enables or disables automatic restaking of bro rewards
+///
+/// * **ExecuteMsg::AutoCompoundBroRewards { stakers }** Restakes bro rewards of auto-compounding stakers
+///
+/// * **ExecuteMsg::UpdateBoost { stakers }** Recalculates boost multiplier of specified stakers
+///
+/// * **ExecuteMsg::MigrateLockupsBatch { limit }** Converts deprecated lockups of the next batch of stakers
+///
+/// * **ExecuteMsg::EmergencyWithdraw {}** Withdraws whole stake and pending withdrawals immediately while emergency mode is enabled
+///
+/// * **ExecuteMsg::EnableEmergencyMode {}** Enables emergency mode
+///
+/// * **ExecuteMsg::AddRewardAsset { asset_info }** Registers new reward asset
+///
+/// * **ExecuteMsg::FundRewardAsset { asset }** Distributes attached native tokens of registered reward asset
+///
+/// * **ExecuteMsg::UpdateBondLockContracts { bond_lock_contracts }** Replaces a list of contracts allowed to lock bond payouts
+///
 /// * **ExecuteMsg::UpdateConfig {
 ///         paused,
 ///         unstake_period_blocks,
@@ -134,6 +209,10 @@ pub fn instantiate(
 ///         linear_growth,
 ///         exponential_growth,
 ///         community_bonding_contract,
+///         early_unlock_penalty,
+///         boost,
+///         instant_unstake,
+///         staker_history_limit,
 ///     }** Updates contract settings
 ///
 /// * **ExecuteMsg::ProposeNewOwner {
@@ -161,16 +240,81 @@ pub fn execute(
             epochs_locked,
         } => {
             assert_not_paused(deps.storage)?;
+            assert_not_emergency_mode(deps.storage)?;
             commands::lockup_staked(deps, env, info, amount, epochs_locked)
         }
-        ExecuteMsg::Unstake { amount } => {
+        ExecuteMsg::ExtendLockup {
+            lockup_index,
+            additional_epochs,
+        } => {
+            assert_not_paused(deps.storage)?;
+            assert_not_emergency_mode(deps.storage)?;
+            commands::extend_lockup(deps, env, info, lockup_index, additional_epochs)
+        }
+        ExecuteMsg::MergeLockups { indices } => {
+            assert_not_paused(deps.storage)?;
+            assert_not_emergency_mode(deps.storage)?;
+            commands::merge_lockups(deps, env, info, indices)
+        }
+        ExecuteMsg::EarlyUnlock {
+            lockup_index,
+            amount,
+        } => {
+            assert_not_paused(deps.storage)?;
+            commands::early_unlock(deps, env, info, lockup_index, amount)
+        }
+        ExecuteMsg::Unstake {
+            amount,
+            unstake_type,
+        } => {
             assert_not_paused(deps.storage)?;
-            commands::unstake(deps, env, info, amount)
+            commands::unstake(deps, env, info, amount, unstake_type.unwrap_or_default())
         }
         ExecuteMsg::Withdraw {} => {
             assert_not_paused(deps.storage)?;
             commands::withdraw(deps, env, info)
         }
+        ExecuteMsg::SetRewardRecipient { recipient } => {
+            assert_not_paused(deps.storage)?;
+            commands::set_reward_recipient(deps, info, recipient)
+        }
+        ExecuteMsg::ApproveClaimOperator { operator, expires } => {
+            assert_not_paused(deps.storage)?;
+            commands::approve_claim_operator(deps, env, info, operator, expires)
+        }
+        ExecuteMsg::RevokeClaimOperator { operator } => {
+            assert_not_paused(deps.storage)?;
+            commands::revoke_claim_operator(deps, info, operator)
+        }
+        ExecuteMsg::ClaimFor { staker } => {
+            assert_not_paused(deps.storage)?;
+            commands::claim_for(deps, env, info, staker)
+        }
+        ExecuteMsg::TransferPosition {
+            recipient,
+            lockup_indices,
+            include_unlocked,
+        } => {
+            assert_not_paused(deps.storage)?;
+            commands::transfer_position(
+                deps,
+                env,
+                info,
+                recipient,
+                lockup_indices,
+                include_unlocked,
+            )
+        }
+        ExecuteMsg::CancelUnstake { index } => {
+            assert_not_paused(deps.storage)?;
+            assert_not_emergency_mode(deps.storage)?;
+            commands::cancel_unstake(deps, env, info, index)
+        }
+        ExecuteMsg::RestakeWithdrawals {} => {
+            assert_not_paused(deps.storage)?;
+            assert_not_emergency_mode(deps.storage)?;
+            commands::restake_withdrawals(deps, env, info)
+        }
         ExecuteMsg::ClaimBroRewards {} => {
             assert_not_paused(deps.storage)?;
             commands::claim_bro_rewards(deps, env, info)
@@ -179,6 +323,53 @@ pub fn execute(
             assert_not_paused(deps.storage)?;
             commands::claim_bbro_rewards(deps, env, info)
         }
+        ExecuteMsg::ClaimAllRewards {} => {
+            assert_not_paused(deps.storage)?;
+            commands::claim_all_rewards(deps, env, info)
+        }
+        ExecuteMsg::CompoundBroRewards { epochs_locked } => {
+            assert_not_paused(deps.storage)?;
+            assert_not_emergency_mode(deps.storage)?;
+            commands::compound_bro_rewards(deps, env, info, epochs_locked)
+        }
+        ExecuteMsg::SetAutoCompound { enabled } => {
+            assert_not_paused(deps.storage)?;
+            commands::set_auto_compound(deps, env, info, enabled)
+        }
+        ExecuteMsg::AutoCompoundBroRewards { stakers } => {
+            assert_not_paused(deps.storage)?;
+            assert_not_emergency_mode(deps.storage)?;
+            commands::auto_compound_bro_rewards(deps, env, stakers)
+        }
+        ExecuteMsg::UpdateBoost { stakers } => {
+            assert_not_paused(deps.storage)?;
+            commands::update_boost(deps, env, stakers)
+        }
+        ExecuteMsg::EmergencyWithdraw {} => commands::emergency_withdraw(deps, env, info),
+        ExecuteMsg::EnableEmergencyMode {} => {
+            assert_owner(deps.storage, deps.api, info.sender)?;
+            commands::enable_emergency_mode(deps)
+        }
+        ExecuteMsg::AddRewardAsset { asset_info } => {
+            assert_owner(deps.storage, deps.api, info.sender)?;
+            commands::add_reward_asset(deps, asset_info)
+        }
+        ExecuteMsg::UpdateBondLockContracts {
+            bond_lock_contracts,
+        } => {
+            assert_owner(deps.storage, deps.api, info.sender)?;
+            commands::update_bond_lock_contracts(deps, bond_lock_contracts)
+        }
+        ExecuteMsg::FundRewardAsset { asset } => {
+            assert_not_paused(deps.storage)?;
+            // cw20 reward assets are funded via receive hook
+            if !asset.is_native_token() {
+                return Err(ContractError::InvalidRewardAsset {});
+            }
+
+            asset.assert_sent_native_token_balance(&info)?;
+            commands::fund_reward_asset(deps, info.sender, asset)
+        }
         ExecuteMsg::UpdateConfig {
             paused,
             unstake_period_blocks,
@@ -189,10 +380,15 @@ pub fn execute(
             linear_growth,
             exponential_growth,
             community_bonding_contract,
+            early_unlock_penalty,
+            boost,
+            instant_unstake,
+            staker_history_limit,
         } => {
             assert_owner(deps.storage, deps.api, info.sender)?;
             commands::update_config(
                 deps,
+                env,
                 paused,
                 unstake_period_blocks,
                 min_staking_amount,
@@ -202,11 +398,18 @@ pub fn execute(
                 linear_growth,
                 exponential_growth,
                 community_bonding_contract,
+                early_unlock_penalty.map(|penalty| *penalty),
+                boost.map(|boost| *boost),
+                instant_unstake.map(|instant_unstake| *instant_unstake),
+                staker_history_limit,
             )
         }
         ExecuteMsg::UpdateStakerLockups { stakers } => {
             commands::update_staker_lockups(deps, env, stakers)
         }
+        ExecuteMsg::MigrateLockupsBatch { limit } => {
+            commands::migrate_lockups_batch(deps, env, limit)
+        }
         ExecuteMsg::ProposeNewOwner {
             new_owner,
             expires_in_blocks,
@@ -250,6 +453,20 @@ pub fn receive_cw20(
     cw20_msg: Cw20ReceiveMsg,
 ) -> Result<Response, ContractError> {
     let config = load_config(deps.storage)?;
+
+    // any registered cw20 reward asset can be funded by anyone
+    if let Ok(Cw20HookMsg::FundRewardAsset {}) = from_binary(&cw20_msg.msg) {
+        let cw20_sender = deps.api.addr_validate(&cw20_msg.sender)?;
+        let asset = Asset {
+            info: AssetInfo::Token {
+                contract_addr: info.sender,
+            },
+            amount: cw20_msg.amount,
+        };
+
+        return commands::fund_reward_asset(deps, cw20_sender, asset);
+    }
+
     if info.sender != deps.api.addr_humanize(&config.bro_token)? {
         return Err(ContractError::Unauthorized {});
     }
@@ -263,9 +480,10 @@ pub fn receive_cw20(
                 return Err(ContractError::Unauthorized {});
             }
 
-            commands::distribute_reward(deps, cw20_msg.amount, distributed_at_block)
+            commands::distribute_reward(deps, env, cw20_msg.amount, distributed_at_block)
         }
         Ok(Cw20HookMsg::Stake { stake_type }) => {
+            assert_not_emergency_mode(deps.storage)?;
             let cw20_sender = deps.api.addr_validate(&cw20_msg.sender)?;
             commands::stake(deps, env, cw20_sender, cw20_msg.amount, stake_type)
         }
@@ -273,21 +491,24 @@ pub fn receive_cw20(
             sender,
             epochs_locked,
         }) => {
-            let community_bonding_contract = match config.community_bonding_contract {
-                Some(addr) => addr,
-                None => {
-                    return Err(ContractError::StakingFromCommunityBondingContractIsNotEnabled {})
-                }
-            };
-
-            // only community bonding contract allowed to stake bonded bro tokens with locked staking type
-            if community_bonding_contract != deps.api.addr_canonicalize(&cw20_msg.sender)? {
+            assert_not_emergency_mode(deps.storage)?;
+            if config.community_bonding_contract.is_none() && config.bond_lock_contracts.is_empty()
+            {
+                return Err(ContractError::StakingFromCommunityBondingContractIsNotEnabled {});
+            }
+
+            // only community bonding contract and whitelisted bond lock contracts
+            // allowed to stake bonded bro tokens with locked staking type
+            let cw20_sender_raw = deps.api.addr_canonicalize(&cw20_msg.sender)?;
+            if config.community_bonding_contract.as_ref() != Some(&cw20_sender_raw)
+                && !config.bond_lock_contracts.contains(&cw20_sender_raw)
+            {
                 return Err(ContractError::Unauthorized {});
             }
 
             commands::community_bond_lock(deps, env, sender, cw20_msg.amount, epochs_locked)
         }
-        Err(_) => Err(ContractError::InvalidHookData {}),
+        Ok(Cw20HookMsg::FundRewardAsset {}) | Err(_) => Err(ContractError::InvalidHookData {}),
     }
 }
 
@@ -308,6 +529,19 @@ fn assert_owner(storage: &dyn Storage, api: &dyn Api, sender: Addr) -> Result<()
     Ok(())
 }
 
+/// ## Description
+/// Verifies that emergency mode is not enabled.
+/// Returns [`Ok`] if emergency mode is disabled, otherwise returns [`ContractError`]
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+fn assert_not_emergency_mode(storage: &dyn Storage) -> Result<(), ContractError> {
+    if load_config(storage)?.emergency_mode {
+        return Err(ContractError::EmergencyModeIsEnabled {});
+    }
+
+    Ok(())
+}
+
 /// ## Description
 /// Verifies that contract is not paused.
 /// Returns [`Ok`] if address is valid, otherwise returns [`ContractError`]
@@ -340,12 +574,40 @@ fn assert_not_paused(storage: &dyn Storage) -> Result<(), ContractError> {
 ///
 /// * **QueryMsg::Withdrawals { staker }** Returns available withdrawals for staker by specified address
 ///
+/// * **QueryMsg::Stakers { start_after, limit, order_by }** Returns a paginated list of stakers info
+///
+/// * **QueryMsg::SimulateRewards { staker, at_block }** Returns staker rewards and stake amounts simulated at specified block
+///
+/// * **QueryMsg::SimulateLockup { amount, epochs_locked }** Returns premium bbro reward for locking specified amount
+///
+/// * **QueryMsg::BoostInfo { staker }** Returns staker's boost multiplier and reward weight
+///
+/// * **QueryMsg::ClaimPermissions { staker }** Returns reward recipient and claim operators of staker
+///
+/// * **QueryMsg::RewardHistory { start_after, limit }** Returns reward distribution snapshots
+///
+/// * **QueryMsg::StakerHistory { staker, start_after, limit }** Returns balance-changing actions of staker
+///
+/// * **QueryMsg::EstimatedApr { window_epochs }** Returns apr estimated using distributions within specified amount of last epochs
+///
+/// * **QueryMsg::LockupStats {}** Returns aggregated lockup statistics
+///
+/// * **QueryMsg::RewardAssets {}** Returns registered reward assets
+///
+/// * **QueryMsg::PendingRewards { staker }** Returns staker's pending amounts per reward asset
+///
+/// * **QueryMsg::VotingPower { address, at_block }** Returns voting power of address at specified block
+///
+/// * **QueryMsg::TotalVotingPower { at_block }** Returns total voting power at specified block
+///
+/// * **QueryMsg::LockupsMigrationProgress {}** Returns progress of migration to epoch lockups
+///
 /// * **QueryMsg::OwnershipProposal {}** Returns information about created ownership proposal otherwise returns not-found error
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_binary(&queries::query_config(deps)?),
-        QueryMsg::State {} => to_binary(&queries::query_state(deps)?),
+        QueryMsg::State {} => to_binary(&queries::query_state(deps, env)?),
         QueryMsg::StakerInfo { staker } => {
             to_binary(&queries::query_staker_info(deps, env, staker)?)
         }
@@ -353,6 +615,62 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::StakersWithDeprecatedLockups { skip, limit } => to_binary(
             &queries::query_stakers_with_deprecated_lockups(deps, skip, limit)?,
         ),
+        QueryMsg::Stakers {
+            start_after,
+            limit,
+            order_by,
+        } => to_binary(&queries::query_stakers(
+            deps,
+            env,
+            start_after,
+            limit,
+            order_by,
+        )?),
+        QueryMsg::SimulateRewards { staker, at_block } => to_binary(
+            &queries::query_simulate_rewards(deps, env, staker, at_block)?,
+        ),
+        QueryMsg::SimulateLockup {
+            amount,
+            epochs_locked,
+        } => to_binary(&queries::query_simulate_lockup(
+            deps,
+            amount,
+            epochs_locked,
+        )?),
+        QueryMsg::BoostInfo { staker } => to_binary(&queries::query_boost_info(deps, env, staker)?),
+        QueryMsg::ClaimPermissions { staker } => {
+            to_binary(&queries::query_claim_permissions(deps, staker)?)
+        }
+        QueryMsg::RewardHistory { start_after, limit } => {
+            to_binary(&queries::query_reward_history(deps, start_after, limit)?)
+        }
+        QueryMsg::StakerHistory {
+            staker,
+            start_after,
+            limit,
+        } => to_binary(&queries::query_staker_history(
+            deps,
+            staker,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::EstimatedApr { window_epochs } => {
+            to_binary(&queries::query_estimated_apr(deps, env, window_epochs)?)
+        }
+        QueryMsg::LockupStats {} => to_binary(&queries::query_lockup_stats(deps)?),
+        QueryMsg::RewardAssets {} => to_binary(&queries::query_reward_assets(deps)?),
+        QueryMsg::PendingRewards { staker } => {
+            to_binary(&queries::query_pending_rewards(deps, env, staker)?)
+        }
+        QueryMsg::VotingPower { address, at_block } => {
+            to_binary(&queries::query_voting_power(deps, env, address, at_block)?)
+        }
+        QueryMsg::TotalVotingPower { at_block } => {
+            to_binary(&queries::query_total_voting_power(deps, env, at_block)?)
+        }
+        QueryMsg::LockupsMigrationProgress {} => {
+            to_binary(&queries::query_lockups_migration_progress(deps)?)
+        }
         QueryMsg::OwnershipProposal {} => to_binary(&query_ownership_proposal(deps)?),
     }
 }
@@ -385,19 +703,29 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, Co
                 let new_config = Config {
                     owner: config.owner,
                     paused: config.paused,
+                    emergency_mode: false,
                     bro_token: config.bro_token,
                     rewards_pool_contract: config.rewards_pool_contract,
                     bbro_minter_contract: config.bbro_minter_contract,
                     epoch_manager_contract: config.epoch_manager_contract,
                     community_bonding_contract,
+                    bond_lock_contracts: vec![],
                     unstake_period_blocks: config.unstake_period_blocks,
                     min_staking_amount: config.min_staking_amount,
                     lockup_config: config.lockup_config,
                     prev_epoch_blocks: msg.prev_epoch_blocks,
+                    boost_config: None,
+                    instant_unstake: None,
+                    staker_history_limit: 0,
                 };
 
                 new_config.validate()?;
                 store_config(deps.storage, &new_config)?;
+
+                migrate_stakers_stats(deps.storage)?;
+            }
+            "1.1.0" => {
+                migrate_stakers_stats(deps.storage)?;
             }
             _ => return Err(ContractError::MigrationError {}),
         },
@@ -414,3 +742,14 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, Co
         ("new_contract_version", CONTRACT_VERSION),
     ]))
 }
+
+/// ## Description
+/// Resets stakers stats and restarts lockups migration batches which accumulate them,
+/// so migration does not iterate over all stakers at once
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+fn migrate_stakers_stats(storage: &mut dyn Storage) -> StdResult<()> {
+    let state = load_state(storage)?;
+    reset_stakers_stats(storage, state.total_stake_amount)?;
+    store_lockups_migration_progress(storage, &LockupsMigrationProgress::default())
+}
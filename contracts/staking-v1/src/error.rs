@@ -28,6 +28,33 @@ pub enum ContractError {
     #[error("Contract is paused")]
     ContractIsPaused {},
 
+    #[error("Emergency mode is enabled")]
+    EmergencyModeIsEnabled {},
+
+    #[error("Emergency mode is not enabled")]
+    EmergencyModeIsNotEnabled {},
+
+    #[error("Boost is not enabled")]
+    BoostIsNotEnabled {},
+
+    #[error("Invalid reward asset")]
+    InvalidRewardAsset {},
+
+    #[error("Reward asset already exists")]
+    RewardAssetAlreadyExists {},
+
+    #[error("Reward asset not found")]
+    RewardAssetNotFound {},
+
+    #[error("Too many reward assets")]
+    TooManyRewardAssets {},
+
+    #[error("Invalid fund amount")]
+    InvalidFundAmount {},
+
+    #[error("Nothing staked")]
+    NothingStaked {},
+
     #[error("Invalid receive hook msg")]
     InvalidHookData {},
 
@@ -40,6 +67,27 @@ pub enum ContractError {
     #[error("Nothing to claim")]
     NothingToClaim {},
 
+    #[error("Nothing to compound")]
+    NothingToCompound {},
+
+    #[error("Cannot set own account as claim operator")]
+    CannotSetOwnAccount {},
+
+    #[error("Invalid expiration")]
+    InvalidExpiration {},
+
+    #[error("Nothing to transfer")]
+    NothingToTransfer {},
+
+    #[error("Position cannot be transferred to the same address")]
+    InvalidTransferRecipient {},
+
+    #[error("Nothing to restake")]
+    NothingToRestake {},
+
+    #[error("Withdrawal not found")]
+    WithdrawalNotFound {},
+
     #[error("Invalid lockup period")]
     InvalidLockupPeriod {},
 
@@ -48,4 +96,24 @@ pub enum ContractError {
 
     #[error("Forbidden to lockup more than unlocked")]
     ForbiddenToLockupMoreThanUnlocked {},
+
+    #[error("Early unlock is not enabled")]
+    EarlyUnlockIsNotEnabled {},
+
+    #[error("Lockup not found")]
+    LockupNotFound {},
+
+    #[error("At least two different lockups must be specified to merge")]
+    InvalidLockupsToMerge {},
+
+    #[error(
+        "Early unlock amount must be higher than zero and less than or equal to lockup amount"
+    )]
+    InvalidEarlyUnlockAmount {},
+
+    #[error("Instant unstake is not enabled")]
+    InstantUnstakeIsNotEnabled {},
+
+    #[error("Lockups migration is already completed")]
+    LockupsMigrationIsCompleted {},
 }
@@ -19,6 +19,15 @@ pub fn decimal_sum_in_256(a: Decimal, b: Decimal) -> Decimal {
     c_u256
 }
 
+/// ## Description
+/// Performs decimal division
+pub fn decimal_div_in_256(a: Decimal, b: Decimal) -> Decimal {
+    let a_u256: Decimal256 = a.into();
+    let b_u256: Decimal256 = b.into();
+    let c_u256: Decimal = (a_u256 / b_u256).into();
+    c_u256
+}
+
 /// ## Description
 /// Performs decimal substraction
 pub fn decimal_sub_in_256(a: Decimal, b: Decimal) -> Decimal {
@@ -1,15 +1,26 @@
-use cosmwasm_std::{CanonicalAddr, StdResult, Storage, Uint128};
+use cosmwasm_std::{CanonicalAddr, Decimal, Order, StdResult, Storage, Uint128};
 
-use cw_storage_plus::Item;
+use cw20::Expiration;
+use cw_storage_plus::{Bound, Item, Map};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::state::LockupConfig;
+use crate::state::{LockupConfig, LockupInfo, StakerInfo};
 
 /// ## Description
 /// Stores outdated config struct of type [`ConfigV100`] at the given key
 static CONFIGV100: Item<ConfigV100> = Item::new("config");
 
+/// ## Description
+/// A map which stores stakers info of outdated struct [`StakerInfoV120`] at the given key.
+/// Outdated struct is able to read stakers info stored using both outdated and current lockups schema
+static STAKERSV120: Map<&[u8], StakerInfoV120> = Map::new("stakers");
+
+/// ## Description
+/// Stores progress of converting outdated stakers lockups of type [`LockupsMigrationProgress`] at the given key
+static LOCKUPS_MIGRATION_PROGRESS: Item<LockupsMigrationProgress> =
+    Item::new("lockups_migration_progress");
+
 /// ## Description
 /// This structure describes a contract migration message.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -51,3 +62,239 @@ pub struct ConfigV100 {
 pub fn load_config_v100(storage: &dyn Storage) -> StdResult<ConfigV100> {
     CONFIGV100.load(storage)
 }
+
+/// ## Description
+/// This structure describes the lockup info of staking contract before migration to epoch lockups.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LockupInfoV120 {
+    /// locked amount
+    pub amount: Uint128,
+    /// [DEPRECATED] block at which amount will be unlocked
+    #[serde(default)]
+    pub unlocked_at: Expiration,
+    /// block at whick locup was created
+    pub locked_at_block: Option<u64>,
+    /// amount of epochs until lockup will be unlocked
+    pub epochs_locked: Option<u64>,
+}
+
+/// ## Description
+/// This structure describes the staker info of staking contract before migration to epoch lockups.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakerInfoV120 {
+    /// reward index of staker
+    pub reward_index: Decimal,
+    /// amount of unlocked BRO tokens staked by staker
+    pub unlocked_stake_amount: Uint128,
+    /// amount of locked BRO tokens staked by staker
+    pub locked_stake_amount: Uint128,
+    /// amount of pending bro rewards of staker
+    pub pending_bro_reward: Uint128,
+    /// amount of pending bbro rewards of staker
+    pub pending_bbro_reward: Uint128,
+    /// last balance update(stake, unstake, claim) block
+    pub last_balance_update: u64,
+    /// amounts locked by staker
+    pub lockups: Vec<LockupInfoV120>,
+    /// defines whether pending bro rewards are restaked automatically or not
+    #[serde(default)]
+    pub auto_compound: bool,
+    /// bbro boost multiplier applied to staked amount at last checkpoint
+    #[serde(default)]
+    pub boost_multiplier: Option<Decimal>,
+}
+
+impl StakerInfoV120 {
+    /// ## Description
+    /// Returns amount of lockups which are not converted to epoch lockups yet
+    pub fn deprecated_lockups_count(&self) -> u64 {
+        self.lockups
+            .iter()
+            .filter(|l| l.locked_at_block.is_none() || l.epochs_locked.is_none())
+            .count() as u64
+    }
+
+    /// ## Description
+    /// Converts staker info to current struct.
+    /// Deprecated lockups are locked starting from current block for their remaining epochs
+    /// calculated using previous epoch length, lockups with less than one epoch left are unlocked
+    pub fn into_staker_info(
+        self,
+        current_block: u64,
+        prev_epoch_blocks: u64,
+    ) -> StdResult<StakerInfo> {
+        let mut unlocked_amount = Uint128::zero();
+        let mut lockups: Vec<LockupInfo> = vec![];
+        for lockup in self.lockups {
+            if let (Some(locked_at_block), Some(epochs_locked)) =
+                (lockup.locked_at_block, lockup.epochs_locked)
+            {
+                lockups.push(LockupInfo {
+                    amount: lockup.amount,
+                    locked_at_block,
+                    epochs_locked,
+                });
+                continue;
+            }
+
+            let remaining_epochs = match lockup.unlocked_at {
+                Expiration::AtHeight(height) => {
+                    height.saturating_sub(current_block) / prev_epoch_blocks
+                }
+                _ => 0,
+            };
+
+            if remaining_epochs == 0 {
+                unlocked_amount = unlocked_amount.checked_add(lockup.amount)?;
+            } else {
+                lockups.push(LockupInfo {
+                    amount: lockup.amount,
+                    locked_at_block: current_block,
+                    epochs_locked: remaining_epochs,
+                });
+            }
+        }
+
+        Ok(StakerInfo {
+            reward_index: self.reward_index,
+            unlocked_stake_amount: self.unlocked_stake_amount.checked_add(unlocked_amount)?,
+            locked_stake_amount: self.locked_stake_amount.checked_sub(unlocked_amount)?,
+            pending_bro_reward: self.pending_bro_reward,
+            pending_bbro_reward: self.pending_bbro_reward,
+            last_balance_update: self.last_balance_update,
+            lockups,
+            auto_compound: self.auto_compound,
+            boost_multiplier: self.boost_multiplier,
+        })
+    }
+
+    /// ## Description
+    /// Converts staker info to current struct as it is accounted in stakers stats.
+    /// Deprecated lockups are not included into lockup stats, so they are left out
+    pub fn into_accounted_staker_info(self) -> StakerInfo {
+        StakerInfo {
+            reward_index: self.reward_index,
+            unlocked_stake_amount: self.unlocked_stake_amount,
+            locked_stake_amount: self.locked_stake_amount,
+            pending_bro_reward: self.pending_bro_reward,
+            pending_bbro_reward: self.pending_bbro_reward,
+            last_balance_update: self.last_balance_update,
+            lockups: self
+                .lockups
+                .into_iter()
+                .filter_map(|l| {
+                    Some(LockupInfo {
+                        amount: l.amount,
+                        locked_at_block: l.locked_at_block?,
+                        epochs_locked: l.epochs_locked?,
+                    })
+                })
+                .collect(),
+            auto_compound: self.auto_compound,
+            boost_multiplier: self.boost_multiplier,
+        }
+    }
+}
+
+/// ## Description
+/// Returns staker info of outdated struct [`StakerInfoV120`]
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **staker** is an object of type [`CanonicalAddr`]
+pub fn read_staker_info_v120(
+    storage: &dyn Storage,
+    staker: &CanonicalAddr,
+) -> StdResult<Option<StakerInfoV120>> {
+    STAKERSV120.may_load(storage, staker.as_slice())
+}
+
+/// ## Description
+/// Returns the vector that contains stakers info of outdated struct [`StakerInfoV120`]
+/// in specified range and order
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **start** is an [`Option`] of type [`Bound`]
+///
+/// * **end** is an [`Option`] of type [`Bound`]
+///
+/// * **order** is an object of type [`Order`]
+///
+/// * **limit** is an [`Option`] of type [`usize`]
+pub fn read_stakers_v120(
+    storage: &dyn Storage,
+    start: Option<Bound>,
+    end: Option<Bound>,
+    order: Order,
+    limit: Option<usize>,
+) -> StdResult<Vec<(CanonicalAddr, StakerInfoV120)>> {
+    range_stakers_v120(storage, start, end, order)
+        .take(limit.unwrap_or(usize::MAX))
+        .collect()
+}
+
+/// ## Description
+/// Returns the lazy iterator over stakers info of outdated struct [`StakerInfoV120`]
+/// in specified range and order
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **start** is an [`Option`] of type [`Bound`]
+///
+/// * **end** is an [`Option`] of type [`Bound`]
+///
+/// * **order** is an object of type [`Order`]
+pub fn range_stakers_v120<'a>(
+    storage: &'a dyn Storage,
+    start: Option<Bound>,
+    end: Option<Bound>,
+    order: Order,
+) -> impl Iterator<Item = StdResult<(CanonicalAddr, StakerInfoV120)>> + 'a {
+    STAKERSV120.range(storage, start, end, order).map(|item| {
+        let (k, info) = item?;
+        Ok((CanonicalAddr::from(k), info))
+    })
+}
+
+/// ## Description
+/// This structure describes progress of converting outdated stakers lockups.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct LockupsMigrationProgress {
+    /// last migrated staker, next batch starts after it
+    pub last_migrated_staker: Option<CanonicalAddr>,
+    /// amount of migrated stakers
+    pub migrated_stakers: u64,
+    /// amount of converted deprecated lockups
+    pub converted_lockups: u64,
+    /// defines whether all stakers are migrated or not
+    pub completed: bool,
+    /// block at which migration was completed
+    #[serde(default)]
+    pub completed_at_block: u64,
+}
+
+/// ## Description
+/// Returns progress of converting outdated stakers lockups
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+pub fn load_lockups_migration_progress(
+    storage: &dyn Storage,
+) -> StdResult<LockupsMigrationProgress> {
+    Ok(LOCKUPS_MIGRATION_PROGRESS
+        .may_load(storage)?
+        .unwrap_or_default())
+}
+
+/// ## Description
+/// Saves changes of progress of converting outdated stakers lockups
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **progress** is an object of type [`LockupsMigrationProgress`]
+pub fn store_lockups_migration_progress(
+    storage: &mut dyn Storage,
+    progress: &LockupsMigrationProgress,
+) -> StdResult<()> {
+    LOCKUPS_MIGRATION_PROGRESS.save(storage, progress)
+}
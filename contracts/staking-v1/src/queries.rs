@@ -1,16 +1,33 @@
-use cosmwasm_std::{Deps, Env, StdResult};
+use astroport::asset::{Asset, AssetInfo};
+use cosmwasm_std::{Decimal, Deps, Env, StdError, StdResult, Uint128};
 
 use services::{
+    common::OrderBy,
+    epoch_manager::EpochInfoResponse,
     querier::query_epoch_info,
     staking::{
-        ConfigResponse, LockupConfigResponse, LockupInfoResponse, StakerInfoResponse,
-        StateResponse, WithdrawalInfoResponse, WithdrawalsResponse,
+        BoostInfoResponse, BoostParams, ClaimOperatorResponse, ClaimPermissionsResponse,
+        ConfigResponse, EstimatedAprResponse, LockupConfigResponse, LockupInfoResponse,
+        LockupStatsItem, LockupStatsResponse, LockupsMigrationProgressResponse,
+        PendingRewardsResponse, RewardAssetResponse, RewardAssetsResponse, RewardHistoryResponse,
+        RewardSnapshotResponse, SimulateLockupResponse, SimulateRewardsResponse,
+        StakerHistoryEntryResponse, StakerHistoryResponse, StakerInfoResponse, StakersResponse,
+        StateResponse, TotalVotingPowerResponse, VotingPowerResponse, WithdrawalInfoResponse,
+        WithdrawalsResponse,
     },
 };
 
-use crate::state::{
-    load_config, load_state, load_withdrawals, read_staker_info,
-    read_stakers_with_deprecated_lockups,
+use crate::{
+    math::{decimal_mul_in_256, decimal_sum_in_256},
+    migration::load_lockups_migration_progress,
+    state::{
+        load_active_stakers_count, load_config, load_state_at, load_total_working_balance,
+        load_withdrawals, read_claim_operators, read_lockup_stats, read_reward_assets,
+        read_reward_recipient, read_reward_snapshots, read_reward_snapshots_since,
+        read_staker_asset_reward, read_staker_history, read_staker_info, read_stakers,
+        read_stakers_with_deprecated_lockups, read_total_voting_balance, read_voting_balance,
+        read_voting_weight_blocks, StakerInfo, State,
+    },
 };
 
 /// ## Description
@@ -26,9 +43,22 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
         None
     };
 
+    let boost = if let Some(boost_config) = config.boost_config {
+        Some(BoostParams {
+            bbro_token: deps
+                .api
+                .addr_humanize(&boost_config.bbro_token)?
+                .to_string(),
+            max_boost: boost_config.max_boost,
+        })
+    } else {
+        None
+    };
+
     let resp = ConfigResponse {
         owner: deps.api.addr_humanize(&config.owner)?.to_string(),
         paused: config.paused,
+        emergency_mode: config.emergency_mode,
         bro_token: deps.api.addr_humanize(&config.bro_token)?.to_string(),
         rewards_pool_contract: deps
             .api
@@ -43,6 +73,11 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
             .addr_humanize(&config.epoch_manager_contract)?
             .to_string(),
         community_bonding_contract,
+        bond_lock_contracts: config
+            .bond_lock_contracts
+            .iter()
+            .map(|addr| Ok(deps.api.addr_humanize(addr)?.to_string()))
+            .collect::<StdResult<Vec<String>>>()?,
         unstake_period_blocks: config.unstake_period_blocks,
         min_staking_amount: config.min_staking_amount,
         lockup_config: LockupConfigResponse {
@@ -51,7 +86,11 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
             base_rate: config.lockup_config.base_rate,
             linear_growth: config.lockup_config.linear_growth,
             exponential_growth: config.lockup_config.exponential_growth,
+            early_unlock_penalty: config.lockup_config.early_unlock_penalty,
         },
+        boost,
+        instant_unstake: config.instant_unstake,
+        staker_history_limit: config.staker_history_limit,
     };
 
     Ok(resp)
@@ -61,12 +100,16 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
 /// Returns staking contract state in the [`StateResponse`] object
 /// ## Params
 /// * **deps** is an object of type [`Deps`]
-pub fn query_state(deps: Deps) -> StdResult<StateResponse> {
-    let state = load_state(deps.storage)?;
+///
+/// * **env** is an object of type [`Env`]
+pub fn query_state(deps: Deps, env: Env) -> StdResult<StateResponse> {
+    let state = load_state_at(deps.storage, env.block.height)?;
     let resp = StateResponse {
         global_reward_index: state.global_reward_index,
         total_stake_amount: state.total_stake_amount,
         last_distribution_block: state.last_distribution_block,
+        reward_rate: state.reward_rate,
+        period_finish: state.period_finish,
     };
 
     Ok(resp)
@@ -84,18 +127,530 @@ pub fn query_staker_info(deps: Deps, env: Env, staker: String) -> StdResult<Stak
     let staker_raw = deps.api.addr_canonicalize(&staker)?;
 
     let config = load_config(deps.storage)?;
-    let state = load_state(deps.storage)?;
+    let state = load_state_at(deps.storage, env.block.height)?;
+    let staker_info = read_staker_info(deps.storage, &staker_raw, env.block.height)?;
+    let epoch_info = query_epoch_info(
+        &deps.querier,
+        deps.api.addr_humanize(&config.epoch_manager_contract)?,
+    )?;
+
+    staker_info_response(&env, &state, &epoch_info, staker, staker_info)
+}
+
+/// ## Description
+/// Returns a paginated list of stakers info in the [`StakersResponse`] object
+/// ## Params
+/// * **deps** is an object of type [`Deps`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **start_after** is an [`Option`] field of type [`String`]. Sets the index to start reading
+///
+/// * **limit** is an [`Option`] field of type [`u32`]. Sets the limit to reading
+///
+/// * **order_by** is an [`Option`] field of type [`OrderBy`]
+pub fn query_stakers(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+    order_by: Option<OrderBy>,
+) -> StdResult<StakersResponse> {
+    let start_after = if let Some(start_after) = start_after {
+        Some(deps.api.addr_canonicalize(&start_after)?)
+    } else {
+        None
+    };
+
+    let config = load_config(deps.storage)?;
+    let state = load_state_at(deps.storage, env.block.height)?;
+    let epoch_info = query_epoch_info(
+        &deps.querier,
+        deps.api.addr_humanize(&config.epoch_manager_contract)?,
+    )?;
+
+    let stakers = read_stakers(deps.storage, start_after, limit, order_by, env.block.height)?
+        .into_iter()
+        .map(|(staker_raw, staker_info)| {
+            let staker = deps.api.addr_humanize(&staker_raw)?.to_string();
+            staker_info_response(&env, &state, &epoch_info, staker, staker_info)
+        })
+        .collect::<StdResult<Vec<StakerInfoResponse>>>()?;
+
+    Ok(StakersResponse { stakers })
+}
+
+/// ## Description
+/// Returns staker rewards and stake amounts simulated at specified block in the [`SimulateRewardsResponse`] object
+/// ## Params
+/// * **deps** is an object of type [`Deps`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **staker** is a field of type [`String`]
+///
+/// * **at_block** is an [`Option`] field of type [`u64`]
+pub fn query_simulate_rewards(
+    deps: Deps,
+    env: Env,
+    staker: String,
+    at_block: Option<u64>,
+) -> StdResult<SimulateRewardsResponse> {
+    let mut block = env.block;
+    if let Some(at_block) = at_block {
+        if at_block < block.height {
+            return Err(StdError::generic_err(
+                "at_block must be greater than or equal to current block",
+            ));
+        }
+
+        block.height = at_block;
+    }
+
+    let staker_raw = deps.api.addr_canonicalize(&staker)?;
+
+    let config = load_config(deps.storage)?;
+    let state = load_state_at(deps.storage, block.height)?;
+    let mut staker_info = read_staker_info(deps.storage, &staker_raw, block.height)?;
+    let epoch_info = query_epoch_info(
+        &deps.querier,
+        deps.api.addr_humanize(&config.epoch_manager_contract)?,
+    )?;
+
+    staker_info.compute_normal_bbro_reward(&epoch_info, block.height)?;
+    staker_info.compute_bro_reward(&state)?;
+
+    let locked_stake_amount = staker_info.locked_stake_amount;
+    staker_info.unlock_expired_lockups(&block, &epoch_info)?;
+
+    let resp = SimulateRewardsResponse {
+        staker,
+        at_block: block.height,
+        pending_bro_reward: staker_info.pending_bro_reward,
+        pending_bbro_reward: staker_info.pending_bbro_reward,
+        unlocked_stake_amount: staker_info.unlocked_stake_amount,
+        locked_stake_amount: staker_info.locked_stake_amount,
+        expired_lockups_amount: locked_stake_amount.checked_sub(staker_info.locked_stake_amount)?,
+    };
+
+    Ok(resp)
+}
+
+/// ## Description
+/// Returns premium bbro reward for locking specified amount in the [`SimulateLockupResponse`] object
+/// ## Params
+/// * **deps** is an object of type [`Deps`]
+///
+/// * **amount** is an object of type [`Uint128`]
+///
+/// * **epochs_locked** is a field of type [`u64`]
+pub fn query_simulate_lockup(
+    deps: Deps,
+    amount: Uint128,
+    epochs_locked: u64,
+) -> StdResult<SimulateLockupResponse> {
+    let config = load_config(deps.storage)?;
+    if !config.lockup_config.valid_lockup_period(epochs_locked) {
+        return Err(StdError::generic_err("Invalid lockup period"));
+    }
+
+    let bbro_premium_lockup_reward = config
+        .lockup_config
+        .compute_premium_bbro_reward(epochs_locked, amount);
+
+    Ok(SimulateLockupResponse {
+        bbro_premium_lockup_reward,
+    })
+}
+
+/// ## Description
+/// Returns staker's boost multiplier and reward weight in the [`BoostInfoResponse`] object
+/// ## Params
+/// * **deps** is an object of type [`Deps`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **staker** is a field of type [`String`]
+pub fn query_boost_info(deps: Deps, env: Env, staker: String) -> StdResult<BoostInfoResponse> {
+    let staker_raw = deps.api.addr_canonicalize(&staker)?;
+    let staker_info = read_staker_info(deps.storage, &staker_raw, env.block.height)?;
+
+    Ok(BoostInfoResponse {
+        staker,
+        boost_multiplier: staker_info.boost_multiplier.unwrap_or_else(Decimal::one),
+        working_balance: staker_info.reward_weight()?,
+        total_working_balance: load_total_working_balance(deps.storage)?,
+    })
+}
+
+/// ## Description
+/// Returns reward recipient and claim operators of staker in the [`ClaimPermissionsResponse`] object
+/// ## Params
+/// * **deps** is an object of type [`Deps`]
+///
+/// * **staker** is a field of type [`String`]
+pub fn query_claim_permissions(deps: Deps, staker: String) -> StdResult<ClaimPermissionsResponse> {
+    let staker_raw = deps.api.addr_canonicalize(&staker)?;
+
+    let operators = read_claim_operators(deps.storage, &staker_raw)?
+        .into_iter()
+        .map(|(operator, expires)| {
+            Ok(ClaimOperatorResponse {
+                operator: deps.api.addr_humanize(&operator)?.to_string(),
+                expires,
+            })
+        })
+        .collect::<StdResult<Vec<ClaimOperatorResponse>>>()?;
+
+    let resp = ClaimPermissionsResponse {
+        reward_recipient: deps
+            .api
+            .addr_humanize(&read_reward_recipient(deps.storage, &staker_raw)?)?
+            .to_string(),
+        operators,
+    };
+
+    Ok(resp)
+}
+
+/// ## Description
+/// Returns reward distribution snapshots in the [`RewardHistoryResponse`] object
+/// ## Params
+/// * **deps** is an object of type [`Deps`]
+///
+/// * **start_after** is an [`Option`] field of type [`u64`]. Sets the distribution block to start reading after
+///
+/// * **limit** is an [`Option`] field of type [`u32`]. Sets the limit to reading
+pub fn query_reward_history(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<RewardHistoryResponse> {
+    let snapshots = read_reward_snapshots(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|(distributed_at_block, snapshot)| RewardSnapshotResponse {
+            distributed_at_block,
+            global_reward_index: snapshot.global_reward_index,
+            total_stake_amount: snapshot.total_stake_amount,
+            distributed_amount: snapshot.distributed_amount,
+        })
+        .collect();
+
+    Ok(RewardHistoryResponse { snapshots })
+}
+
+/// ## Description
+/// Returns balance-changing actions of staker in ascending order of entry id
+/// in the [`StakerHistoryResponse`] object
+/// ## Params
+/// * **deps** is an object of type [`Deps`]
+///
+/// * **staker** is a field of type [`String`]
+///
+/// * **start_after** is an [`Option`] field of type [`u64`]
+///
+/// * **limit** is an [`Option`] field of type [`u32`]
+pub fn query_staker_history(
+    deps: Deps,
+    staker: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<StakerHistoryResponse> {
+    let config = load_config(deps.storage)?;
+    let staker_raw = deps.api.addr_canonicalize(&staker)?;
+
+    let history = read_staker_history(
+        deps.storage,
+        &staker_raw,
+        config.staker_history_limit,
+        start_after,
+        limit,
+    )?
+    .into_iter()
+    .map(|(id, entry)| StakerHistoryEntryResponse {
+        id,
+        block: entry.block,
+        action: entry.action,
+        amount: entry.amount,
+        epochs_locked: entry.epochs_locked,
+    })
+    .collect();
+
+    Ok(StakerHistoryResponse { history })
+}
+
+/// ## Description
+/// Returns annual percentage rate estimated using distributions within specified amount
+/// of last epochs in the [`EstimatedAprResponse`] object
+/// ## Params
+/// * **deps** is an object of type [`Deps`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **window_epochs** is a field of type [`u64`]
+pub fn query_estimated_apr(
+    deps: Deps,
+    env: Env,
+    window_epochs: u64,
+) -> StdResult<EstimatedAprResponse> {
+    if window_epochs == 0 {
+        return Err(StdError::generic_err(
+            "window_epochs must be greater than zero",
+        ));
+    }
+
+    let config = load_config(deps.storage)?;
+    let epoch_info = query_epoch_info(
+        &deps.querier,
+        deps.api.addr_humanize(&config.epoch_manager_contract)?,
+    )?;
+
+    let window_blocks = window_epochs.saturating_mul(epoch_info.epoch);
+    let window_start_block = env.block.height.saturating_sub(window_blocks);
+
+    // reward index growth equals to reward received by one staked token within window
+    let mut reward_index_growth = Decimal::zero();
+    let mut distributed_amount = Uint128::zero();
+    for snapshot in read_reward_snapshots_since(deps.storage, window_start_block)? {
+        if snapshot.total_stake_amount.is_zero() {
+            continue;
+        }
+
+        reward_index_growth = decimal_sum_in_256(
+            reward_index_growth,
+            Decimal::from_ratio(snapshot.distributed_amount, snapshot.total_stake_amount),
+        );
+        distributed_amount = distributed_amount.checked_add(snapshot.distributed_amount)?;
+    }
+
+    let apr = decimal_mul_in_256(
+        reward_index_growth,
+        Decimal::from_ratio(epoch_info.blocks_per_year, window_blocks),
+    );
+
+    Ok(EstimatedAprResponse {
+        apr,
+        distributed_amount,
+        window_blocks,
+    })
+}
+
+/// ## Description
+/// Returns aggregated lockup statistics in the [`LockupStatsResponse`] object
+/// ## Params
+/// * **deps** is an object of type [`Deps`]
+pub fn query_lockup_stats(deps: Deps) -> StdResult<LockupStatsResponse> {
+    let mut total_locked_amount = Uint128::zero();
+    let lockups = read_lockup_stats(deps.storage)?
+        .into_iter()
+        .map(|(epochs_locked, locked_amount)| {
+            total_locked_amount = total_locked_amount.checked_add(locked_amount)?;
+            Ok(LockupStatsItem {
+                epochs_locked,
+                locked_amount,
+            })
+        })
+        .collect::<StdResult<Vec<LockupStatsItem>>>()?;
+
+    let resp = LockupStatsResponse {
+        active_stakers: load_active_stakers_count(deps.storage)?,
+        total_locked_amount,
+        lockups,
+    };
+
+    Ok(resp)
+}
+
+/// ## Description
+/// Returns registered reward assets in the [`RewardAssetsResponse`] object
+/// ## Params
+/// * **deps** is an object of type [`Deps`]
+pub fn query_reward_assets(deps: Deps) -> StdResult<RewardAssetsResponse> {
+    let reward_assets = read_reward_assets(deps.storage)?
+        .into_iter()
+        .map(|reward_asset| {
+            Ok(RewardAssetResponse {
+                asset_info: reward_asset.asset_info,
+                global_reward_index: reward_asset.global_reward_index,
+                total_distributed_amount: reward_asset.total_distributed_amount,
+            })
+        })
+        .collect::<StdResult<Vec<RewardAssetResponse>>>()?;
+
+    Ok(RewardAssetsResponse { reward_assets })
+}
+
+/// ## Description
+/// Returns staker's pending amounts of bro reward and all reward assets in the [`PendingRewardsResponse`] object
+/// ## Params
+/// * **deps** is an object of type [`Deps`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **staker** is a field of type [`String`]
+pub fn query_pending_rewards(
+    deps: Deps,
+    env: Env,
+    staker: String,
+) -> StdResult<PendingRewardsResponse> {
+    let config = load_config(deps.storage)?;
+    let state = load_state_at(deps.storage, env.block.height)?;
+
+    let staker_raw = deps.api.addr_canonicalize(&staker)?;
     let mut staker_info = read_staker_info(deps.storage, &staker_raw, env.block.height)?;
+    staker_info.compute_bro_reward(&state)?;
+
+    let mut rewards: Vec<Asset> = vec![Asset {
+        info: AssetInfo::Token {
+            contract_addr: deps.api.addr_humanize(&config.bro_token)?,
+        },
+        amount: staker_info.pending_bro_reward,
+    }];
+
+    let stake_amount = staker_info.total_staked()?;
+    for reward_asset in read_reward_assets(deps.storage)? {
+        let mut staker_reward = read_staker_asset_reward(deps.storage, &staker_raw, &reward_asset)?;
+        staker_reward.compute_reward(&reward_asset, stake_amount)?;
+
+        rewards.push(Asset {
+            info: reward_asset.asset_info,
+            amount: staker_reward.pending_reward,
+        });
+    }
+
+    Ok(PendingRewardsResponse { staker, rewards })
+}
+
+/// ## Description
+/// Returns voting power of address at the beginning of specified block in the [`VotingPowerResponse`] object
+/// ## Params
+/// * **deps** is an object of type [`Deps`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **address** is a field of type [`String`]
+///
+/// * **at_block** is an [`Option`] field of type [`u64`]
+pub fn query_voting_power(
+    deps: Deps,
+    env: Env,
+    address: String,
+    at_block: Option<u64>,
+) -> StdResult<VotingPowerResponse> {
+    validate_voting_block(deps, &env, at_block)?;
+
+    let address_raw = deps.api.addr_canonicalize(&address)?;
+    let block = at_block.unwrap_or(env.block.height);
+    let voting_power = match read_voting_balance(deps.storage, &address_raw, at_block)? {
+        Some(balance) => balance.voting_power(block, voting_weight_blocks(deps, at_block)?)?,
+        None => Uint128::zero(),
+    };
+
+    Ok(VotingPowerResponse {
+        address,
+        at_block: block,
+        voting_power,
+    })
+}
+
+/// ## Description
+/// Returns total voting power at the beginning of specified block in the [`TotalVotingPowerResponse`] object
+/// ## Params
+/// * **deps** is an object of type [`Deps`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **at_block** is an [`Option`] field of type [`u64`]
+pub fn query_total_voting_power(
+    deps: Deps,
+    env: Env,
+    at_block: Option<u64>,
+) -> StdResult<TotalVotingPowerResponse> {
+    validate_voting_block(deps, &env, at_block)?;
+
+    let block = at_block.unwrap_or(env.block.height);
+    let total_voting_power = read_total_voting_balance(deps.storage, at_block, env.block.height)?
+        .voting_power(block, voting_weight_blocks(deps, at_block)?)?;
+
+    Ok(TotalVotingPowerResponse {
+        at_block: block,
+        total_voting_power,
+    })
+}
+
+/// ## Description
+/// Validates that voting power is not queried for future block.
+/// Voting balances of stakers are complete only after lockups migration is completed,
+/// so voting power is not available before it
+/// ## Params
+/// * **deps** is an object of type [`Deps`]
+///
+/// * **env** is an object of type [`Env`]
+///
+/// * **at_block** is an [`Option`] field of type [`u64`]
+fn validate_voting_block(deps: Deps, env: &Env, at_block: Option<u64>) -> StdResult<()> {
+    let progress = load_lockups_migration_progress(deps.storage)?;
+    match at_block {
+        _ if !progress.completed => Err(StdError::generic_err(
+            "voting power is not available until lockups migration is completed",
+        )),
+        Some(at_block) if at_block > env.block.height => Err(StdError::generic_err(
+            "at_block must be less than or equal to current block",
+        )),
+        Some(at_block) if at_block <= progress.completed_at_block => Err(StdError::generic_err(
+            "at_block must be greater than lockups migration completion block",
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// ## Description
+/// Returns max lockup period in blocks snapshotted at specified block,
+/// locked amount with such remaining period has double voting power.
+/// Falls back to current lockup config and epoch length if weight was never snapshotted
+/// ## Params
+/// * **deps** is an object of type [`Deps`]
+///
+/// * **at_block** is an [`Option`] field of type [`u64`]
+fn voting_weight_blocks(deps: Deps, at_block: Option<u64>) -> StdResult<u64> {
+    let weight_blocks = read_voting_weight_blocks(deps.storage, at_block)?;
+    if weight_blocks != 0 {
+        return Ok(weight_blocks);
+    }
+
+    let config = load_config(deps.storage)?;
     let epoch_info = query_epoch_info(
         &deps.querier,
         deps.api.addr_humanize(&config.epoch_manager_contract)?,
     )?;
 
+    Ok(config.lockup_config.voting_weight_blocks(epoch_info.epoch))
+}
+
+/// ## Description
+/// Computes pending rewards and expired lockups of staker and returns it in the [`StakerInfoResponse`] object
+/// ## Params
+/// * **env** is an object of type [`Env`]
+///
+/// * **state** is an object of type [`State`]
+///
+/// * **epoch_info** is an object of type [`EpochInfoResponse`]
+///
+/// * **staker** is a field of type [`String`]
+///
+/// * **staker_info** is an object of type [`StakerInfo`]
+fn staker_info_response(
+    env: &Env,
+    state: &State,
+    epoch_info: &EpochInfoResponse,
+    staker: String,
+    mut staker_info: StakerInfo,
+) -> StdResult<StakerInfoResponse> {
     let last_balance_update = staker_info.last_balance_update;
 
-    staker_info.compute_normal_bbro_reward(&epoch_info, env.block.height)?;
-    staker_info.compute_bro_reward(&state)?;
-    staker_info.unlock_expired_lockups(&env.block, &epoch_info, config.prev_epoch_blocks)?;
+    staker_info.compute_normal_bbro_reward(epoch_info, env.block.height)?;
+    staker_info.compute_bro_reward(state)?;
+    staker_info.unlock_expired_lockups(&env.block, epoch_info)?;
 
     let resp = StakerInfoResponse {
         staker,
@@ -110,10 +665,11 @@ pub fn query_staker_info(deps: Deps, env: Env, staker: String) -> StdResult<Stak
             .into_iter()
             .map(|l| LockupInfoResponse {
                 amount: l.amount,
-                locked_at_block: l.locked_at_block.unwrap(),
-                epochs_locked: l.epochs_locked.unwrap(),
+                locked_at_block: l.locked_at_block,
+                epochs_locked: l.epochs_locked,
             })
             .collect(),
+        auto_compound: staker_info.auto_compound,
     };
 
     Ok(resp)
@@ -146,3 +702,24 @@ pub fn query_stakers_with_deprecated_lockups(
 ) -> StdResult<Vec<String>> {
     read_stakers_with_deprecated_lockups(deps.storage, deps.api, skip, limit)
 }
+
+/// ## Description
+/// Returns progress of migration to epoch lockups in the [`LockupsMigrationProgressResponse`] object
+/// ## Params
+/// * **deps** is an object of type [`Deps`]
+pub fn query_lockups_migration_progress(deps: Deps) -> StdResult<LockupsMigrationProgressResponse> {
+    let progress = load_lockups_migration_progress(deps.storage)?;
+
+    let last_migrated_staker = if let Some(staker) = progress.last_migrated_staker {
+        Some(deps.api.addr_humanize(&staker)?.to_string())
+    } else {
+        None
+    };
+
+    Ok(LockupsMigrationProgressResponse {
+        last_migrated_staker,
+        migrated_stakers: progress.migrated_stakers,
+        converted_lockups: progress.converted_lockups,
+        completed: progress.completed,
+    })
+}
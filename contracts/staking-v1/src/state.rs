@@ -1,14 +1,23 @@
-use std::str::FromStr;
+use std::{collections::BTreeMap, convert::TryInto, str::FromStr};
 
 use cosmwasm_std::{Api, BlockInfo, CanonicalAddr, Decimal, StdError, StdResult, Storage, Uint128};
 use cw20::Expiration;
-use cw_storage_plus::{Item, Map};
+use cw_storage_plus::{Bound, Item, Map, SnapshotMap, Strategy, U64Key};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::math::{decimal_mul_in_256, decimal_sub_in_256, decimal_sum_in_256};
+use crate::math::{decimal_div_in_256, decimal_mul_in_256, decimal_sub_in_256, decimal_sum_in_256};
+use crate::migration::{
+    load_lockups_migration_progress, range_stakers_v120, read_staker_info_v120, read_stakers_v120,
+    StakerInfoV120,
+};
 
-use services::epoch_manager::EpochInfoResponse;
+use astroport::asset::AssetInfo;
+use services::{
+    common::OrderBy,
+    epoch_manager::EpochInfoResponse,
+    staking::{EarlyUnlockPenalty, InstantUnstake},
+};
 
 /// ## Description
 /// Stores config struct of type [`Config`] at the given key
@@ -22,10 +31,76 @@ static STATE: Item<State> = Item::new("state");
 /// A map which stores stakers info with [`CanonicalAddr`] type as key and [`StakerInfo`] type as value
 static STAKERS: Map<&[u8], StakerInfo> = Map::new("stakers");
 
+/// ## Description
+/// A map which stores total locked amount with lockup period in epochs of type [`u64`] as key and [`Uint128`] type as value
+static LOCKUP_STATS: Map<U64Key, Uint128> = Map::new("lockup_stats");
+
+/// ## Description
+/// A map which stores reward distribution snapshots with distribution block of type [`u64`] as key and [`RewardSnapshot`] type as value
+static REWARD_SNAPSHOTS: Map<U64Key, RewardSnapshot> = Map::new("reward_snapshots");
+
+/// ## Description
+/// A map which stores staker history entries with (staker, entry id) pair as key and [`StakerHistoryEntry`] type as value
+static STAKER_HISTORY: Map<(&[u8], U64Key), StakerHistoryEntry> = Map::new("staker_history");
+
+/// ## Description
+/// A map which stores range of stored history entry ids with staker [`CanonicalAddr`] as key and [`StakerHistoryIds`] type as value
+static STAKER_HISTORY_IDS: Map<&[u8], StakerHistoryIds> = Map::new("staker_history_ids");
+
+/// ## Description
+/// Stores amount of stakers with non-zero staked amount
+static ACTIVE_STAKERS_COUNT: Item<u64> = Item::new("active_stakers_count");
+
+/// ## Description
+/// Stores sum of boosted reward weights of all stakers
+static TOTAL_WORKING_BALANCE: Item<Uint128> = Item::new("total_working_balance");
+
 /// ## Description
 /// A map which stores staker withdrawals info with [`CanonicalAddr`] type as key and [`Vec<WithdrawalInfo>`] type as value
 static WITHDRAWALS: Map<&[u8], Vec<WithdrawalInfo>> = Map::new("withdrawals");
 
+/// ## Description
+/// A map which stores reward recipient with staker [`CanonicalAddr`] as key and recipient [`CanonicalAddr`] as value
+static REWARD_RECIPIENTS: Map<&[u8], CanonicalAddr> = Map::new("reward_recipients");
+
+/// ## Description
+/// A map which stores claim operators permissions with (staker, operator) pair as key and [`Expiration`] type as value
+static CLAIM_OPERATORS: Map<(&[u8], &[u8]), Expiration> = Map::new("claim_operators");
+
+/// ## Description
+/// A map which stores registered reward assets with raw asset info bytes as key and [`RewardAssetInfo`] type as value
+static REWARD_ASSETS: Map<&[u8], RewardAssetInfo> = Map::new("reward_assets");
+
+/// ## Description
+/// A map which stores staker rewards of reward assets with (staker, asset) pair as key and [`StakerAssetReward`] type as value
+static STAKER_ASSET_REWARDS: Map<(&[u8], &[u8]), StakerAssetReward> =
+    Map::new("staker_asset_rewards");
+
+/// ## Description
+/// A map which stores historical staked balances of stakers with [`CanonicalAddr`] type as key and [`VotingBalance`] type as value
+static VOTING_BALANCES: SnapshotMap<&[u8], VotingBalance> = SnapshotMap::new(
+    "voting_balances",
+    "voting_balances__checkpoints",
+    "voting_balances__changelog",
+    Strategy::EveryBlock,
+);
+
+/// ## Description
+/// Stores historical total staked balance of type [`TotalVotingBalance`] at [`TOTAL_VOTING_BALANCE_KEY`] key
+static TOTAL_VOTING_BALANCE: SnapshotMap<&[u8], TotalVotingBalance> = SnapshotMap::new(
+    "total_voting_balance",
+    "total_voting_balance__checkpoints",
+    "total_voting_balance__changelog",
+    Strategy::EveryBlock,
+);
+
+const TOTAL_VOTING_BALANCE_KEY: &[u8] = b"total";
+
+/// ## Description
+/// A map which stores total locked amount with unlock block of type [`u64`] as key and [`Uint128`] type as value.
+/// Entries for past blocks are never changed, so they can be used for historical voting power calculation
+static LOCKUP_UNLOCKS: Map<U64Key, Uint128> = Map::new("lockup_unlocks");
+
 /// ## Description
 /// This structure describes the main control config of staking contract.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -34,6 +109,10 @@ pub struct Config {
     pub owner: CanonicalAddr,
     /// defines whether the contract is paused or not
     pub paused: bool,
+    /// defines whether emergency mode is enabled or not.
+    /// Once enabled, stakers can withdraw their stake immediately
+    #[serde(default)]
+    pub emergency_mode: bool,
     /// bro token address
     pub bro_token: CanonicalAddr,
     /// rewards pool address
@@ -46,6 +125,10 @@ pub struct Config {
     /// if value is set to none
     /// than option to stake from community bonding contract is disabled
     pub community_bonding_contract: Option<CanonicalAddr>,
+    /// contracts allowed to lock bond payouts of bonders
+    /// in addition to community bonding contract
+    #[serde(default)]
+    pub bond_lock_contracts: Vec<CanonicalAddr>,
     /// vesting period for withdrawal
     pub unstake_period_blocks: u64,
     /// minimum staking amount
@@ -54,6 +137,21 @@ pub struct Config {
     pub lockup_config: LockupConfig,
     /// previous amount of blocks in epoch
     pub prev_epoch_blocks: u64,
+    /// bbro boost config,
+    /// if value is set to none
+    /// than bro rewards are not boosted
+    #[serde(default)]
+    pub boost_config: Option<BoostConfig>,
+    /// instant unstake config,
+    /// if value is set to none
+    /// than option to unstake instantly is disabled
+    #[serde(default)]
+    pub instant_unstake: Option<InstantUnstake>,
+    /// max amount of history entries kept per staker,
+    /// if value is set to zero
+    /// than staker history is not recorded
+    #[serde(default)]
+    pub staker_history_limit: u64,
 }
 
 impl Config {
@@ -107,10 +205,54 @@ impl Config {
             ));
         }
 
+        if let Some(early_unlock_penalty) = &self.lockup_config.early_unlock_penalty {
+            if early_unlock_penalty.min_rate > early_unlock_penalty.max_rate {
+                return Err(StdError::generic_err(
+                    "min_rate must be less than or equal to max_rate",
+                ));
+            }
+
+            if early_unlock_penalty.max_rate > Decimal::one() {
+                return Err(StdError::generic_err(
+                    "max_rate must be less than or equal to 1.0",
+                ));
+            }
+        }
+
+        if let Some(boost_config) = &self.boost_config {
+            if boost_config.max_boost < Decimal::one() {
+                return Err(StdError::generic_err(
+                    "max_boost must be greater than or equal to 1.0",
+                ));
+            }
+        }
+
+        if let Some(instant_unstake) = &self.instant_unstake {
+            if instant_unstake.fee_rate >= Decimal::one() {
+                return Err(StdError::generic_err("fee_rate must be less than 1.0"));
+            }
+        }
+
+        if self.staker_history_limit > MAX_STAKER_HISTORY_LIMIT {
+            return Err(StdError::generic_err(
+                "staker_history_limit must be less than or equal to 100",
+            ));
+        }
+
         Ok(())
     }
 }
 
+/// ## Description
+/// This structure describes the bbro boost config of staking contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BoostConfig {
+    /// bbro token address
+    pub bbro_token: CanonicalAddr,
+    /// max multiplier applied to staked amount
+    pub max_boost: Decimal,
+}
+
 /// ## Description
 /// This structure describes the lockup config of staking contract.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -125,9 +267,53 @@ pub struct LockupConfig {
     pub linear_growth: Decimal,
     /// exponential growth for bbro premium reward calculation
     pub exponential_growth: Decimal,
+    /// penalty config for unlocking lockups before expiration,
+    /// if value is set to none
+    /// than option to unlock lockups early is disabled
+    pub early_unlock_penalty: Option<EarlyUnlockPenalty>,
 }
 
 impl LockupConfig {
+    /// ## Description
+    /// Returns max lockup period in blocks, locked amount with such remaining period has double voting power
+    pub fn voting_weight_blocks(&self, epoch_blocks: u64) -> u64 {
+        std::cmp::max(self.max_lockup_period_epochs * epoch_blocks, 1)
+    }
+
+    /// ## Description
+    /// Computes premium bbro reward when locking staked BRO using next formula:
+    /// ((base_rate+linear_growth*epochs_locked+exponential_growth*epochs_locked^2)-0.0005)*bro_locked_amount
+    pub fn compute_premium_bbro_reward(&self, epochs_locked: u64, amount: Uint128) -> Uint128 {
+        let epochs_locked: u128 = epochs_locked.into();
+
+        // epochs_locked * linear_growth
+        let linear_growth = decimal_mul_in_256(
+            self.linear_growth,
+            Decimal::from_ratio(Uint128::from(epochs_locked), Uint128::from(1u128)),
+        );
+
+        // epochs_locked^2 * exponential_growth
+        let exponential_growth = decimal_mul_in_256(
+            self.exponential_growth,
+            Decimal::from_ratio(
+                Uint128::from(epochs_locked * epochs_locked),
+                Uint128::from(1u128),
+            ),
+        );
+
+        // (base_rate + linear_growth * epochs_locked + exponential_growth * epochs_locked^2) - 0.0005
+        let bbro_rate = decimal_sub_in_256(
+            decimal_sum_in_256(
+                self.base_rate,
+                decimal_sum_in_256(linear_growth, exponential_growth),
+            ),
+            Decimal::from_ratio(Uint128::from(5u128), Uint128::from(10000u128)),
+        );
+
+        // (...) * bro_locked_amount
+        bbro_rate * amount
+    }
+
     /// ## Description
     /// Validates that passed lockup period is valid
     pub fn valid_lockup_period(&self, epochs_locked: u64) -> bool {
@@ -139,6 +325,31 @@ impl LockupConfig {
 
         true
     }
+
+    /// ## Description
+    /// Computes early unlock penalty rate using next formula:
+    /// min_rate+(max_rate-min_rate)*remaining_blocks/lockup_blocks
+    pub fn early_unlock_penalty_rate(
+        &self,
+        lockup_blocks: u64,
+        remaining_blocks: u64,
+    ) -> Option<Decimal> {
+        let early_unlock_penalty = self.early_unlock_penalty.as_ref()?;
+        if lockup_blocks == 0 {
+            return Some(early_unlock_penalty.min_rate);
+        }
+
+        let remaining_ratio =
+            Decimal::from_ratio(remaining_blocks.min(lockup_blocks), lockup_blocks);
+
+        Some(decimal_sum_in_256(
+            early_unlock_penalty.min_rate,
+            decimal_mul_in_256(
+                decimal_sub_in_256(early_unlock_penalty.max_rate, early_unlock_penalty.min_rate),
+                remaining_ratio,
+            ),
+        ))
+    }
 }
 
 /// ## Description
@@ -149,8 +360,149 @@ pub struct State {
     pub global_reward_index: Decimal,
     /// total amount of staked BRO tokens by all stakers
     pub total_stake_amount: Uint128,
-    /// last rewards distribution block
+    /// block up to which received rewards are streamed into global reward index
     pub last_distribution_block: u64,
+    /// amount of BRO tokens streamed per block
+    #[serde(default)]
+    pub reward_rate: Decimal,
+    /// block at which current reward stream ends
+    #[serde(default)]
+    pub period_finish: u64,
+}
+
+impl State {
+    /// ## Description
+    /// Streams rewards accrued since last distribution block into global reward index.
+    /// If there is no reward weight, remaining stream is postponed
+    pub fn update_reward_index(
+        &mut self,
+        current_block: u64,
+        total_working_balance: Uint128,
+    ) -> StdResult<()> {
+        if current_block <= self.last_distribution_block {
+            return Ok(());
+        }
+
+        if total_working_balance.is_zero() {
+            let remaining_blocks = self
+                .period_finish
+                .saturating_sub(self.last_distribution_block);
+            if remaining_blocks > 0 {
+                self.period_finish = current_block + remaining_blocks;
+            }
+        } else {
+            let last_applicable_block = std::cmp::min(current_block, self.period_finish);
+            if last_applicable_block > self.last_distribution_block {
+                let blocks = last_applicable_block - self.last_distribution_block;
+                let streamed_amount =
+                    decimal_mul_in_256(self.reward_rate, Decimal::from_ratio(blocks, 1u64));
+
+                self.global_reward_index = decimal_sum_in_256(
+                    self.global_reward_index,
+                    decimal_div_in_256(
+                        streamed_amount,
+                        Decimal::from_ratio(total_working_balance, 1u128),
+                    ),
+                );
+            }
+        }
+
+        self.last_distribution_block = current_block;
+        Ok(())
+    }
+
+    /// ## Description
+    /// Starts new reward stream for specified amount
+    /// adding not yet streamed amount of current stream
+    pub fn add_reward_stream(&mut self, amount: Uint128, current_block: u64, blocks: u64) {
+        let remaining_blocks = self.period_finish.saturating_sub(current_block);
+        let leftover = decimal_mul_in_256(
+            self.reward_rate,
+            Decimal::from_ratio(remaining_blocks, 1u64),
+        );
+
+        self.reward_rate = decimal_div_in_256(
+            decimal_sum_in_256(Decimal::from_ratio(amount, 1u128), leftover),
+            Decimal::from_ratio(blocks, 1u64),
+        );
+        self.last_distribution_block = current_block;
+        self.period_finish = current_block + blocks;
+    }
+}
+
+/// ## Description
+/// This structure describes the reward distribution snapshot of staking contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardSnapshot {
+    /// global reward index after distribution
+    pub global_reward_index: Decimal,
+    /// total amount of staked BRO tokens at distribution
+    pub total_stake_amount: Uint128,
+    /// amount of distributed BRO tokens
+    pub distributed_amount: Uint128,
+}
+
+/// ## Description
+/// This structure describes the balance-changing action of staker.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakerHistoryEntry {
+    /// block at which action was performed
+    pub block: u64,
+    /// performed action
+    pub action: String,
+    /// amount of BRO tokens affected by action
+    pub amount: Uint128,
+    /// amount of epochs locked by action
+    pub epochs_locked: Option<u64>,
+}
+
+/// ## Description
+/// This structure describes the range of stored staker history entry ids.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct StakerHistoryIds {
+    /// id of the oldest stored entry
+    pub first_id: u64,
+    /// id of the next entry
+    pub next_id: u64,
+}
+
+/// ## Description
+/// This structure describes the reward asset info of staking contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardAssetInfo {
+    /// reward asset info
+    pub asset_info: AssetInfo,
+    /// global reward index of reward asset
+    pub global_reward_index: Decimal,
+    /// total distributed amount of reward asset
+    pub total_distributed_amount: Uint128,
+}
+
+/// ## Description
+/// This structure describes the staker reward of reward asset.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakerAssetReward {
+    /// reward index of staker
+    pub reward_index: Decimal,
+    /// amount of pending reward of staker
+    pub pending_reward: Uint128,
+}
+
+impl StakerAssetReward {
+    /// ## Description
+    /// Computes reward for staked amount and adds it to pending_reward
+    pub fn compute_reward(
+        &mut self,
+        reward_asset: &RewardAssetInfo,
+        stake_amount: Uint128,
+    ) -> StdResult<()> {
+        let pending_reward = (stake_amount * reward_asset.global_reward_index)
+            .checked_sub(stake_amount * self.reward_index)?;
+
+        self.reward_index = reward_asset.global_reward_index;
+        self.pending_reward = self.pending_reward.checked_add(pending_reward)?;
+        Ok(())
+    }
 }
 
 /// ## Description
@@ -159,12 +511,33 @@ pub struct State {
 pub struct LockupInfo {
     /// locked amount
     pub amount: Uint128,
-    /// [DEPRECATED] block at which amount will be unlocked
-    pub unlocked_at: Expiration,
     /// block at whick locup was created
-    pub locked_at_block: Option<u64>,
+    pub locked_at_block: u64,
     /// amount of epochs until lockup will be unlocked
-    pub epochs_locked: Option<u64>,
+    pub epochs_locked: u64,
+}
+
+impl LockupInfo {
+    /// ## Description
+    /// Returns amount of epochs left until lockup will be unlocked rounded up
+    pub fn remaining_epochs(&self, current_block: u64, epoch_blocks: u64) -> u64 {
+        let remaining_blocks = self
+            .unlocked_at_block(epoch_blocks)
+            .saturating_sub(current_block);
+
+        let mut remaining_epochs = remaining_blocks / epoch_blocks;
+        if remaining_epochs * epoch_blocks < remaining_blocks {
+            remaining_epochs += 1;
+        }
+
+        remaining_epochs
+    }
+
+    /// ## Description
+    /// Returns block at which lockup will be unlocked
+    pub fn unlocked_at_block(&self, epoch_blocks: u64) -> u64 {
+        self.locked_at_block + (self.epochs_locked * epoch_blocks)
+    }
 }
 
 /// ## Description
@@ -185,6 +558,12 @@ pub struct StakerInfo {
     pub last_balance_update: u64,
     /// amounts locked for specified amount of epochs
     pub lockups: Vec<LockupInfo>,
+    /// defines whether pending bro rewards are restaked automatically or not
+    #[serde(default)]
+    pub auto_compound: bool,
+    /// bbro boost multiplier applied to staked amount at last checkpoint
+    #[serde(default)]
+    pub boost_multiplier: Option<Decimal>,
 }
 
 impl StakerInfo {
@@ -196,18 +575,77 @@ impl StakerInfo {
             .checked_add(self.locked_stake_amount)?)
     }
 
+    /// ## Description
+    /// Returns staked amount multiplied by boost multiplier
+    pub fn reward_weight(&self) -> StdResult<Uint128> {
+        let stake_amount = self.total_staked()?;
+        match self.boost_multiplier {
+            Some(boost_multiplier) => Ok(stake_amount * boost_multiplier),
+            None => Ok(stake_amount),
+        }
+    }
+
+    /// ## Description
+    /// Recalculates boost multiplier using next formula:
+    /// 1+(max_boost-1)*min((bbro_balance/bbro_supply)/(staked_amount/total_stake_amount),1)
+    pub fn update_boost_multiplier(
+        &mut self,
+        boost_config: &BoostConfig,
+        total_stake_amount: Uint128,
+        bbro_balance: Uint128,
+        bbro_supply: Uint128,
+    ) -> StdResult<()> {
+        let stake_amount = self.total_staked()?;
+        if stake_amount.is_zero()
+            || total_stake_amount.is_zero()
+            || bbro_balance.is_zero()
+            || bbro_supply.is_zero()
+        {
+            self.boost_multiplier = Some(Decimal::one());
+            return Ok(());
+        }
+
+        let bbro_share = Decimal::from_ratio(bbro_balance, bbro_supply);
+        let stake_share = Decimal::from_ratio(stake_amount, total_stake_amount);
+        let share_ratio =
+            std::cmp::min(decimal_div_in_256(bbro_share, stake_share), Decimal::one());
+
+        self.boost_multiplier = Some(decimal_sum_in_256(
+            Decimal::one(),
+            decimal_mul_in_256(
+                decimal_sub_in_256(boost_config.max_boost, Decimal::one()),
+                share_ratio,
+            ),
+        ));
+
+        Ok(())
+    }
+
     /// ## Description
     /// Computes bro staking reward and adds it to pending_reward
     pub fn compute_bro_reward(&mut self, state: &State) -> StdResult<()> {
-        let stake_amount = self.total_staked()?;
-        let pending_bro_reward = (stake_amount * state.global_reward_index)
-            .checked_sub(stake_amount * self.reward_index)?;
+        let reward_weight = self.reward_weight()?;
+        let pending_bro_reward = (reward_weight * state.global_reward_index)
+            .checked_sub(reward_weight * self.reward_index)?;
 
         self.reward_index = state.global_reward_index;
         self.pending_bro_reward = self.pending_bro_reward.checked_add(pending_bro_reward)?;
         Ok(())
     }
 
+    /// ## Description
+    /// Moves pending bro reward into unlocked staked amount
+    /// and returns compounded amount
+    pub fn compound_bro_reward(&mut self, state: &mut State) -> StdResult<Uint128> {
+        let amount = self.pending_bro_reward;
+
+        self.unlocked_stake_amount = self.unlocked_stake_amount.checked_add(amount)?;
+        self.pending_bro_reward = Uint128::zero();
+        state.total_stake_amount = state.total_stake_amount.checked_add(amount)?;
+
+        Ok(amount)
+    }
+
     /// ## Description
     /// Computes normal bbro reward for staked BRO
     pub fn compute_normal_bbro_reward(
@@ -233,42 +671,15 @@ impl StakerInfo {
     }
 
     /// ## Description
-    /// Computes premium bbro reward when locking staked BRO using next formula:
-    /// ((base_rate+linear_growth*epochs_locked+exponential_growth*epochs_locked^2)-0.0005)*bro_locked_amount
+    /// Computes premium bbro reward when locking staked BRO.
+    /// See [`LockupConfig::compute_premium_bbro_reward`]
     pub fn compute_premium_bbro_reward(
         &self,
         lockup_config: &LockupConfig,
         epochs_locked: u64,
         amount: Uint128,
     ) -> Uint128 {
-        let epochs_locked: u128 = epochs_locked.into();
-
-        // epochs_locked * linear_growth
-        let linear_growth = decimal_mul_in_256(
-            lockup_config.linear_growth,
-            Decimal::from_ratio(Uint128::from(epochs_locked), Uint128::from(1u128)),
-        );
-
-        // epochs_locked^2 * exponential_growth
-        let exponential_growth = decimal_mul_in_256(
-            lockup_config.exponential_growth,
-            Decimal::from_ratio(
-                Uint128::from((epochs_locked * epochs_locked) as u128),
-                Uint128::from(1u128),
-            ),
-        );
-
-        // (base_rate + linear_growth * epochs_locked + exponential_growth * epochs_locked^2) - 0.0005
-        let bbro_rate = decimal_sub_in_256(
-            decimal_sum_in_256(
-                lockup_config.base_rate,
-                decimal_sum_in_256(linear_growth, exponential_growth),
-            ),
-            Decimal::from_ratio(Uint128::from(5u128), Uint128::from(10000u128)),
-        );
-
-        // (...) * bro_locked_amount
-        bbro_rate * amount
+        lockup_config.compute_premium_bbro_reward(epochs_locked, amount)
     }
 
     /// ## Description
@@ -282,9 +693,8 @@ impl StakerInfo {
         self.locked_stake_amount = self.locked_stake_amount.checked_add(amount)?;
         self.lockups.push(LockupInfo {
             amount,
-            unlocked_at: Expiration::Never {},
-            locked_at_block: Some(current_block),
-            epochs_locked: Some(epochs_locked),
+            locked_at_block: current_block,
+            epochs_locked,
         });
 
         Ok(())
@@ -296,43 +706,18 @@ impl StakerInfo {
         &mut self,
         current_block: &BlockInfo,
         epoch_info: &EpochInfoResponse,
-        prev_epoch_blocks: u64,
     ) -> StdResult<()> {
         let mut unlocked_amount = Uint128::zero();
         let lockups: Vec<LockupInfo> = self
             .lockups
             .clone()
             .into_iter()
-            .filter_map(|mut l| {
-                if l.locked_at_block.is_none() {
-                    let unlocked_at = match l.unlocked_at {
-                        Expiration::AtHeight(height) => height,
-                        _ => return None,
-                    };
-
-                    let remaining_epochs = (unlocked_at
-                        .checked_sub(current_block.height)
-                        .unwrap_or_default())
-                        / prev_epoch_blocks;
-
-                    if l.unlocked_at.is_expired(current_block) || remaining_epochs == 0 {
-                        unlocked_amount += l.amount;
-                        return None;
-                    } else {
-                        l.locked_at_block = Some(current_block.height);
-                        l.epochs_locked = Some(remaining_epochs);
-                    }
-                }
-
-                let locked_at_block = l.locked_at_block?;
-                let epochs_locked = l.epochs_locked?;
-
-                let unlocked_at_block = locked_at_block + (epochs_locked * epoch_info.epoch);
-                if current_block.height >= unlocked_at_block {
+            .filter(|l| {
+                if current_block.height >= l.unlocked_at_block(epoch_info.epoch) {
                     unlocked_amount += l.amount;
-                    None
+                    false
                 } else {
-                    Some(l)
+                    true
                 }
             })
             .collect();
@@ -345,36 +730,253 @@ impl StakerInfo {
     }
 
     /// ## Description
-    /// Checks if staker info can be deleted or not
-    pub fn can_be_removed(&self) -> StdResult<bool> {
-        Ok(self.total_staked()?.is_zero()
-            && self.pending_bro_reward.is_zero()
-            && self.pending_bbro_reward.is_zero())
+    /// Locks lockup by index again for specified amount of epochs starting from current block
+    pub fn relock(
+        &mut self,
+        lockup_index: usize,
+        current_block: u64,
+        epochs_locked: u64,
+    ) -> StdResult<()> {
+        let lockup = self
+            .lockups
+            .get_mut(lockup_index)
+            .ok_or_else(|| StdError::generic_err("lockup not found"))?;
+
+        lockup.locked_at_block = current_block;
+        lockup.epochs_locked = epochs_locked;
+
+        Ok(())
     }
-}
 
-/// ## Description
-/// This structure describes withdrawal info of staking contract.
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct WithdrawalInfo {
-    /// amount to withdraw
-    pub amount: Uint128,
-    /// block at which amount can be claimed
-    pub claimable_at: Expiration,
-}
+    /// ## Description
+    /// Replaces lockups by indices with single lockup
+    /// locked for specified amount of epochs starting from current block
+    pub fn merge_lockups(
+        &mut self,
+        lockup_indices: &[usize],
+        current_block: u64,
+        epochs_locked: u64,
+    ) -> StdResult<()> {
+        let mut amount = Uint128::zero();
+        let mut lockups: Vec<LockupInfo> = vec![];
+        for (index, lockup) in self.lockups.iter().enumerate() {
+            if lockup_indices.contains(&index) {
+                amount = amount.checked_add(lockup.amount)?;
+            } else {
+                lockups.push(lockup.clone());
+            }
+        }
 
-/// ## Description
-/// Saves changes of [`Config`] struct in [`CONFIG`] storage
-/// ## Params
-/// * **storage** is an object of type [`Storage`]
-///
-/// * **config** updated config struct of type [`Config`]
-pub fn store_config(storage: &mut dyn Storage, config: &Config) -> StdResult<()> {
-    CONFIG.save(storage, config)
-}
+        lockups.push(LockupInfo {
+            amount,
+            locked_at_block: current_block,
+            epochs_locked,
+        });
+        self.lockups = lockups;
 
-/// ## Description
-/// Returns config struct of type [`Config`]
+        Ok(())
+    }
+
+    /// ## Description
+    /// Removes lockups by indices from staker and returns them
+    pub fn take_lockups(&mut self, lockup_indices: &[usize]) -> StdResult<Vec<LockupInfo>> {
+        let mut taken: Vec<LockupInfo> = vec![];
+        let mut lockups: Vec<LockupInfo> = vec![];
+        for (index, lockup) in self.lockups.iter().enumerate() {
+            if lockup_indices.contains(&index) {
+                self.locked_stake_amount = self.locked_stake_amount.checked_sub(lockup.amount)?;
+                taken.push(lockup.clone());
+            } else {
+                lockups.push(lockup.clone());
+            }
+        }
+
+        self.lockups = lockups;
+        Ok(taken)
+    }
+
+    /// ## Description
+    /// Adds existing lockups to staker
+    pub fn append_lockups(&mut self, lockups: Vec<LockupInfo>) -> StdResult<()> {
+        for lockup in lockups {
+            self.locked_stake_amount = self.locked_stake_amount.checked_add(lockup.amount)?;
+            self.lockups.push(lockup);
+        }
+
+        Ok(())
+    }
+
+    /// ## Description
+    /// Moves specified amount of lockup by index to unlocked staked amount
+    pub fn early_unlock(&mut self, lockup_index: usize, amount: Uint128) -> StdResult<()> {
+        let lockup = self
+            .lockups
+            .get_mut(lockup_index)
+            .ok_or_else(|| StdError::generic_err("lockup not found"))?;
+
+        lockup.amount = lockup.amount.checked_sub(amount)?;
+        if lockup.amount.is_zero() {
+            self.lockups.remove(lockup_index);
+        }
+
+        self.locked_stake_amount = self.locked_stake_amount.checked_sub(amount)?;
+        self.unlocked_stake_amount = self.unlocked_stake_amount.checked_add(amount)?;
+
+        Ok(())
+    }
+
+    /// ## Description
+    /// Checks if staker info can be deleted or not
+    pub fn can_be_removed(&self) -> StdResult<bool> {
+        Ok(self.total_staked()?.is_zero()
+            && self.pending_bro_reward.is_zero()
+            && self.pending_bbro_reward.is_zero())
+    }
+}
+
+/// ## Description
+/// This structure describes withdrawal info of staking contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WithdrawalInfo {
+    /// amount to withdraw
+    pub amount: Uint128,
+    /// block at which amount can be claimed
+    pub claimable_at: Expiration,
+}
+
+/// ## Description
+/// This structure describes locked amount used for voting power calculation.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VotingLockup {
+    /// locked amount
+    pub amount: Uint128,
+    /// block at which amount will be unlocked
+    pub unlocked_at_block: u64,
+}
+
+/// ## Description
+/// This structure describes staked balance of staker used for voting power calculation.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VotingBalance {
+    /// amount of unlocked BRO tokens staked by staker
+    pub unlocked_amount: Uint128,
+    /// amounts locked by staker
+    pub lockups: Vec<VotingLockup>,
+}
+
+impl VotingBalance {
+    /// ## Description
+    /// Creates voting balance from staker info
+    pub fn new(info: &StakerInfo, epoch_blocks: u64) -> Self {
+        VotingBalance {
+            unlocked_amount: info.unlocked_stake_amount,
+            lockups: info
+                .lockups
+                .iter()
+                .map(|l| VotingLockup {
+                    amount: l.amount,
+                    unlocked_at_block: l.unlocked_at_block(epoch_blocks),
+                })
+                .collect(),
+        }
+    }
+
+    /// ## Description
+    /// Returns voting power at specified block.
+    /// Every locked amount gets additional weight proportional to its remaining lockup period
+    /// where max lockup period (**weight_blocks**) doubles it
+    pub fn voting_power(&self, at_block: u64, weight_blocks: u64) -> StdResult<Uint128> {
+        let mut voting_power = self.unlocked_amount;
+        for lockup in self.lockups.iter() {
+            let remaining_blocks = lockup.unlocked_at_block.saturating_sub(at_block);
+            voting_power = voting_power.checked_add(lockup.amount)?.checked_add(
+                lockup
+                    .amount
+                    .multiply_ratio(remaining_blocks, weight_blocks),
+            )?;
+        }
+
+        Ok(voting_power)
+    }
+}
+
+/// ## Description
+/// This structure describes total staked balance used for voting power calculation.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct TotalVotingBalance {
+    /// total amount of staked BRO tokens by all stakers
+    pub total_amount: Uint128,
+    /// total amount of BRO tokens which are still locked at checkpoint block
+    pub locked_amount: Uint128,
+    /// sum of locked amounts multiplied by their unlock blocks
+    pub locked_amount_by_unlock_block: Uint128,
+    /// block up to which expired lockups are excluded
+    pub checkpoint_block: u64,
+    /// max lockup period in blocks used as voting weight of locked amounts
+    #[serde(default)]
+    pub weight_blocks: u64,
+}
+
+impl TotalVotingBalance {
+    /// ## Description
+    /// Excludes lockups unlocked after checkpoint block up to specified block
+    pub fn expire_lockups(&mut self, storage: &dyn Storage, current_block: u64) -> StdResult<()> {
+        if current_block <= self.checkpoint_block {
+            return Ok(());
+        }
+
+        let start = Bound::exclusive(U64Key::from(self.checkpoint_block));
+        let end = Bound::inclusive(U64Key::from(current_block));
+        let unlocks = LOCKUP_UNLOCKS
+            .range(
+                storage,
+                Some(start),
+                Some(end),
+                cosmwasm_std::Order::Ascending,
+            )
+            .map(|item| {
+                let (k, amount) = item?;
+                Ok((u64_from_key(&k)?, amount))
+            })
+            .collect::<StdResult<Vec<(u64, Uint128)>>>()?;
+
+        for (unlocked_at_block, amount) in unlocks {
+            self.locked_amount = self.locked_amount.checked_sub(amount)?;
+            self.locked_amount_by_unlock_block = self
+                .locked_amount_by_unlock_block
+                .checked_sub(amount.checked_mul(Uint128::from(unlocked_at_block))?)?;
+        }
+
+        self.checkpoint_block = current_block;
+        Ok(())
+    }
+
+    /// ## Description
+    /// Returns total voting power at specified block.
+    /// Lockups must be expired up to specified block before calculation
+    pub fn voting_power(&self, at_block: u64, weight_blocks: u64) -> StdResult<Uint128> {
+        let remaining_blocks_sum = self
+            .locked_amount_by_unlock_block
+            .checked_sub(self.locked_amount.checked_mul(Uint128::from(at_block))?)?;
+
+        Ok(self
+            .total_amount
+            .checked_add(remaining_blocks_sum.multiply_ratio(1u128, weight_blocks))?)
+    }
+}
+
+/// ## Description
+/// Saves changes of [`Config`] struct in [`CONFIG`] storage
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **config** updated config struct of type [`Config`]
+pub fn store_config(storage: &mut dyn Storage, config: &Config) -> StdResult<()> {
+    CONFIG.save(storage, config)
+}
+
+/// ## Description
+/// Returns config struct of type [`Config`]
 /// ## Params
 /// * **storage** is an object of type [`Storage`]
 pub fn load_config(storage: &dyn Storage) -> StdResult<Config> {
@@ -414,6 +1016,34 @@ pub fn load_state(storage: &dyn Storage) -> StdResult<State> {
     STATE.load(storage)
 }
 
+/// ## Description
+/// Returns state struct of type [`State`] with rewards streamed up to specified block
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **current_block** is a field of type [`u64`]
+pub fn load_state_at(storage: &dyn Storage, current_block: u64) -> StdResult<State> {
+    let mut state = STATE.load(storage)?;
+    state.update_reward_index(current_block, load_total_working_balance(storage)?)?;
+
+    Ok(state)
+}
+
+/// ## Description
+/// Streams rewards up to specified block into global reward index,
+/// saves and returns updated state struct of type [`State`].
+/// Must be called before any change of stakers reward weight
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **current_block** is a field of type [`u64`]
+pub fn sync_state(storage: &mut dyn Storage, current_block: u64) -> StdResult<State> {
+    let state = load_state_at(storage, current_block)?;
+    STATE.save(storage, &state)?;
+
+    Ok(state)
+}
+
 /// ## Description
 /// Saves or updates changes in [`STAKERS`] map for specified key of type [`CanonicalAddr`] and value of type [`StakerInfo`]
 /// ## Params
@@ -422,11 +1052,36 @@ pub fn load_state(storage: &dyn Storage) -> StdResult<State> {
 /// * **staker** is an object of type [`CanonicalAddr`]
 ///
 /// * **info** is an object of type [`StakerInfo`]
+///
+/// * **current_block** is a field of type [`u64`]
+///
+/// * **epoch_blocks** is a field of type [`u64`]
 pub fn store_staker_info(
     storage: &mut dyn Storage,
     staker: &CanonicalAddr,
     info: &StakerInfo,
+    current_block: u64,
+    epoch_blocks: u64,
 ) -> StdResult<()> {
+    let prev_info = read_accounted_staker_info(storage, staker)?;
+    update_total_working_balance(storage, prev_info.as_ref(), Some(info))?;
+    if is_staker_in_stats(storage, staker)? {
+        update_stakers_stats(storage, prev_info.as_ref(), Some(info))?;
+    }
+    settle_asset_rewards(storage, staker, prev_info.as_ref())?;
+    update_voting_balances(
+        storage,
+        staker,
+        Some(VotingBalance::new(info, epoch_blocks)),
+        current_block,
+    )?;
+    let config = load_config(storage)?;
+    update_voting_weight_blocks(
+        storage,
+        config.lockup_config.voting_weight_blocks(epoch_blocks),
+        current_block,
+    )?;
+
     STAKERS.save(storage, staker.as_slice(), info)
 }
 
@@ -443,10 +1098,10 @@ pub fn read_staker_info(
     staker: &CanonicalAddr,
     current_block: u64,
 ) -> StdResult<StakerInfo> {
-    let res: Option<StakerInfo> = STAKERS.may_load(storage, staker.as_slice())?;
+    let res = read_staker_info_v120(storage, staker)?;
 
     match res {
-        Some(info) => Ok(info),
+        Some(info) => convert_staker_info(storage, info, current_block),
         None => Ok(StakerInfo {
             reward_index: Decimal::zero(),
             unlocked_stake_amount: Uint128::zero(),
@@ -455,87 +1110,981 @@ pub fn read_staker_info(
             pending_bbro_reward: Uint128::zero(),
             last_balance_update: current_block,
             lockups: vec![],
+            auto_compound: false,
+            boost_multiplier: None,
         }),
     }
 }
 
 /// ## Description
-/// Removes staker info object of type [`StakerInfo`] by specified key of type [`CanonicalAddr`] from map [`STAKERS`]
-/// ## Params
-/// * **storage** is an object of type [`Storage`]
-///
-/// * **staker** is an object of type [`CanonicalAddr`]
-pub fn remove_staker_info(storage: &mut dyn Storage, staker: &CanonicalAddr) {
-    STAKERS.remove(storage, staker.as_slice())
+/// Converts stored staker info to current struct.
+/// Deprecated lockups are left only in stakers which are not migrated yet
+fn convert_staker_info(
+    storage: &dyn Storage,
+    info: StakerInfoV120,
+    current_block: u64,
+) -> StdResult<StakerInfo> {
+    if info.deprecated_lockups_count() == 0 {
+        return Ok(info.into_accounted_staker_info());
+    }
+
+    let config = load_config(storage)?;
+    info.into_staker_info(current_block, config.prev_epoch_blocks)
 }
 
 /// ## Description
-/// Saves or updates changes in [`WITHDRAWALS`] map for specified key of type [`CanonicalAddr`] and value of type [`Vec<WithdrawalInfo>`]
+/// Returns stored staker info as it is accounted in stakers stats
+fn read_accounted_staker_info(
+    storage: &dyn Storage,
+    staker: &CanonicalAddr,
+) -> StdResult<Option<StakerInfo>> {
+    Ok(read_staker_info_v120(storage, staker)?.map(|info| info.into_accounted_staker_info()))
+}
+
+/// ## Description
+/// Saves staker info converted by lockups migration batch and includes staker into
+/// active stakers count and lockup stats
 /// ## Params
 /// * **storage** is an object of type [`Storage`]
 ///
 /// * **staker** is an object of type [`CanonicalAddr`]
 ///
-/// * **claims** is an [`Vec`] of type [`WithdrawalInfo`]
-#[allow(clippy::ptr_arg)]
-pub fn store_withdrawals(
+/// * **info** is an object of type [`StakerInfo`]
+///
+/// * **current_block** is a field of type [`u64`]
+///
+/// * **epoch_blocks** is a field of type [`u64`]
+pub fn store_migrated_staker_info(
     storage: &mut dyn Storage,
     staker: &CanonicalAddr,
-    claims: &Vec<WithdrawalInfo>,
+    info: &StakerInfo,
+    current_block: u64,
+    epoch_blocks: u64,
 ) -> StdResult<()> {
-    WITHDRAWALS.save(storage, staker.as_slice(), claims)
+    store_staker_info(storage, staker, info, current_block, epoch_blocks)?;
+    update_stakers_stats(storage, None, Some(info))
 }
 
 /// ## Description
-/// Returns stakers withdrawal info object of type [`Vec<WithdrawalInfo>`] by specified key of type [`CanonicalAddr`] from map [`WITHDRAWALS`]
+/// Returns whether staker is already accounted in active stakers count and lockup stats.
+/// Stakers are included into them in order of lockups migration batches
+fn is_staker_in_stats(storage: &dyn Storage, staker: &CanonicalAddr) -> StdResult<bool> {
+    let progress = load_lockups_migration_progress(storage)?;
+    Ok(progress.completed
+        || matches!(progress.last_migrated_staker, Some(last) if staker.as_slice() <= last.as_slice()))
+}
+
+/// ## Description
+/// Removes staker info object of type [`StakerInfo`] by specified key of type [`CanonicalAddr`] from map [`STAKERS`]
 /// ## Params
 /// * **storage** is an object of type [`Storage`]
 ///
 /// * **staker** is an object of type [`CanonicalAddr`]
-pub fn load_withdrawals(
-    storage: &dyn Storage,
+///
+/// * **current_block** is a field of type [`u64`]
+pub fn remove_staker_info(
+    storage: &mut dyn Storage,
     staker: &CanonicalAddr,
-) -> StdResult<Vec<WithdrawalInfo>> {
-    WITHDRAWALS
-        .may_load(storage, staker.as_slice())
-        .map(|res| res.unwrap_or_default())
+    current_block: u64,
+) -> StdResult<()> {
+    let prev_info = read_accounted_staker_info(storage, staker)?;
+    update_total_working_balance(storage, prev_info.as_ref(), None)?;
+    if is_staker_in_stats(storage, staker)? {
+        update_stakers_stats(storage, prev_info.as_ref(), None)?;
+    }
+    settle_asset_rewards(storage, staker, prev_info.as_ref())?;
+    update_voting_balances(storage, staker, None, current_block)?;
+
+    STAKERS.remove(storage, staker.as_slice());
+    Ok(())
 }
 
-const MAX_LIMIT: u32 = 100;
-const DEFAULT_LIMIT: u32 = 10;
-pub fn read_stakers_with_deprecated_lockups(
+/// ## Description
+/// Returns the empty vector if does not found data to read, otherwise returns the vector that
+/// contains the objects of type [`StakerInfo`]
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **start_after** is an [`Option`] field of type [`CanonicalAddr`]. Sets the index to start reading
+///
+/// * **limit** is an [`Option`] field of type [`u32`]. Sets the limit to reading
+///
+/// * **order_by** is an [`Option`] field of type [`OrderBy`]
+///
+/// * **current_block** is a field of type [`u64`]
+pub fn read_stakers(
     storage: &dyn Storage,
-    api: &dyn Api,
-    skip: u32,
+    start_after: Option<CanonicalAddr>,
     limit: Option<u32>,
-) -> StdResult<Vec<String>> {
-    let skip = skip as usize;
+    order_by: Option<OrderBy>,
+    current_block: u64,
+) -> StdResult<Vec<(CanonicalAddr, StakerInfo)>> {
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let (start, end, order_by) = match order_by {
+        Some(OrderBy::Asc) => (
+            calc_range_start_addr(start_after).map(Bound::exclusive),
+            None,
+            OrderBy::Asc,
+        ),
+        _ => (
+            None,
+            calc_range_end_addr(start_after).map(Bound::exclusive),
+            OrderBy::Desc,
+        ),
+    };
 
-    STAKERS
-        .range(storage, None, None, cosmwasm_std::Order::Ascending)
-        .filter(|item| match item {
-            Ok(staker) => {
-                let (_, info) = staker;
-                if info.lockups.is_empty() {
-                    return false;
-                }
+    read_stakers_v120(storage, start, end, order_by.into(), Some(limit))?
+        .into_iter()
+        .map(|(staker, info)| Ok((staker, convert_staker_info(storage, info, current_block)?)))
+        .collect()
+}
 
-                for lockup in info.lockups.iter() {
-                    if lockup.locked_at_block.is_none() {
-                        return true;
-                    }
-                }
+fn calc_range_start_addr(start_after: Option<CanonicalAddr>) -> Option<Vec<u8>> {
+    start_after.map(|addr| {
+        let mut v = addr.as_slice().to_vec();
+        v.push(1);
+        v
+    })
+}
+
+fn calc_range_end_addr(start_after: Option<CanonicalAddr>) -> Option<Vec<u8>> {
+    start_after.map(|addr| addr.as_slice().to_vec())
+}
+
+/// ## Description
+/// Updates total working balance according to changes of staker info
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **prev_info** is an [`Option`] of type [`StakerInfo`]. Staker info before update
+///
+/// * **info** is an [`Option`] of type [`StakerInfo`]. Staker info after update
+fn update_total_working_balance(
+    storage: &mut dyn Storage,
+    prev_info: Option<&StakerInfo>,
+    info: Option<&StakerInfo>,
+) -> StdResult<()> {
+    let reward_weight = |info: Option<&StakerInfo>| -> StdResult<Uint128> {
+        match info {
+            Some(info) => info.reward_weight(),
+            None => Ok(Uint128::zero()),
+        }
+    };
+
+    let prev_weight = reward_weight(prev_info)?;
+    let weight = reward_weight(info)?;
+    if prev_weight != weight {
+        let total_working_balance = TOTAL_WORKING_BALANCE
+            .may_load(storage)?
+            .unwrap_or_default()
+            .checked_add(weight)?
+            .checked_sub(prev_weight)?;
+
+        TOTAL_WORKING_BALANCE.save(storage, &total_working_balance)?;
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Updates lockup stats and active stakers count according to changes of staker info
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **prev_info** is an [`Option`] of type [`StakerInfo`]. Staker info before update
+///
+/// * **info** is an [`Option`] of type [`StakerInfo`]. Staker info after update
+fn update_stakers_stats(
+    storage: &mut dyn Storage,
+    prev_info: Option<&StakerInfo>,
+    info: Option<&StakerInfo>,
+) -> StdResult<()> {
+    let is_active = |info: Option<&StakerInfo>| -> StdResult<bool> {
+        match info {
+            Some(info) => Ok(!info.total_staked()?.is_zero()),
+            None => Ok(false),
+        }
+    };
+
+    let was_active = is_active(prev_info)?;
+    let now_active = is_active(info)?;
+    if was_active != now_active {
+        let active_stakers = ACTIVE_STAKERS_COUNT.may_load(storage)?.unwrap_or_default();
+        let active_stakers = if now_active {
+            active_stakers + 1
+        } else {
+            active_stakers.saturating_sub(1)
+        };
+
+        ACTIVE_STAKERS_COUNT.save(storage, &active_stakers)?;
+    }
+
+    // (added amount, removed amount) per lockup period
+    let mut changes: BTreeMap<u64, (Uint128, Uint128)> = BTreeMap::new();
+    for lockup in prev_info.iter().flat_map(|i| i.lockups.iter()) {
+        let change = changes.entry(lockup.epochs_locked).or_default();
+        change.1 = change.1.checked_add(lockup.amount)?;
+    }
+
+    for lockup in info.iter().flat_map(|i| i.lockups.iter()) {
+        let change = changes.entry(lockup.epochs_locked).or_default();
+        change.0 = change.0.checked_add(lockup.amount)?;
+    }
+
+    for (epochs_locked, (added, removed)) in changes {
+        if added == removed {
+            continue;
+        }
 
-                false
+        let key = U64Key::from(epochs_locked);
+        let locked_amount = LOCKUP_STATS
+            .may_load(storage, key.clone())?
+            .unwrap_or_default()
+            .checked_add(added)?
+            .checked_sub(removed)?;
+
+        if locked_amount.is_zero() {
+            LOCKUP_STATS.remove(storage, key);
+        } else {
+            LOCKUP_STATS.save(storage, key, &locked_amount)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Snapshots staker voting balance and updates total voting balance and lockups unlock schedule
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **staker** is an object of type [`CanonicalAddr`]
+///
+/// * **balance** is an [`Option`] of type [`VotingBalance`]
+///
+/// * **current_block** is a field of type [`u64`]
+fn update_voting_balances(
+    storage: &mut dyn Storage,
+    staker: &CanonicalAddr,
+    balance: Option<VotingBalance>,
+    current_block: u64,
+) -> StdResult<()> {
+    let prev_balance = VOTING_BALANCES.may_load(storage, staker.as_slice())?;
+    if prev_balance == balance {
+        return Ok(());
+    }
+
+    let mut total = TOTAL_VOTING_BALANCE
+        .may_load(storage, TOTAL_VOTING_BALANCE_KEY)?
+        .unwrap_or_default();
+    total.expire_lockups(storage, current_block)?;
+
+    let total_amount = |balance: Option<&VotingBalance>| -> StdResult<Uint128> {
+        let mut amount = Uint128::zero();
+        for b in balance.iter() {
+            amount = amount.checked_add(b.unlocked_amount)?;
+            for lockup in b.lockups.iter() {
+                amount = amount.checked_add(lockup.amount)?;
             }
-            Err(_) => false,
-        })
-        .map(|item| {
-            let (addr, _) = item?;
-            Ok(api.addr_humanize(&addr.into())?.to_string())
-        })
-        .skip(skip)
-        .take(limit)
+        }
+
+        Ok(amount)
+    };
+
+    total.total_amount = total
+        .total_amount
+        .checked_add(total_amount(balance.as_ref())?)?
+        .checked_sub(total_amount(prev_balance.as_ref())?)?;
+
+    // (added amount, removed amount) per unlock block, expired lockups are already excluded
+    let mut changes: BTreeMap<u64, (Uint128, Uint128)> = BTreeMap::new();
+    for lockup in prev_balance.iter().flat_map(|b| b.lockups.iter()) {
+        if lockup.unlocked_at_block > current_block {
+            let change = changes.entry(lockup.unlocked_at_block).or_default();
+            change.1 = change.1.checked_add(lockup.amount)?;
+        }
+    }
+
+    for lockup in balance.iter().flat_map(|b| b.lockups.iter()) {
+        if lockup.unlocked_at_block > current_block {
+            let change = changes.entry(lockup.unlocked_at_block).or_default();
+            change.0 = change.0.checked_add(lockup.amount)?;
+        }
+    }
+
+    for (unlocked_at_block, (added, removed)) in changes {
+        if added == removed {
+            continue;
+        }
+
+        let block = Uint128::from(unlocked_at_block);
+        total.locked_amount = total
+            .locked_amount
+            .checked_add(added)?
+            .checked_sub(removed)?;
+        total.locked_amount_by_unlock_block = total
+            .locked_amount_by_unlock_block
+            .checked_add(added.checked_mul(block)?)?
+            .checked_sub(removed.checked_mul(block)?)?;
+
+        let key = U64Key::from(unlocked_at_block);
+        let unlock_amount = LOCKUP_UNLOCKS
+            .may_load(storage, key.clone())?
+            .unwrap_or_default()
+            .checked_add(added)?
+            .checked_sub(removed)?;
+
+        if unlock_amount.is_zero() {
+            LOCKUP_UNLOCKS.remove(storage, key);
+        } else {
+            LOCKUP_UNLOCKS.save(storage, key, &unlock_amount)?;
+        }
+    }
+
+    TOTAL_VOTING_BALANCE.save(storage, TOTAL_VOTING_BALANCE_KEY, &total, current_block)?;
+    match balance {
+        Some(balance) => VOTING_BALANCES.save(storage, staker.as_slice(), &balance, current_block),
+        None => VOTING_BALANCES.remove(storage, staker.as_slice(), current_block),
+    }
+}
+
+/// ## Description
+/// Snapshots voting weight of locked amounts with total voting balance if it is changed,
+/// so voting power at past blocks is not affected by lockup config or epoch length changes
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **weight_blocks** is a field of type [`u64`]
+///
+/// * **current_block** is a field of type [`u64`]
+pub fn update_voting_weight_blocks(
+    storage: &mut dyn Storage,
+    weight_blocks: u64,
+    current_block: u64,
+) -> StdResult<()> {
+    let mut total = TOTAL_VOTING_BALANCE
+        .may_load(storage, TOTAL_VOTING_BALANCE_KEY)?
+        .unwrap_or_default();
+    if total.weight_blocks == weight_blocks {
+        return Ok(());
+    }
+
+    total.weight_blocks = weight_blocks;
+    TOTAL_VOTING_BALANCE.save(storage, TOTAL_VOTING_BALANCE_KEY, &total, current_block)
+}
+
+/// ## Description
+/// Returns voting weight of locked amounts at the beginning of specified block
+/// or current one if block is not specified. Returns zero if it was never snapshotted
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **at_block** is an [`Option`] field of type [`u64`]
+pub fn read_voting_weight_blocks(storage: &dyn Storage, at_block: Option<u64>) -> StdResult<u64> {
+    let total = match at_block {
+        Some(at_block) => {
+            TOTAL_VOTING_BALANCE.may_load_at_height(storage, TOTAL_VOTING_BALANCE_KEY, at_block)?
+        }
+        None => TOTAL_VOTING_BALANCE.may_load(storage, TOTAL_VOTING_BALANCE_KEY)?,
+    };
+
+    Ok(total.map(|t| t.weight_blocks).unwrap_or_default())
+}
+
+/// ## Description
+/// Returns voting balance of staker at the beginning of specified block
+/// or current voting balance if block is not specified
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **staker** is an object of type [`CanonicalAddr`]
+///
+/// * **at_block** is an [`Option`] field of type [`u64`]
+pub fn read_voting_balance(
+    storage: &dyn Storage,
+    staker: &CanonicalAddr,
+    at_block: Option<u64>,
+) -> StdResult<Option<VotingBalance>> {
+    match at_block {
+        Some(at_block) => VOTING_BALANCES.may_load_at_height(storage, staker.as_slice(), at_block),
+        None => VOTING_BALANCES.may_load(storage, staker.as_slice()),
+    }
+}
+
+/// ## Description
+/// Returns total voting balance of type [`TotalVotingBalance`] with lockups expired up to specified block.
+/// Balance is taken at the beginning of **at_block** or current one if block is not specified
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **at_block** is an [`Option`] field of type [`u64`]
+///
+/// * **current_block** is a field of type [`u64`]
+pub fn read_total_voting_balance(
+    storage: &dyn Storage,
+    at_block: Option<u64>,
+    current_block: u64,
+) -> StdResult<TotalVotingBalance> {
+    let total = match at_block {
+        Some(at_block) => {
+            TOTAL_VOTING_BALANCE.may_load_at_height(storage, TOTAL_VOTING_BALANCE_KEY, at_block)?
+        }
+        None => TOTAL_VOTING_BALANCE.may_load(storage, TOTAL_VOTING_BALANCE_KEY)?,
+    };
+
+    let mut total = total.unwrap_or_default();
+    total.expire_lockups(storage, at_block.unwrap_or(current_block))?;
+
+    Ok(total)
+}
+
+/// ## Description
+/// Returns amount of stakers with non-zero staked amount
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+pub fn load_active_stakers_count(storage: &dyn Storage) -> StdResult<u64> {
+    Ok(ACTIVE_STAKERS_COUNT.may_load(storage)?.unwrap_or_default())
+}
+
+/// ## Description
+/// Returns sum of boosted reward weights of all stakers
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+pub fn load_total_working_balance(storage: &dyn Storage) -> StdResult<Uint128> {
+    Ok(TOTAL_WORKING_BALANCE.may_load(storage)?.unwrap_or_default())
+}
+
+/// ## Description
+/// Returns total locked amounts grouped by lockup period in epochs
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+pub fn read_lockup_stats(storage: &dyn Storage) -> StdResult<Vec<(u64, Uint128)>> {
+    LOCKUP_STATS
+        .range(storage, None, None, cosmwasm_std::Order::Ascending)
+        .map(|item| {
+            let (k, locked_amount) = item?;
+            Ok((u64_from_key(&k)?, locked_amount))
+        })
+        .collect()
+}
+
+/// ## Description
+/// Resets stakers stats after migration from version without them.
+/// Working balance of not boosted stakers equals to their stake amount, so it is set at once,
+/// while active stakers count and lockup stats are accumulated by lockups migration batches
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **total_stake_amount** is an object of type [`Uint128`]
+pub fn reset_stakers_stats(
+    storage: &mut dyn Storage,
+    total_stake_amount: Uint128,
+) -> StdResult<()> {
+    for (epochs_locked, _) in read_lockup_stats(storage)? {
+        LOCKUP_STATS.remove(storage, U64Key::from(epochs_locked));
+    }
+    ACTIVE_STAKERS_COUNT.save(storage, &0)?;
+    TOTAL_WORKING_BALANCE.save(storage, &total_stake_amount)?;
+
+    Ok(())
+}
+
+/// ## Description
+/// Saves or updates changes in [`WITHDRAWALS`] map for specified key of type [`CanonicalAddr`] and value of type [`Vec<WithdrawalInfo>`]
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **staker** is an object of type [`CanonicalAddr`]
+///
+/// * **claims** is an [`Vec`] of type [`WithdrawalInfo`]
+#[allow(clippy::ptr_arg)]
+pub fn store_withdrawals(
+    storage: &mut dyn Storage,
+    staker: &CanonicalAddr,
+    claims: &Vec<WithdrawalInfo>,
+) -> StdResult<()> {
+    WITHDRAWALS.save(storage, staker.as_slice(), claims)
+}
+
+/// ## Description
+/// Returns stakers withdrawal info object of type [`Vec<WithdrawalInfo>`] by specified key of type [`CanonicalAddr`] from map [`WITHDRAWALS`]
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **staker** is an object of type [`CanonicalAddr`]
+pub fn load_withdrawals(
+    storage: &dyn Storage,
+    staker: &CanonicalAddr,
+) -> StdResult<Vec<WithdrawalInfo>> {
+    WITHDRAWALS
+        .may_load(storage, staker.as_slice())
+        .map(|res| res.unwrap_or_default())
+}
+
+/// ## Description
+/// Saves or removes reward recipient in [`REWARD_RECIPIENTS`] map for specified staker
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **staker** is an object of type [`CanonicalAddr`]
+///
+/// * **recipient** is an [`Option`] field of type [`CanonicalAddr`]
+pub fn store_reward_recipient(
+    storage: &mut dyn Storage,
+    staker: &CanonicalAddr,
+    recipient: Option<&CanonicalAddr>,
+) -> StdResult<()> {
+    match recipient {
+        Some(recipient) => REWARD_RECIPIENTS.save(storage, staker.as_slice(), recipient),
+        None => {
+            REWARD_RECIPIENTS.remove(storage, staker.as_slice());
+            Ok(())
+        }
+    }
+}
+
+/// ## Description
+/// Returns reward recipient of specified staker from map [`REWARD_RECIPIENTS`].
+/// If recipient is not set returns staker address
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **staker** is an object of type [`CanonicalAddr`]
+pub fn read_reward_recipient(
+    storage: &dyn Storage,
+    staker: &CanonicalAddr,
+) -> StdResult<CanonicalAddr> {
+    Ok(REWARD_RECIPIENTS
+        .may_load(storage, staker.as_slice())?
+        .unwrap_or_else(|| staker.clone()))
+}
+
+/// ## Description
+/// Saves claim operator permission in [`CLAIM_OPERATORS`] map
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **staker** is an object of type [`CanonicalAddr`]
+///
+/// * **operator** is an object of type [`CanonicalAddr`]
+///
+/// * **expires** is an object of type [`Expiration`]
+pub fn store_claim_operator(
+    storage: &mut dyn Storage,
+    staker: &CanonicalAddr,
+    operator: &CanonicalAddr,
+    expires: &Expiration,
+) -> StdResult<()> {
+    CLAIM_OPERATORS.save(storage, (staker.as_slice(), operator.as_slice()), expires)
+}
+
+/// ## Description
+/// Removes claim operator permission from [`CLAIM_OPERATORS`] map
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **staker** is an object of type [`CanonicalAddr`]
+///
+/// * **operator** is an object of type [`CanonicalAddr`]
+pub fn remove_claim_operator(
+    storage: &mut dyn Storage,
+    staker: &CanonicalAddr,
+    operator: &CanonicalAddr,
+) {
+    CLAIM_OPERATORS.remove(storage, (staker.as_slice(), operator.as_slice()))
+}
+
+/// ## Description
+/// Returns claim operator permission expiration from [`CLAIM_OPERATORS`] map if it exists
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **staker** is an object of type [`CanonicalAddr`]
+///
+/// * **operator** is an object of type [`CanonicalAddr`]
+pub fn read_claim_operator(
+    storage: &dyn Storage,
+    staker: &CanonicalAddr,
+    operator: &CanonicalAddr,
+) -> StdResult<Option<Expiration>> {
+    CLAIM_OPERATORS.may_load(storage, (staker.as_slice(), operator.as_slice()))
+}
+
+/// ## Description
+/// Returns all claim operators of specified staker with their permissions expiration
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **staker** is an object of type [`CanonicalAddr`]
+pub fn read_claim_operators(
+    storage: &dyn Storage,
+    staker: &CanonicalAddr,
+) -> StdResult<Vec<(CanonicalAddr, Expiration)>> {
+    CLAIM_OPERATORS
+        .prefix(staker.as_slice())
+        .range(storage, None, None, cosmwasm_std::Order::Ascending)
+        .map(|item| {
+            let (k, expires) = item?;
+            Ok((CanonicalAddr::from(k), expires))
+        })
+        .collect()
+}
+
+/// ## Description
+/// Saves or updates reward asset in [`REWARD_ASSETS`] map
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **reward_asset** is an object of type [`RewardAssetInfo`]
+pub fn store_reward_asset(
+    storage: &mut dyn Storage,
+    reward_asset: &RewardAssetInfo,
+) -> StdResult<()> {
+    REWARD_ASSETS.save(storage, reward_asset.asset_info.as_bytes(), reward_asset)
+}
+
+/// ## Description
+/// Returns reward asset by specified asset info if it is registered
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **asset_info** is an object of type [`AssetInfo`]
+pub fn read_reward_asset(
+    storage: &dyn Storage,
+    asset_info: &AssetInfo,
+) -> StdResult<Option<RewardAssetInfo>> {
+    REWARD_ASSETS.may_load(storage, asset_info.as_bytes())
+}
+
+/// ## Description
+/// Returns all registered reward assets
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+pub fn read_reward_assets(storage: &dyn Storage) -> StdResult<Vec<RewardAssetInfo>> {
+    REWARD_ASSETS
+        .range(storage, None, None, cosmwasm_std::Order::Ascending)
+        .map(|item| item.map(|(_, reward_asset)| reward_asset))
+        .collect()
+}
+
+/// ## Description
+/// Computes rewards of all reward assets for staked amount before update
+/// and adds them to staker's pending rewards
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **staker** is an object of type [`CanonicalAddr`]
+///
+/// * **prev_info** is an [`Option`] of type [`StakerInfo`]. Staker info before update
+fn settle_asset_rewards(
+    storage: &mut dyn Storage,
+    staker: &CanonicalAddr,
+    prev_info: Option<&StakerInfo>,
+) -> StdResult<()> {
+    let stake_amount = match prev_info {
+        Some(info) => info.total_staked()?,
+        None => Uint128::zero(),
+    };
+
+    for reward_asset in read_reward_assets(storage)? {
+        let key = (staker.as_slice(), reward_asset.asset_info.as_bytes());
+        let mut staker_reward = read_staker_asset_reward(storage, staker, &reward_asset)?;
+        if staker_reward.reward_index == reward_asset.global_reward_index {
+            continue;
+        }
+
+        staker_reward.compute_reward(&reward_asset, stake_amount)?;
+        STAKER_ASSET_REWARDS.save(storage, key, &staker_reward)?;
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Returns staker reward of specified reward asset
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **staker** is an object of type [`CanonicalAddr`]
+///
+/// * **reward_asset** is an object of type [`RewardAssetInfo`]
+pub fn read_staker_asset_reward(
+    storage: &dyn Storage,
+    staker: &CanonicalAddr,
+    reward_asset: &RewardAssetInfo,
+) -> StdResult<StakerAssetReward> {
+    Ok(STAKER_ASSET_REWARDS
+        .may_load(
+            storage,
+            (staker.as_slice(), reward_asset.asset_info.as_bytes()),
+        )?
+        .unwrap_or(StakerAssetReward {
+            reward_index: Decimal::zero(),
+            pending_reward: Uint128::zero(),
+        }))
+}
+
+/// ## Description
+/// Resets settled pending rewards of all reward assets for staker
+/// and returns non-zero claimed amounts
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **staker** is an object of type [`CanonicalAddr`]
+pub fn take_asset_rewards(
+    storage: &mut dyn Storage,
+    staker: &CanonicalAddr,
+) -> StdResult<Vec<(AssetInfo, Uint128)>> {
+    let mut rewards: Vec<(AssetInfo, Uint128)> = vec![];
+    for reward_asset in read_reward_assets(storage)? {
+        let key = (staker.as_slice(), reward_asset.asset_info.as_bytes());
+        let mut staker_reward = match STAKER_ASSET_REWARDS.may_load(storage, key)? {
+            Some(staker_reward) if !staker_reward.pending_reward.is_zero() => staker_reward,
+            _ => continue,
+        };
+
+        rewards.push((
+            reward_asset.asset_info.clone(),
+            staker_reward.pending_reward,
+        ));
+        staker_reward.pending_reward = Uint128::zero();
+        STAKER_ASSET_REWARDS.save(storage, key, &staker_reward)?;
+    }
+
+    Ok(rewards)
+}
+
+/// ## Description
+/// Adds specified amounts to pending rewards of reward assets for staker.
+/// Staker rewards must be settled before
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **staker** is an object of type [`CanonicalAddr`]
+///
+/// * **rewards** is a [`Vec`] of type ([`AssetInfo`], [`Uint128`])
+pub fn add_asset_rewards(
+    storage: &mut dyn Storage,
+    staker: &CanonicalAddr,
+    rewards: &[(AssetInfo, Uint128)],
+) -> StdResult<()> {
+    for (asset_info, amount) in rewards {
+        let reward_asset = match read_reward_asset(storage, asset_info)? {
+            Some(reward_asset) => reward_asset,
+            None => continue,
+        };
+
+        let mut staker_reward = read_staker_asset_reward(storage, staker, &reward_asset)?;
+        staker_reward.pending_reward = staker_reward.pending_reward.checked_add(*amount)?;
+        STAKER_ASSET_REWARDS.save(
+            storage,
+            (staker.as_slice(), asset_info.as_bytes()),
+            &staker_reward,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// ## Description
+/// Adds distributed amount to the snapshot of specified block in [`REWARD_SNAPSHOTS`] map
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **distributed_at_block** is a field of type [`u64`]
+///
+/// * **state** is an object of type [`State`]. State after distribution
+///
+/// * **distributed_amount** is an object of type [`Uint128`]
+pub fn store_reward_snapshot(
+    storage: &mut dyn Storage,
+    distributed_at_block: u64,
+    state: &State,
+    distributed_amount: Uint128,
+) -> StdResult<()> {
+    REWARD_SNAPSHOTS.update(
+        storage,
+        U64Key::from(distributed_at_block),
+        |snapshot| -> StdResult<RewardSnapshot> {
+            let prev_distributed_amount =
+                snapshot.map(|s| s.distributed_amount).unwrap_or_default();
+
+            Ok(RewardSnapshot {
+                global_reward_index: state.global_reward_index,
+                total_stake_amount: state.total_stake_amount,
+                distributed_amount: prev_distributed_amount.checked_add(distributed_amount)?,
+            })
+        },
+    )?;
+
+    Ok(())
+}
+
+/// ## Description
+/// Returns the empty vector if does not found data to read, otherwise returns the vector that
+/// contains distribution blocks with objects of type [`RewardSnapshot`] in ascending order
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **start_after** is an [`Option`] field of type [`u64`]. Sets the distribution block to start reading after
+///
+/// * **limit** is an [`Option`] field of type [`u32`]. Sets the limit to reading
+pub fn read_reward_snapshots(
+    storage: &dyn Storage,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, RewardSnapshot)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|block| Bound::exclusive(U64Key::from(block)));
+
+    REWARD_SNAPSHOTS
+        .range(storage, start, None, cosmwasm_std::Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, snapshot) = item?;
+            Ok((u64_from_key(&k)?, snapshot))
+        })
+        .collect()
+}
+
+/// ## Description
+/// Returns all reward snapshots with distribution block higher than specified one
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **start_after** is a field of type [`u64`]
+pub fn read_reward_snapshots_since(
+    storage: &dyn Storage,
+    start_after: u64,
+) -> StdResult<Vec<RewardSnapshot>> {
+    REWARD_SNAPSHOTS
+        .range(
+            storage,
+            Some(Bound::exclusive(U64Key::from(start_after))),
+            None,
+            cosmwasm_std::Order::Ascending,
+        )
+        .map(|item| item.map(|(_, snapshot)| snapshot))
+        .collect()
+}
+
+/// ## Description
+/// Appends entry to staker history and removes the oldest entries exceeding history limit.
+/// Nothing is recorded if history limit is zero
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **staker** is an object of type [`CanonicalAddr`]
+///
+/// * **entry** is an object of type [`StakerHistoryEntry`]
+///
+/// * **history_limit** is a field of type [`u64`]
+pub fn store_staker_history_entry(
+    storage: &mut dyn Storage,
+    staker: &CanonicalAddr,
+    entry: &StakerHistoryEntry,
+    history_limit: u64,
+) -> StdResult<()> {
+    if history_limit == 0 {
+        return Ok(());
+    }
+
+    let mut ids = STAKER_HISTORY_IDS
+        .may_load(storage, staker.as_slice())?
+        .unwrap_or_default();
+
+    STAKER_HISTORY.save(
+        storage,
+        (staker.as_slice(), U64Key::from(ids.next_id)),
+        entry,
+    )?;
+    ids.next_id += 1;
+
+    while ids.next_id - ids.first_id > history_limit {
+        STAKER_HISTORY.remove(storage, (staker.as_slice(), U64Key::from(ids.first_id)));
+        ids.first_id += 1;
+    }
+
+    STAKER_HISTORY_IDS.save(storage, staker.as_slice(), &ids)
+}
+
+/// ## Description
+/// Returns the empty vector if does not found data to read, otherwise returns the vector that
+/// contains entry ids with objects of type [`StakerHistoryEntry`] in ascending order.
+/// Only the latest entries within history limit are returned
+/// ## Params
+/// * **storage** is an object of type [`Storage`]
+///
+/// * **staker** is an object of type [`CanonicalAddr`]
+///
+/// * **history_limit** is a field of type [`u64`]
+///
+/// * **start_after** is an [`Option`] field of type [`u64`]. Sets the entry id to start reading after
+///
+/// * **limit** is an [`Option`] field of type [`u32`]. Sets the limit to reading
+pub fn read_staker_history(
+    storage: &dyn Storage,
+    staker: &CanonicalAddr,
+    history_limit: u64,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, StakerHistoryEntry)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    let ids = STAKER_HISTORY_IDS
+        .may_load(storage, staker.as_slice())?
+        .unwrap_or_default();
+    // entries beyond current limit are kept until next entry is recorded
+    let mut start_id = ids.next_id.saturating_sub(history_limit);
+    if let Some(start_after) = start_after {
+        start_id = std::cmp::max(start_id, start_after.saturating_add(1));
+    }
+
+    STAKER_HISTORY
+        .prefix(staker.as_slice())
+        .range(
+            storage,
+            Some(Bound::inclusive(U64Key::from(start_id))),
+            None,
+            cosmwasm_std::Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| {
+            let (k, entry) = item?;
+            Ok((u64_from_key(&k)?, entry))
+        })
+        .collect()
+}
+
+fn u64_from_key(key: &[u8]) -> StdResult<u64> {
+    Ok(u64::from_be_bytes(
+        key.try_into()
+            .map_err(|_| StdError::generic_err("invalid u64 key"))?,
+    ))
+}
+
+const MAX_LIMIT: u32 = 100;
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_STAKER_HISTORY_LIMIT: u64 = 100;
+pub fn read_stakers_with_deprecated_lockups(
+    storage: &dyn Storage,
+    api: &dyn Api,
+    skip: u32,
+    limit: Option<u32>,
+) -> StdResult<Vec<String>> {
+    let skip = skip as usize;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    range_stakers_v120(storage, None, None, cosmwasm_std::Order::Ascending)
+        .filter(|item| match item {
+            Ok((_, info)) => info.deprecated_lockups_count() != 0,
+            Err(_) => true,
+        })
+        .skip(skip)
+        .take(limit)
+        .map(|item| {
+            let (addr, _) = item?;
+            Ok(api.addr_humanize(&addr)?.to_string())
+        })
         .collect()
 }
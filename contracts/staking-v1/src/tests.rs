@@ -1,22 +1,33 @@
 use crate::contract::{execute, instantiate, query};
 use crate::error::ContractError;
 use crate::math::{decimal_mul_in_256, decimal_sub_in_256, decimal_sum_in_256};
-use crate::mock_querier::mock_dependencies;
+use crate::migration::{LockupInfoV120, StakerInfoV120};
+use crate::mock_querier::{mock_dependencies, MOCK_BBRO_TOKEN_ADDR};
 use crate::state::{LockupInfo, StakerInfo};
 use services::bbro_minter::ExecuteMsg as BbroMintMsg;
+use services::common::OrderBy;
 use services::epoch_manager::EpochInfoResponse;
 use services::ownership_proposal::OwnershipProposalResponse;
 use services::staking::{
-    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, LockupConfigResponse,
-    LockupInfoResponse, QueryMsg, StakeType, StakerInfoResponse, StateResponse,
+    BoostInfoResponse, BoostParams, ClaimOperatorResponse, ClaimPermissionsResponse,
+    ConfigResponse, Cw20HookMsg, EarlyUnlockPenalty, EarlyUnlockPenaltyType, EstimatedAprResponse,
+    ExecuteMsg, InstantUnstake, InstantUnstakeFeeDestination, InstantiateMsg, LockupConfigResponse,
+    LockupInfoResponse, LockupStatsItem, LockupStatsResponse, LockupsMigrationProgressResponse,
+    PendingRewardsResponse, QueryMsg, RewardAssetResponse, RewardAssetsResponse,
+    RewardHistoryResponse, RewardSnapshotResponse, SimulateLockupResponse, SimulateRewardsResponse,
+    StakeType, StakerHistoryEntryResponse, StakerHistoryResponse, StakerInfoResponse,
+    StakersResponse, StateResponse, TotalVotingPowerResponse, UnstakeType, VotingPowerResponse,
     WithdrawalInfoResponse, WithdrawalsResponse,
 };
 
+use astroport::asset::{Asset, AssetInfo};
 use cosmwasm_std::testing::{mock_env, mock_info};
 use cosmwasm_std::{
-    from_binary, to_binary, Attribute, CosmosMsg, Decimal, StdError, SubMsg, Uint128, WasmMsg,
+    coins, from_binary, to_binary, Addr, Attribute, BankMsg, CosmosMsg, Decimal, Deps, Env, Order,
+    StdError, StdResult, Storage, SubMsg, Uint128, WasmMsg,
 };
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, Expiration};
+use cw_storage_plus::Map;
 
 use std::str::FromStr;
 
@@ -39,6 +50,10 @@ fn proper_initialization() {
         base_rate: Decimal::from_str("0.00001").unwrap(),
         linear_growth: Decimal::from_str("0.0005").unwrap(),
         exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
     };
     let info = mock_info("addr0000", &[]);
     let res = instantiate(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
@@ -66,6 +81,10 @@ fn proper_initialization() {
         base_rate: Decimal::from_str("0.0006").unwrap(),
         linear_growth: Decimal::from_str("0.0005").unwrap(),
         exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
     };
     let info = mock_info("addr0000", &[]);
     let res = instantiate(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
@@ -93,6 +112,10 @@ fn proper_initialization() {
         base_rate: Decimal::from_str("0.0001").unwrap(),
         linear_growth: Decimal::from_str("0.0003").unwrap(),
         exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
     };
     let info = mock_info("addr0000", &[]);
     let res = instantiate(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
@@ -120,6 +143,10 @@ fn proper_initialization() {
         base_rate: Decimal::from_str("0.0001").unwrap(),
         linear_growth: Decimal::from_str("0.0016").unwrap(),
         exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
     };
     let info = mock_info("addr0000", &[]);
     let res = instantiate(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
@@ -147,6 +174,10 @@ fn proper_initialization() {
         base_rate: Decimal::from_str("0.0001").unwrap(),
         linear_growth: Decimal::from_str("0.0005").unwrap(),
         exponential_growth: Decimal::from_str("0.0000009").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
     };
     let info = mock_info("addr0000", &[]);
     let res = instantiate(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
@@ -174,6 +205,10 @@ fn proper_initialization() {
         base_rate: Decimal::from_str("0.0001").unwrap(),
         linear_growth: Decimal::from_str("0.0005").unwrap(),
         exponential_growth: Decimal::from_str("0.000016").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
     };
     let info = mock_info("addr0000", &[]);
     let res = instantiate(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
@@ -201,6 +236,10 @@ fn proper_initialization() {
         base_rate: Decimal::from_str("0.0001").unwrap(),
         linear_growth: Decimal::from_str("0.0005").unwrap(),
         exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
     };
     let info = mock_info("addr0000", &[]);
     let res = instantiate(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
@@ -230,6 +269,10 @@ fn proper_initialization() {
         base_rate: Decimal::from_str("0.0001").unwrap(),
         linear_growth: Decimal::from_str("0.0005").unwrap(),
         exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -243,11 +286,13 @@ fn proper_initialization() {
         ConfigResponse {
             owner: "owner".to_string(),
             paused: false,
+            emergency_mode: false,
             bro_token: "bro0000".to_string(),
             rewards_pool_contract: "reward0000".to_string(),
             bbro_minter_contract: "bbrominter0000".to_string(),
             epoch_manager_contract: "epoch0000".to_string(),
             community_bonding_contract: Some("community_bonding0000".to_string()),
+            bond_lock_contracts: vec![],
             unstake_period_blocks: 10,
             min_staking_amount: Uint128::zero(),
             lockup_config: LockupConfigResponse {
@@ -256,7 +301,11 @@ fn proper_initialization() {
                 base_rate: Decimal::from_str("0.0001").unwrap(),
                 linear_growth: Decimal::from_str("0.0005").unwrap(),
                 exponential_growth: Decimal::from_str("0.0000075").unwrap(),
-            }
+                early_unlock_penalty: None,
+            },
+            boost: None,
+            instant_unstake: None,
+            staker_history_limit: 0,
         }
     );
 
@@ -269,6 +318,8 @@ fn proper_initialization() {
             total_stake_amount: Uint128::zero(),
             global_reward_index: Decimal::zero(),
             last_distribution_block: 12345,
+            reward_rate: Decimal::zero(),
+            period_finish: 12345,
         }
     );
 }
@@ -334,6 +385,10 @@ fn test_fractional_rewards() {
         base_rate: Decimal::from_str("0.0001").unwrap(),
         linear_growth: Decimal::from_str("0.0005").unwrap(),
         exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -401,6 +456,9 @@ fn test_fractional_rewards() {
 
     let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
 
+    // distributed reward is streamed over the next epoch
+    env.block.height += 1;
+
     // checking pending rewards through StakerInfoResponse
     assert_eq!(
         from_binary::<StakerInfoResponse>(
@@ -420,9 +478,10 @@ fn test_fractional_rewards() {
             unlocked_stake_amount: Uint128::from(100u128),
             locked_stake_amount: Uint128::zero(),
             pending_bro_reward: Uint128::from(333u128),
-            pending_bbro_reward: Uint128::new(60u128),
+            pending_bbro_reward: Uint128::new(90u128),
             last_balance_update: 12346,
             lockups: vec![],
+            auto_compound: false,
         }
     );
 
@@ -445,9 +504,10 @@ fn test_fractional_rewards() {
             unlocked_stake_amount: Uint128::from(100u128),
             locked_stake_amount: Uint128::zero(),
             pending_bro_reward: Uint128::from(333u128),
-            pending_bbro_reward: Uint128::new(30u128),
+            pending_bbro_reward: Uint128::new(60u128),
             last_balance_update: 12347,
             lockups: vec![],
+            auto_compound: false,
         }
     );
 
@@ -470,9 +530,10 @@ fn test_fractional_rewards() {
             unlocked_stake_amount: Uint128::from(100u128),
             locked_stake_amount: Uint128::zero(),
             pending_bro_reward: Uint128::from(333u128),
-            pending_bbro_reward: Uint128::zero(),
+            pending_bbro_reward: Uint128::new(30u128),
             last_balance_update: 12348,
             lockups: vec![],
+            auto_compound: false,
         }
     );
 
@@ -485,6 +546,8 @@ fn test_fractional_rewards() {
             total_stake_amount: Uint128::from(300u128),
             global_reward_index: Decimal::from_ratio(10u128, 3u128),
             last_distribution_block: 12349,
+            reward_rate: Decimal::from_ratio(1000u128, 1u128),
+            period_finish: 12349,
         }
     );
 
@@ -494,7 +557,6 @@ fn test_fractional_rewards() {
     ////////////////////////////////////////////////////////////////////////////
 
     let info = mock_info("bro0000", &[]);
-    env.block.height += 1;
 
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: "reward0000".to_string(),
@@ -506,6 +568,7 @@ fn test_fractional_rewards() {
     });
 
     let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    env.block.height += 1;
 
     // checking pending rewards through StakerInfoResponse
     assert_eq!(
@@ -527,9 +590,10 @@ fn test_fractional_rewards() {
             unlocked_stake_amount: Uint128::from(100u128),
             locked_stake_amount: Uint128::zero(),
             pending_bro_reward: Uint128::from(999u128),
-            pending_bbro_reward: Uint128::new(90u128),
+            pending_bbro_reward: Uint128::new(120u128),
             last_balance_update: 12346,
             lockups: vec![],
+            auto_compound: false,
         }
     );
 }
@@ -556,6 +620,10 @@ fn test_unlocked_stake_tokens() {
         base_rate: Decimal::from_str("0.0001").unwrap(),
         linear_growth: Decimal::from_str("0.0005").unwrap(),
         exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -600,6 +668,7 @@ fn test_unlocked_stake_tokens() {
             pending_bbro_reward: Uint128::zero(),
             last_balance_update: 12345,
             lockups: vec![],
+            auto_compound: false,
         }
     );
 
@@ -612,6 +681,8 @@ fn test_unlocked_stake_tokens() {
             total_stake_amount: Uint128::zero(),
             global_reward_index: Decimal::zero(),
             last_distribution_block: 12345,
+            reward_rate: Decimal::zero(),
+            period_finish: 12345,
         }
     );
 
@@ -674,6 +745,7 @@ fn test_unlocked_stake_tokens() {
             pending_bbro_reward: Uint128::zero(),
             last_balance_update: env.block.height,
             lockups: vec![],
+            auto_compound: false,
         }
     );
 
@@ -685,7 +757,9 @@ fn test_unlocked_stake_tokens() {
         StateResponse {
             total_stake_amount: Uint128::from(100u128),
             global_reward_index: Decimal::zero(),
-            last_distribution_block: 12345,
+            last_distribution_block: 12346,
+            reward_rate: Decimal::zero(),
+            period_finish: 12345,
         }
     );
 
@@ -728,6 +802,7 @@ fn test_unlocked_stake_tokens() {
             pending_bbro_reward: Uint128::zero(),
             last_balance_update: env.block.height,
             lockups: vec![],
+            auto_compound: false,
         }
     );
 
@@ -739,7 +814,9 @@ fn test_unlocked_stake_tokens() {
         StateResponse {
             total_stake_amount: Uint128::from(200u128),
             global_reward_index: Decimal::zero(),
-            last_distribution_block: 12345,
+            last_distribution_block: 12347,
+            reward_rate: Decimal::zero(),
+            period_finish: 12345,
         }
     );
 
@@ -781,6 +858,9 @@ fn test_unlocked_stake_tokens() {
 
     let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
 
+    // distributed reward is streamed over the next epoch
+    env.block.height += 1;
+
     // checking pending rewards through StakerInfoResponse
     assert_eq!(
         from_binary::<StakerInfoResponse>(
@@ -800,9 +880,10 @@ fn test_unlocked_stake_tokens() {
             unlocked_stake_amount: Uint128::from(100u128),
             locked_stake_amount: Uint128::zero(),
             pending_bro_reward: Uint128::from(500u128),
-            pending_bbro_reward: Uint128::new(60u128),
+            pending_bbro_reward: Uint128::new(90u128),
             last_balance_update: 12346,
             lockups: vec![],
+            auto_compound: false,
         }
     );
 
@@ -814,7 +895,9 @@ fn test_unlocked_stake_tokens() {
         StateResponse {
             total_stake_amount: Uint128::from(200u128),
             global_reward_index: Decimal::from_ratio(5u128, 1u128),
-            last_distribution_block: 12348,
+            last_distribution_block: 12349,
+            reward_rate: Decimal::from_ratio(1000u128, 1u128),
+            period_finish: 12349,
         }
     );
 
@@ -825,14 +908,15 @@ fn test_unlocked_stake_tokens() {
     let info = mock_info("addr0000", &[]);
 
     let msg = ExecuteMsg::ClaimBroRewards {};
-    env.block.height += 1;
 
     let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
-    assert_eq!(res.attributes.len(), 3);
+    assert_eq!(res.attributes.len(), 4);
     assert_eq!(res.attributes[0].key, "action");
     assert_eq!(res.attributes[0].value, "claim_bro_rewards");
     assert_eq!(res.attributes[1].key, "staker");
     assert_eq!(res.attributes[1].value, "addr0000");
+    assert_eq!(res.attributes[3].key, "recipient");
+    assert_eq!(res.attributes[3].value, "addr0000");
     assert_eq!(res.attributes[2].key, "amount");
     assert_eq!(
         Decimal::from_str(&res.attributes[2].value).unwrap(),
@@ -876,6 +960,7 @@ fn test_unlocked_stake_tokens() {
             pending_bbro_reward: Uint128::from(90u128),
             last_balance_update: 12346,
             lockups: vec![],
+            auto_compound: false,
         }
     );
 
@@ -887,7 +972,9 @@ fn test_unlocked_stake_tokens() {
         StateResponse {
             total_stake_amount: Uint128::from(200u128),
             global_reward_index: Decimal::from_ratio(5u128, 1u128),
-            last_distribution_block: 12348,
+            last_distribution_block: 12349,
+            reward_rate: Decimal::from_ratio(1000u128, 1u128),
+            period_finish: 12349,
         }
     );
 
@@ -914,6 +1001,7 @@ fn test_unlocked_stake_tokens() {
 
     let msg = ExecuteMsg::Unstake {
         amount: Uint128::new(150),
+        unstake_type: None,
     };
 
     match execute(deps.as_mut(), env.clone(), info, msg) {
@@ -929,10 +1017,11 @@ fn test_unlocked_stake_tokens() {
 
     let msg = ExecuteMsg::Unstake {
         amount: Uint128::new(50),
+        unstake_type: None,
     };
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
-    assert_eq!(res.attributes.len(), 3);
+    assert_eq!(res.attributes.len(), 6);
     assert_eq!(res.attributes[0].key, "action");
     assert_eq!(res.attributes[0].value, "unstake");
     assert_eq!(res.attributes[1].key, "staker");
@@ -942,6 +1031,12 @@ fn test_unlocked_stake_tokens() {
         Decimal::from_str(&res.attributes[2].value).unwrap(),
         Decimal::from_ratio(50u128, 1u128)
     );
+    assert_eq!(res.attributes[3].key, "unstake_type");
+    assert_eq!(res.attributes[3].value, "delayed");
+    assert_eq!(res.attributes[4].key, "fee_amount");
+    assert_eq!(res.attributes[4].value, "0");
+    assert_eq!(res.attributes[5].key, "withdrawal_amount");
+    assert_eq!(res.attributes[5].value, "50");
 
     assert_eq!(
         from_binary::<StakerInfoResponse>(
@@ -964,6 +1059,7 @@ fn test_unlocked_stake_tokens() {
             pending_bbro_reward: Uint128::new(120u128),
             last_balance_update: 12350,
             lockups: vec![],
+            auto_compound: false,
         }
     );
 
@@ -975,7 +1071,9 @@ fn test_unlocked_stake_tokens() {
         StateResponse {
             total_stake_amount: Uint128::from(150u128),
             global_reward_index: Decimal::from_ratio(5u128, 1u128),
-            last_distribution_block: 12348,
+            last_distribution_block: 12350,
+            reward_rate: Decimal::from_ratio(1000u128, 1u128),
+            period_finish: 12349,
         }
     );
 
@@ -995,7 +1093,7 @@ fn test_unlocked_stake_tokens() {
         WithdrawalsResponse {
             claims: vec![WithdrawalInfoResponse {
                 amount: Uint128::new(50),
-                claimable_at: Expiration::AtHeight(12360)
+                claimable_at: Expiration::AtHeight(12360),
             }]
         }
     );
@@ -1018,6 +1116,9 @@ fn test_unlocked_stake_tokens() {
 
     let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
 
+    // distributed reward is streamed over the next epoch
+    env.block.height += 1;
+
     assert_eq!(
         from_binary::<StakerInfoResponse>(
             &query(
@@ -1036,9 +1137,10 @@ fn test_unlocked_stake_tokens() {
             unlocked_stake_amount: Uint128::from(50u128),
             locked_stake_amount: Uint128::zero(),
             pending_bro_reward: Uint128::from(333u128),
-            pending_bbro_reward: Uint128::new(120u128),
+            pending_bbro_reward: Uint128::new(135u128),
             last_balance_update: 12350,
             lockups: vec![],
+            auto_compound: false,
         }
     );
 
@@ -1060,9 +1162,10 @@ fn test_unlocked_stake_tokens() {
             unlocked_stake_amount: Uint128::from(100u128),
             locked_stake_amount: Uint128::zero(),
             pending_bro_reward: Uint128::from(1166u128),
-            pending_bbro_reward: Uint128::new(90u128),
+            pending_bbro_reward: Uint128::new(120u128),
             last_balance_update: 12347,
             lockups: vec![],
+            auto_compound: false,
         }
     );
 
@@ -1073,7 +1176,6 @@ fn test_unlocked_stake_tokens() {
     let info = mock_info("addr0000", &[]);
 
     let msg = ExecuteMsg::Withdraw {};
-    env.block.height += 1;
 
     match execute(deps.as_mut(), env.clone(), info, msg) {
         Err(ContractError::NothingToClaim {}) => (),
@@ -1107,11 +1209,13 @@ fn test_unlocked_stake_tokens() {
         assert!(false);
     }
 
-    assert_eq!(res.attributes.len(), 3);
+    assert_eq!(res.attributes.len(), 4);
     assert_eq!(res.attributes[0].key, "action");
     assert_eq!(res.attributes[0].value, "withdraw");
     assert_eq!(res.attributes[1].key, "staker");
     assert_eq!(res.attributes[1].value, "addr0000");
+    assert_eq!(res.attributes[3].key, "recipient");
+    assert_eq!(res.attributes[3].value, "addr0000");
     assert_eq!(res.attributes[2].key, "amount");
     assert_eq!(
         Decimal::from_str(&res.attributes[2].value).unwrap(),
@@ -1140,10 +1244,11 @@ fn test_unlocked_stake_tokens() {
 
     let msg = ExecuteMsg::Unstake {
         amount: Uint128::new(100),
+        unstake_type: None,
     };
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
-    assert_eq!(res.attributes.len(), 3);
+    assert_eq!(res.attributes.len(), 6);
     assert_eq!(res.attributes[0].key, "action");
     assert_eq!(res.attributes[0].value, "unstake");
     assert_eq!(res.attributes[1].key, "staker");
@@ -1175,6 +1280,7 @@ fn test_unlocked_stake_tokens() {
             pending_bbro_reward: Uint128::new(450u128),
             last_balance_update: 12362,
             lockups: vec![],
+            auto_compound: false,
         }
     );
 
@@ -1187,7 +1293,9 @@ fn test_unlocked_stake_tokens() {
             total_stake_amount: Uint128::from(50u128),
             global_reward_index: Decimal::from_ratio(5u128, 1u128)
                 + Decimal::from_ratio(100u128, 15u128),
-            last_distribution_block: 12348,
+            last_distribution_block: 12362,
+            reward_rate: Decimal::from_ratio(1000u128, 1u128),
+            period_finish: 12351,
         }
     );
 
@@ -1207,7 +1315,7 @@ fn test_unlocked_stake_tokens() {
         WithdrawalsResponse {
             claims: vec![WithdrawalInfoResponse {
                 amount: Uint128::new(100),
-                claimable_at: Expiration::AtHeight(12372)
+                claimable_at: Expiration::AtHeight(12372),
             }]
         }
     );
@@ -1222,11 +1330,13 @@ fn test_unlocked_stake_tokens() {
     env.block.height += 1;
 
     let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
-    assert_eq!(res.attributes.len(), 3);
+    assert_eq!(res.attributes.len(), 4);
     assert_eq!(res.attributes[0].key, "action");
     assert_eq!(res.attributes[0].value, "claim_bro_rewards");
     assert_eq!(res.attributes[1].key, "staker");
     assert_eq!(res.attributes[1].value, "addr0001");
+    assert_eq!(res.attributes[3].key, "recipient");
+    assert_eq!(res.attributes[3].value, "addr0001");
     assert_eq!(res.attributes[2].key, "amount");
     assert_eq!(
         Decimal::from_str(&res.attributes[2].value).unwrap(),
@@ -1254,6 +1364,7 @@ fn test_unlocked_stake_tokens() {
             pending_bbro_reward: Uint128::from(450u128),
             last_balance_update: 12362,
             lockups: vec![],
+            auto_compound: false,
         }
     );
 }
@@ -1277,6 +1388,10 @@ fn test_locked_stake_tokens() {
         base_rate: Decimal::from_str("0.0001").unwrap(),
         linear_growth: Decimal::from_str("0.0005").unwrap(),
         exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -1337,6 +1452,7 @@ fn test_locked_stake_tokens() {
                 locked_at_block: 12346,
                 epochs_locked: 1,
             }],
+            auto_compound: false,
         },
     );
 
@@ -1393,6 +1509,7 @@ fn test_locked_stake_tokens() {
                 locked_at_block: 12347,
                 epochs_locked: 5,
             }],
+            auto_compound: false,
         },
     );
 
@@ -1404,7 +1521,9 @@ fn test_locked_stake_tokens() {
         StateResponse {
             total_stake_amount: Uint128::from(2_000000u128),
             global_reward_index: Decimal::zero(),
-            last_distribution_block: 12345,
+            last_distribution_block: 12347,
+            reward_rate: Decimal::zero(),
+            period_finish: 12345,
         },
     );
 
@@ -1422,7 +1541,7 @@ fn test_locked_stake_tokens() {
 
     let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
 
-    // check bbro pending reward
+    // check bbro pending reward, distributed bro reward is not streamed yet
     assert_eq!(
         from_binary::<StakerInfoResponse>(
             &query(
@@ -1437,10 +1556,10 @@ fn test_locked_stake_tokens() {
         .unwrap(),
         StakerInfoResponse {
             staker: addr1.clone(),
-            reward_index: Decimal::from_str("0.0005").unwrap(),
+            reward_index: Decimal::zero(),
             unlocked_stake_amount: Uint128::from(1000000u128),
             locked_stake_amount: Uint128::from(1000000u128),
-            pending_bro_reward: Uint128::from(1000u128),
+            pending_bro_reward: Uint128::zero(),
             pending_bbro_reward: Uint128::from(300000u128),
             last_balance_update: 12347,
             lockups: vec![LockupInfoResponse {
@@ -1448,6 +1567,7 @@ fn test_locked_stake_tokens() {
                 locked_at_block: 12347,
                 epochs_locked: 5,
             }],
+            auto_compound: false,
         },
     );
 
@@ -1494,6 +1614,7 @@ fn test_locked_stake_tokens() {
             pending_bbro_reward: Uint128::zero(),
             last_balance_update: 12352,
             lockups: vec![],
+            auto_compound: false,
         },
     );
 
@@ -1587,6 +1708,7 @@ fn test_locked_stake_tokens() {
                 locked_at_block: 12370,
                 epochs_locked: 5,
             }],
+            auto_compound: false,
         },
     );
 
@@ -1612,6 +1734,7 @@ fn test_locked_stake_tokens() {
             pending_bbro_reward: Uint128::from(28800000u128),
             last_balance_update: 12352,
             lockups: vec![],
+            auto_compound: false,
         },
     );
 }
@@ -1635,6 +1758,10 @@ fn community_bond_stake() {
         base_rate: Decimal::from_str("0.0001").unwrap(),
         linear_growth: Decimal::from_str("0.0005").unwrap(),
         exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -1667,6 +1794,10 @@ fn community_bond_stake() {
         base_rate: None,
         linear_growth: None,
         exponential_growth: None,
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: None,
     };
 
     let info = mock_info("owner", &[]);
@@ -1786,6 +1917,7 @@ fn community_bond_stake() {
                 locked_at_block: 12346,
                 epochs_locked: 10,
             }],
+            auto_compound: false,
         },
     );
 
@@ -1797,9 +1929,76 @@ fn community_bond_stake() {
         StateResponse {
             total_stake_amount: Uint128::from(50_000000u128),
             global_reward_index: Decimal::zero(),
-            last_distribution_block: 12345,
+            last_distribution_block: 12346,
+            reward_rate: Decimal::zero(),
+            period_finish: 12345,
         },
     );
+
+    // error: bond lock contract is not whitelisted
+    let bond_lock_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "bonding0000".to_string(),
+        amount: Uint128::from(50_000000u128),
+        msg: to_binary(&Cw20HookMsg::CommunityBondLock {
+            sender: "addr0001".to_string(),
+            epochs_locked: 10,
+        })
+        .unwrap(),
+    });
+    let info = mock_info("bro0000", &[]);
+    match execute(deps.as_mut(), env.clone(), info, bond_lock_msg.clone()) {
+        Err(ContractError::Unauthorized {}) => (),
+        _ => panic!("expecting ContractError::Unauthorized"),
+    }
+
+    // whitelist bond lock contract
+    let msg = ExecuteMsg::UpdateBondLockContracts {
+        bond_lock_contracts: vec!["bonding0000".to_string()],
+    };
+
+    // error: unauthorized
+    let info = mock_info("addr0000", &[]);
+    match execute(deps.as_mut(), env.clone(), info, msg.clone()) {
+        Err(ContractError::Unauthorized {}) => (),
+        _ => panic!("expecting ContractError::Unauthorized"),
+    }
+
+    let info = mock_info("owner", &[]);
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            Attribute::new("action", "update_bond_lock_contracts"),
+            Attribute::new("bond_lock_contracts", "bonding0000"),
+        ]
+    );
+
+    let config = from_binary::<ConfigResponse>(
+        &query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(config.bond_lock_contracts, vec!["bonding0000".to_string()]);
+
+    // both community bonding and whitelisted contracts can lock bond payouts
+    let info = mock_info("bro0000", &[]);
+    let res = execute(deps.as_mut(), env.clone(), info, bond_lock_msg).unwrap();
+    assert_eq!(res.attributes[1], Attribute::new("staker", "addr0001"));
+
+    let staker_info = from_binary::<StakerInfoResponse>(
+        &query(
+            deps.as_ref(),
+            env,
+            QueryMsg::StakerInfo {
+                staker: "addr0001".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        staker_info.locked_stake_amount,
+        Uint128::from(50_000000u128)
+    );
 }
 
 #[test]
@@ -1821,6 +2020,10 @@ fn update_config() {
         base_rate: Decimal::from_str("0.0001").unwrap(),
         linear_growth: Decimal::from_str("0.0005").unwrap(),
         exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -1837,6 +2040,10 @@ fn update_config() {
         linear_growth: None,
         exponential_growth: None,
         community_bonding_contract: None,
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: None,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -1857,6 +2064,10 @@ fn update_config() {
         linear_growth: Some(Decimal::from_str("0.0006").unwrap()),
         exponential_growth: Some(Decimal::from_str("0.0000076").unwrap()),
         community_bonding_contract: Some("new_community_bonding".to_string()),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: None,
     };
 
     let info = mock_info("owner", &[]);
@@ -1908,11 +2119,13 @@ fn update_config() {
         ConfigResponse {
             owner: "owner".to_string(),
             paused: true,
+            emergency_mode: false,
             bro_token: "bro0000".to_string(),
             rewards_pool_contract: "reward0000".to_string(),
             bbro_minter_contract: "bbrominter0000".to_string(),
             epoch_manager_contract: "epoch0000".to_string(),
             community_bonding_contract: Some("new_community_bonding".to_string()),
+            bond_lock_contracts: vec![],
             unstake_period_blocks: 11,
             min_staking_amount: Uint128::from(1u128),
             lockup_config: LockupConfigResponse {
@@ -1921,7 +2134,11 @@ fn update_config() {
                 base_rate: Decimal::from_str("0.0002").unwrap(),
                 linear_growth: Decimal::from_str("0.0006").unwrap(),
                 exponential_growth: Decimal::from_str("0.0000076").unwrap(),
-            }
+                early_unlock_penalty: None,
+            },
+            boost: None,
+            instant_unstake: None,
+            staker_history_limit: 0,
         }
     );
 }
@@ -1945,6 +2162,10 @@ fn pause_contract() {
         base_rate: Decimal::from_str("0.0001").unwrap(),
         linear_growth: Decimal::from_str("0.0005").unwrap(),
         exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -1961,6 +2182,10 @@ fn pause_contract() {
         linear_growth: None,
         exponential_growth: None,
         community_bonding_contract: None,
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: None,
     };
 
     let info = mock_info("owner", &[]);
@@ -1982,6 +2207,7 @@ fn pause_contract() {
         },
         ExecuteMsg::Unstake {
             amount: Uint128::zero(),
+            unstake_type: None,
         },
         ExecuteMsg::Withdraw {},
         ExecuteMsg::ClaimBroRewards {},
@@ -2008,6 +2234,10 @@ fn pause_contract() {
         linear_growth: None,
         exponential_growth: None,
         community_bonding_contract: None,
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: None,
     };
 
     let info = mock_info("owner", &[]);
@@ -2021,11 +2251,13 @@ fn pause_contract() {
         ConfigResponse {
             owner: "owner".to_string(),
             paused: false,
+            emergency_mode: false,
             bro_token: "bro0000".to_string(),
             rewards_pool_contract: "reward0000".to_string(),
             bbro_minter_contract: "bbrominter0000".to_string(),
             epoch_manager_contract: "epoch0000".to_string(),
             community_bonding_contract: Some("community_bonding0000".to_string()),
+            bond_lock_contracts: vec![],
             unstake_period_blocks: 10,
             min_staking_amount: Uint128::zero(),
             lockup_config: LockupConfigResponse {
@@ -2034,7 +2266,11 @@ fn pause_contract() {
                 base_rate: Decimal::from_str("0.0001").unwrap(),
                 linear_growth: Decimal::from_str("0.0005").unwrap(),
                 exponential_growth: Decimal::from_str("0.0000075").unwrap(),
-            }
+                early_unlock_penalty: None,
+            },
+            boost: None,
+            instant_unstake: None,
+            staker_history_limit: 0,
         }
     );
 }
@@ -2057,6 +2293,10 @@ fn update_owner() {
         base_rate: Decimal::from_str("0.0001").unwrap(),
         linear_growth: Decimal::from_str("0.0005").unwrap(),
         exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -2097,11 +2337,13 @@ fn update_owner() {
         ConfigResponse {
             owner: "owner0001".to_string(),
             paused: false,
+            emergency_mode: false,
             bro_token: "bro0000".to_string(),
             rewards_pool_contract: "reward0000".to_string(),
             bbro_minter_contract: "bbrominter0000".to_string(),
             epoch_manager_contract: "epoch0000".to_string(),
             community_bonding_contract: Some("community_bonding0000".to_string()),
+            bond_lock_contracts: vec![],
             unstake_period_blocks: 10,
             min_staking_amount: Uint128::zero(),
             lockup_config: LockupConfigResponse {
@@ -2110,7 +2352,11 @@ fn update_owner() {
                 base_rate: Decimal::from_str("0.0001").unwrap(),
                 linear_growth: Decimal::from_str("0.0005").unwrap(),
                 exponential_growth: Decimal::from_str("0.0000075").unwrap(),
-            }
+                early_unlock_penalty: None,
+            },
+            boost: None,
+            instant_unstake: None,
+            staker_history_limit: 0,
         },
     );
 }
@@ -2127,33 +2373,34 @@ fn test_lockups_migration_v100() {
         bbro_emission_rate: Decimal::zero(),
     };
 
-    let mut staker_info = StakerInfo {
-        reward_index: Decimal::zero(),
+    let legacy_staker_info = |lockups: Vec<LockupInfoV120>| StakerInfoV120 {
+        reward_index: Decimal::from_str("1.0").unwrap(),
         unlocked_stake_amount: Uint128::zero(),
-        locked_stake_amount: Uint128::from(100u128),
+        locked_stake_amount: lockups.iter().map(|l| l.amount).sum(),
         pending_bro_reward: Uint128::zero(),
         pending_bbro_reward: Uint128::zero(),
         last_balance_update: 0,
-        lockups: vec![LockupInfo {
-            amount: Uint128::from(100u128),
-            unlocked_at: Expiration::AtHeight(1200),
-            locked_at_block: None,
-            epochs_locked: None,
-        }],
+        lockups,
+        auto_compound: false,
+        boost_multiplier: None,
     };
 
     // must recalculate locked_at_block and epochs_locked
-    staker_info
-        .unlock_expired_lockups(&env.block, &epoch_info, prev_epoch)
-        .unwrap();
+    let mut staker_info = legacy_staker_info(vec![LockupInfoV120 {
+        amount: Uint128::from(100u128),
+        unlocked_at: Expiration::AtHeight(1200),
+        locked_at_block: None,
+        epochs_locked: None,
+    }])
+    .into_staker_info(env.block.height, prev_epoch)
+    .unwrap();
 
     assert_eq!(
         staker_info.lockups,
         vec![LockupInfo {
             amount: Uint128::from(100u128),
-            unlocked_at: Expiration::AtHeight(1200),
-            locked_at_block: Some(1000),
-            epochs_locked: Some(2),
+            locked_at_block: 1000,
+            epochs_locked: 2,
         }]
     );
 
@@ -2162,7 +2409,7 @@ fn test_lockups_migration_v100() {
     env.block.height = 1100;
 
     staker_info
-        .unlock_expired_lockups(&env.block, &epoch_info, prev_epoch)
+        .unlock_expired_lockups(&env.block, &epoch_info)
         .unwrap();
 
     assert_eq!(staker_info.lockups, vec![]);
@@ -2173,38 +2420,28 @@ fn test_lockups_migration_v100() {
     let mut env = mock_env();
     env.block.height = 1000;
 
-    let mut staker_info = StakerInfo {
-        reward_index: Decimal::zero(),
-        unlocked_stake_amount: Uint128::zero(),
-        locked_stake_amount: Uint128::from(200u128),
-        pending_bro_reward: Uint128::zero(),
-        pending_bbro_reward: Uint128::zero(),
-        last_balance_update: 0,
-        lockups: vec![
-            LockupInfo {
-                amount: Uint128::from(100u128),
-                unlocked_at: Expiration::AtHeight(999), // aready expired
-                locked_at_block: None,
-                epochs_locked: None,
-            },
-            LockupInfo {
-                amount: Uint128::from(100u128),
-                unlocked_at: Expiration::AtHeight(1099), // less than 1 epoch left
-                locked_at_block: None,
-                epochs_locked: None,
-            },
-        ],
-    };
-
-    staker_info
-        .unlock_expired_lockups(&env.block, &epoch_info, prev_epoch)
-        .unwrap();
+    let staker_info = legacy_staker_info(vec![
+        LockupInfoV120 {
+            amount: Uint128::from(100u128),
+            unlocked_at: Expiration::AtHeight(999), // aready expired
+            locked_at_block: None,
+            epochs_locked: None,
+        },
+        LockupInfoV120 {
+            amount: Uint128::from(100u128),
+            unlocked_at: Expiration::AtHeight(1099), // less than 1 epoch left
+            locked_at_block: None,
+            epochs_locked: None,
+        },
+    ])
+    .into_staker_info(env.block.height, prev_epoch)
+    .unwrap();
 
     assert_eq!(staker_info.lockups, vec![]);
     assert_eq!(staker_info.unlocked_stake_amount, Uint128::from(200u128));
     assert_eq!(staker_info.locked_stake_amount, Uint128::zero());
 
-    // test query stakers with legacy lockups and update method
+    // test query stakers with legacy lockups, update method and batch migration
     let mut deps = mock_dependencies(&[]);
     let msg = InstantiateMsg {
         owner: "owner".to_string(),
@@ -2220,94 +2457,65 @@ fn test_lockups_migration_v100() {
         base_rate: Decimal::from_str("0.0001").unwrap(),
         linear_growth: Decimal::from_str("0.0005").unwrap(),
         exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
     };
 
     let info = mock_info("addr0000", &[]);
     let _res = instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
 
-    let stakers = vec![
-        deps.as_mut().api.addr_canonicalize("addr0000").unwrap(),
-        deps.as_mut().api.addr_canonicalize("addr0001").unwrap(),
-        deps.as_mut().api.addr_canonicalize("addr0002").unwrap(),
-        deps.as_mut().api.addr_canonicalize("addr0003").unwrap(),
-    ];
+    // new contract has nothing to migrate
+    let msg = ExecuteMsg::MigrateLockupsBatch { limit: None };
+    match execute(deps.as_mut(), env.clone(), mock_info("bruh0000", &[]), msg) {
+        Err(ContractError::LockupsMigrationIsCompleted {}) => (),
+        _ => panic!("expecting lockups migration is completed error"),
+    }
 
-    crate::state::store_staker_info(
+    // store stakers using outdated schema like contract deployed before migration
+    crate::migration::store_lockups_migration_progress(
         deps.as_mut().storage,
-        &stakers[0],
-        &StakerInfo {
-            reward_index: Decimal::from_str("1.0").unwrap(),
-            unlocked_stake_amount: Uint128::zero(),
-            locked_stake_amount: Uint128::from(100u128),
-            pending_bro_reward: Uint128::zero(),
-            pending_bbro_reward: Uint128::zero(),
-            last_balance_update: 0,
-            lockups: vec![LockupInfo {
-                amount: Uint128::from(100u128),
-                unlocked_at: Expiration::AtHeight(1200),
-                locked_at_block: None,
-                epochs_locked: None,
-            }],
-        },
+        &crate::migration::LockupsMigrationProgress::default(),
     )
     .unwrap();
-    crate::state::store_staker_info(
-        deps.as_mut().storage,
-        &stakers[1],
-        &StakerInfo {
-            reward_index: Decimal::from_str("1.0").unwrap(),
-            unlocked_stake_amount: Uint128::zero(),
-            locked_stake_amount: Uint128::from(100u128),
-            pending_bro_reward: Uint128::zero(),
-            pending_bbro_reward: Uint128::zero(),
-            last_balance_update: 0,
-            lockups: vec![LockupInfo {
+
+    let legacy_stakers: Map<&[u8], StakerInfoV120> = Map::new("stakers");
+    let deprecated_lockup = LockupInfoV120 {
+        amount: Uint128::from(100u128),
+        unlocked_at: Expiration::AtHeight(1200),
+        locked_at_block: None,
+        epochs_locked: None,
+    };
+    let stakers = vec![
+        ("addr0000", deprecated_lockup.clone()),
+        (
+            "addr0001",
+            LockupInfoV120 {
                 amount: Uint128::from(100u128),
                 unlocked_at: Expiration::Never {},
                 locked_at_block: Some(env.block.height),
                 epochs_locked: Some(100),
-            }],
-        },
-    )
-    .unwrap();
-    crate::state::store_staker_info(
-        deps.as_mut().storage,
-        &stakers[2],
-        &StakerInfo {
-            reward_index: Decimal::from_str("1.0").unwrap(),
-            unlocked_stake_amount: Uint128::zero(),
-            locked_stake_amount: Uint128::from(100u128),
-            pending_bro_reward: Uint128::zero(),
-            pending_bbro_reward: Uint128::zero(),
-            last_balance_update: 0,
-            lockups: vec![LockupInfo {
-                amount: Uint128::from(100u128),
-                unlocked_at: Expiration::AtHeight(1200),
-                locked_at_block: None,
-                epochs_locked: None,
-            }],
-        },
-    )
-    .unwrap();
-    crate::state::store_staker_info(
-        deps.as_mut().storage,
-        &stakers[3],
-        &StakerInfo {
-            reward_index: Decimal::from_str("1.0").unwrap(),
-            unlocked_stake_amount: Uint128::zero(),
-            locked_stake_amount: Uint128::from(100u128),
-            pending_bro_reward: Uint128::zero(),
-            pending_bbro_reward: Uint128::zero(),
-            last_balance_update: 0,
-            lockups: vec![LockupInfo {
-                amount: Uint128::from(100u128),
-                unlocked_at: Expiration::AtHeight(1200),
-                locked_at_block: None,
-                epochs_locked: None,
-            }],
-        },
-    )
-    .unwrap();
+            },
+        ),
+        ("addr0002", deprecated_lockup.clone()),
+        ("addr0003", deprecated_lockup),
+    ];
+    for (staker, lockup) in stakers {
+        let staker_raw = deps.as_mut().api.addr_canonicalize(staker).unwrap();
+        legacy_stakers
+            .save(
+                deps.as_mut().storage,
+                staker_raw.as_slice(),
+                &legacy_staker_info(vec![lockup]),
+            )
+            .unwrap();
+    }
+    let mut state = crate::state::load_state(deps.as_ref().storage).unwrap();
+    state.global_reward_index = Decimal::one();
+    state.total_stake_amount = Uint128::from(400u128);
+    crate::state::store_state(deps.as_mut().storage, &state).unwrap();
+    crate::state::reset_stakers_stats(deps.as_mut().storage, state.total_stake_amount).unwrap();
 
     assert_eq!(
         from_binary::<Vec<String>>(
@@ -2329,11 +2537,45 @@ fn test_lockups_migration_v100() {
         ],
     );
 
+    // stakers are included into lockup stats by migration batches
+    assert_eq!(
+        from_binary::<LockupStatsResponse>(
+            &query(deps.as_ref(), env.clone(), QueryMsg::LockupStats {}).unwrap()
+        )
+        .unwrap(),
+        LockupStatsResponse {
+            active_stakers: 0,
+            total_locked_amount: Uint128::zero(),
+            lockups: vec![],
+        },
+    );
+
+    // not migrated staker info is converted on read
+    assert_eq!(
+        from_binary::<StakerInfoResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::StakerInfo {
+                    staker: "addr0002".to_string(),
+                },
+            )
+            .unwrap()
+        )
+        .unwrap()
+        .lockups,
+        vec![LockupInfoResponse {
+            amount: Uint128::from(100u128),
+            locked_at_block: 1000,
+            epochs_locked: 200,
+        }],
+    );
+
     let msg = ExecuteMsg::UpdateStakerLockups {
         stakers: vec!["addr0000".to_string()],
     };
     let info = mock_info("bruh0000", &[]);
-    let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
+    let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
     assert_eq!(
         from_binary::<Vec<String>>(
@@ -2351,11 +2593,95 @@ fn test_lockups_migration_v100() {
         vec!["addr0002".to_string(), "addr0003".to_string()],
     );
 
-    let msg = ExecuteMsg::UpdateStakerLockups {
-        stakers: vec!["addr0002".to_string(), "addr0003".to_string()],
-    };
-    let info = mock_info("bruh0000", &[]);
-    let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
+    let msg = ExecuteMsg::MigrateLockupsBatch { limit: Some(3) };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
+    assert_eq!(
+        res.attributes[..2],
+        vec![
+            Attribute::new("action", "migrate_lockups_batch"),
+            Attribute::new("migrated_stakers", "3"),
+        ]
+    );
+    assert_eq!(res.attributes[3], Attribute::new("completed", "false"));
+
+    let lockup_stats = from_binary::<LockupStatsResponse>(
+        &query(deps.as_ref(), env.clone(), QueryMsg::LockupStats {}).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(lockup_stats.active_stakers, 3);
+    assert_eq!(lockup_stats.total_locked_amount, Uint128::from(300u128));
+
+    // voting balances are incomplete until all stakers are migrated
+    match query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::VotingPower {
+            address: "addr0000".to_string(),
+            at_block: None,
+        },
+    ) {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(
+            msg,
+            "voting power is not available until lockups migration is completed"
+        ),
+        _ => panic!("expecting voting power is not available error"),
+    }
+    match query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::TotalVotingPower { at_block: None },
+    ) {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(
+            msg,
+            "voting power is not available until lockups migration is completed"
+        ),
+        _ => panic!("expecting voting power is not available error"),
+    }
+
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
+    assert_eq!(res.attributes[1], Attribute::new("migrated_stakers", "1"));
+    assert_eq!(res.attributes[3], Attribute::new("completed", "true"));
+
+    let _res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::TotalVotingPower { at_block: None },
+    )
+    .unwrap();
+
+    // history before migration completion is not available
+    match query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::TotalVotingPower {
+            at_block: Some(env.block.height),
+        },
+    ) {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(
+            msg,
+            "at_block must be greater than lockups migration completion block"
+        ),
+        _ => panic!("expecting at_block must be greater than completion block error"),
+    }
+
+    let progress = from_binary::<LockupsMigrationProgressResponse>(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::LockupsMigrationProgress {},
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(progress.migrated_stakers, 4);
+    assert_eq!(progress.converted_lockups, 2);
+    assert!(progress.completed);
+    assert!(progress.last_migrated_staker.is_some());
+
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::LockupsMigrationIsCompleted {}) => (),
+        _ => panic!("expecting lockups migration is completed error"),
+    }
 
     let empty: Vec<String> = vec![];
     assert_eq!(
@@ -2373,4 +2699,3768 @@ fn test_lockups_migration_v100() {
         .unwrap(),
         empty,
     );
+
+    assert_eq!(
+        from_binary::<LockupStatsResponse>(
+            &query(deps.as_ref(), env.clone(), QueryMsg::LockupStats {}).unwrap()
+        )
+        .unwrap(),
+        LockupStatsResponse {
+            active_stakers: 4,
+            total_locked_amount: Uint128::from(400u128),
+            lockups: vec![
+                LockupStatsItem {
+                    epochs_locked: 100,
+                    locked_amount: Uint128::from(100u128),
+                },
+                LockupStatsItem {
+                    epochs_locked: 200,
+                    locked_amount: Uint128::from(300u128),
+                },
+            ],
+        },
+    );
+
+    // all stakers are stored using current lockups schema
+    let stored_stakers: Map<&[u8], StakerInfo> = Map::new("stakers");
+    assert_eq!(
+        stored_stakers
+            .range(deps.as_ref().storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()
+            .unwrap()
+            .len(),
+        4
+    );
+    assert!(deps
+        .storage
+        .range(None, None, Order::Ascending)
+        .all(|(_, value)| !String::from_utf8_lossy(&value).contains("\"unlocked_at\"")));
+}
+
+#[test]
+fn compound_bro_rewards() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        bro_token: "bro0000".to_string(),
+        rewards_pool_contract: "reward0000".to_string(),
+        bbro_minter_contract: "bbrominter0000".to_string(),
+        epoch_manager_contract: "epoch0000".to_string(),
+        community_bonding_contract: Some("community_bonding0000".to_string()),
+        unstake_period_blocks: 10,
+        min_staking_amount: Uint128::zero(),
+        min_lockup_period_epochs: 1,
+        max_lockup_period_epochs: 365,
+        base_rate: Decimal::from_str("0.0001").unwrap(),
+        linear_growth: Decimal::from_str("0.0005").unwrap(),
+        exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let mut env = mock_env();
+    env.block.height += 1;
+
+    // addr0000 and addr0001 stake 100 tokens each
+    for staker in ["addr0000", "addr0001"] {
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: staker.to_string(),
+            amount: Uint128::from(100u128),
+            msg: to_binary(&Cw20HookMsg::Stake {
+                stake_type: StakeType::Unlocked {},
+            })
+            .unwrap(),
+        });
+        let info = mock_info("bro0000", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    }
+
+    // nothing to compound yet
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::CompoundBroRewards {
+        epochs_locked: None,
+    };
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::NothingToCompound {}) => (),
+        _ => panic!("expecting ContractError::NothingToCompound"),
+    }
+
+    // distribute 2000 reward, every staker receives 1000
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "reward0000".to_string(),
+        amount: Uint128::from(2000u128),
+        msg: to_binary(&Cw20HookMsg::DistributeReward {
+            distributed_at_block: env.block.height,
+        })
+        .unwrap(),
+    });
+    let info = mock_info("bro0000", &[]);
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    env.block.height += 1;
+
+    // addr0000 compounds rewards into unlocked stake
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::CompoundBroRewards {
+        epochs_locked: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(res.messages.len(), 0);
+    assert_eq!(
+        res.attributes,
+        vec![
+            Attribute::new("action", "compound_bro_rewards"),
+            Attribute::new("staker", "addr0000"),
+            Attribute::new("amount", "1000"),
+        ]
+    );
+
+    assert_eq!(
+        from_binary::<StakerInfoResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::StakerInfo {
+                    staker: "addr0000".to_string()
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap(),
+        StakerInfoResponse {
+            staker: "addr0000".to_string(),
+            reward_index: Decimal::from_ratio(10u128, 1u128),
+            unlocked_stake_amount: Uint128::from(1100u128),
+            locked_stake_amount: Uint128::zero(),
+            pending_bro_reward: Uint128::zero(),
+            pending_bbro_reward: Uint128::from(30u128),
+            last_balance_update: env.block.height,
+            lockups: vec![],
+            auto_compound: false,
+        }
+    );
+
+    assert_eq!(
+        from_binary::<StateResponse>(
+            &query(deps.as_ref(), env.clone(), QueryMsg::State {}).unwrap()
+        )
+        .unwrap(),
+        StateResponse {
+            total_stake_amount: Uint128::from(1200u128),
+            global_reward_index: Decimal::from_ratio(10u128, 1u128),
+            last_distribution_block: env.block.height,
+            reward_rate: Decimal::from_ratio(2000u128, 1u128),
+            period_finish: env.block.height,
+        }
+    );
+
+    // enable auto compounding for addr0001
+    let info = mock_info("addr0002", &[]);
+    let msg = ExecuteMsg::SetAutoCompound { enabled: true };
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::Std(StdError::GenericErr { msg, .. })) => {
+            assert_eq!(msg, "staker not found")
+        }
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    let info = mock_info("addr0001", &[]);
+    let msg = ExecuteMsg::SetAutoCompound { enabled: true };
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    // distribute 1200 reward, staker's index grows by 1
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "reward0000".to_string(),
+        amount: Uint128::from(1200u128),
+        msg: to_binary(&Cw20HookMsg::DistributeReward {
+            distributed_at_block: env.block.height,
+        })
+        .unwrap(),
+    });
+    let info = mock_info("bro0000", &[]);
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    // auto compound skips addr0000 and compounds addr0001 rewards,
+    // bbro reward is settled with stake amount before compounding
+    env.block.height += 1;
+    let info = mock_info("keeper0000", &[]);
+    let msg = ExecuteMsg::AutoCompoundBroRewards {
+        stakers: vec!["addr0000".to_string(), "addr0001".to_string()],
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            Attribute::new("action", "auto_compound_bro_rewards"),
+            Attribute::new("compounded_amount", "1100"),
+        ]
+    );
+
+    assert_eq!(
+        from_binary::<StakerInfoResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::StakerInfo {
+                    staker: "addr0001".to_string()
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap(),
+        StakerInfoResponse {
+            staker: "addr0001".to_string(),
+            reward_index: Decimal::from_ratio(11u128, 1u128),
+            unlocked_stake_amount: Uint128::from(1200u128),
+            locked_stake_amount: Uint128::zero(),
+            pending_bro_reward: Uint128::zero(),
+            pending_bbro_reward: Uint128::from(60u128),
+            last_balance_update: env.block.height,
+            lockups: vec![],
+            auto_compound: true,
+        }
+    );
+
+    // addr0000 compounds rewards into a new lockup
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::CompoundBroRewards {
+        epochs_locked: Some(366),
+    };
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::InvalidLockupPeriod {}) => (),
+        _ => panic!("expecting ContractError::InvalidLockupPeriod"),
+    }
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::CompoundBroRewards {
+        epochs_locked: Some(10),
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "bbrominter0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&BbroMintMsg::Mint {
+                recipient: "addr0000".to_string(),
+                amount: Uint128::from(5u128),
+            })
+            .unwrap(),
+        }))]
+    );
+
+    assert_eq!(
+        from_binary::<StakerInfoResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::StakerInfo {
+                    staker: "addr0000".to_string()
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap(),
+        StakerInfoResponse {
+            staker: "addr0000".to_string(),
+            reward_index: Decimal::from_ratio(11u128, 1u128),
+            unlocked_stake_amount: Uint128::from(1100u128),
+            locked_stake_amount: Uint128::from(1100u128),
+            pending_bro_reward: Uint128::zero(),
+            pending_bbro_reward: Uint128::from(360u128),
+            last_balance_update: env.block.height,
+            lockups: vec![LockupInfoResponse {
+                amount: Uint128::from(1100u128),
+                locked_at_block: env.block.height,
+                epochs_locked: 10,
+            }],
+            auto_compound: false,
+        }
+    );
+
+    assert_eq!(
+        from_binary::<StateResponse>(
+            &query(deps.as_ref(), env.clone(), QueryMsg::State {}).unwrap()
+        )
+        .unwrap(),
+        StateResponse {
+            total_stake_amount: Uint128::from(3400u128),
+            global_reward_index: Decimal::from_ratio(11u128, 1u128),
+            last_distribution_block: env.block.height,
+            reward_rate: Decimal::from_ratio(1200u128, 1u128),
+            period_finish: env.block.height,
+        }
+    );
+}
+
+#[test]
+fn early_unlock() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        bro_token: "bro0000".to_string(),
+        rewards_pool_contract: "reward0000".to_string(),
+        bbro_minter_contract: "bbrominter0000".to_string(),
+        epoch_manager_contract: "epoch0000".to_string(),
+        community_bonding_contract: Some("community_bonding0000".to_string()),
+        unstake_period_blocks: 10,
+        min_staking_amount: Uint128::zero(),
+        min_lockup_period_epochs: 1,
+        max_lockup_period_epochs: 365,
+        base_rate: Decimal::from_str("0.0001").unwrap(),
+        linear_growth: Decimal::from_str("0.0005").unwrap(),
+        exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let mut env = mock_env();
+    env.block.height += 1;
+    let locked_at_block = env.block.height;
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(1_000000u128),
+        msg: to_binary(&Cw20HookMsg::Stake {
+            stake_type: StakeType::Locked { epochs_locked: 10 },
+        })
+        .unwrap(),
+    });
+    let info = mock_info("bro0000", &[]);
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    // early unlock is disabled by default
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::EarlyUnlock {
+        lockup_index: 0,
+        amount: Uint128::from(400000u128),
+    };
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::EarlyUnlockIsNotEnabled {}) => (),
+        _ => panic!("expecting ContractError::EarlyUnlockIsNotEnabled"),
+    }
+
+    // invalid penalty curve
+    let msg = ExecuteMsg::UpdateConfig {
+        paused: None,
+        unstake_period_blocks: None,
+        min_staking_amount: None,
+        min_lockup_period_epochs: None,
+        max_lockup_period_epochs: None,
+        base_rate: None,
+        linear_growth: None,
+        exponential_growth: None,
+        community_bonding_contract: None,
+        early_unlock_penalty: Some(Box::new(EarlyUnlockPenalty {
+            penalty_type: EarlyUnlockPenaltyType::BurnBbro {},
+            min_rate: Decimal::from_str("0.5").unwrap(),
+            max_rate: Decimal::from_str("0.1").unwrap(),
+        })),
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: None,
+    };
+    let info = mock_info("owner", &[]);
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::Std(StdError::GenericErr { msg, .. })) => {
+            assert_eq!(msg, "min_rate must be less than or equal to max_rate")
+        }
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    let msg = ExecuteMsg::UpdateConfig {
+        paused: None,
+        unstake_period_blocks: None,
+        min_staking_amount: None,
+        min_lockup_period_epochs: None,
+        max_lockup_period_epochs: None,
+        base_rate: None,
+        linear_growth: None,
+        exponential_growth: None,
+        community_bonding_contract: None,
+        early_unlock_penalty: Some(Box::new(EarlyUnlockPenalty {
+            penalty_type: EarlyUnlockPenaltyType::BurnBbro {},
+            min_rate: Decimal::from_str("0.1").unwrap(),
+            max_rate: Decimal::from_str("1.1").unwrap(),
+        })),
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: None,
+    };
+    let info = mock_info("owner", &[]);
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::Std(StdError::GenericErr { msg, .. })) => {
+            assert_eq!(msg, "max_rate must be less than or equal to 1.0")
+        }
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    // enable early unlock with bbro burning
+    let msg = ExecuteMsg::UpdateConfig {
+        paused: None,
+        unstake_period_blocks: None,
+        min_staking_amount: None,
+        min_lockup_period_epochs: None,
+        max_lockup_period_epochs: None,
+        base_rate: None,
+        linear_growth: None,
+        exponential_growth: None,
+        community_bonding_contract: None,
+        early_unlock_penalty: Some(Box::new(EarlyUnlockPenalty {
+            penalty_type: EarlyUnlockPenaltyType::BurnBbro {},
+            min_rate: Decimal::from_str("0.1").unwrap(),
+            max_rate: Decimal::from_str("0.5").unwrap(),
+        })),
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: None,
+    };
+    let info = mock_info("owner", &[]);
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    // half of lockup period passed
+    env.block.height += 5;
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::EarlyUnlock {
+        lockup_index: 1,
+        amount: Uint128::from(400000u128),
+    };
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::LockupNotFound {}) => (),
+        _ => panic!("expecting ContractError::LockupNotFound"),
+    }
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::EarlyUnlock {
+        lockup_index: 0,
+        amount: Uint128::from(1_000001u128),
+    };
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::InvalidEarlyUnlockAmount {}) => (),
+        _ => panic!("expecting ContractError::InvalidEarlyUnlockAmount"),
+    }
+
+    // penalty rate: 0.1 + (0.5 - 0.1) * 5 / 10 = 0.3
+    // bbro premium reward for unlocked amount: 400000 * 0.00535 = 2140
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::EarlyUnlock {
+        lockup_index: 0,
+        amount: Uint128::from(400000u128),
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "bbrominter0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&BbroMintMsg::Burn {
+                owner: "addr0000".to_string(),
+                amount: Uint128::from(642u128),
+            })
+            .unwrap(),
+        }))]
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            Attribute::new("action", "early_unlock"),
+            Attribute::new("staker", "addr0000"),
+            Attribute::new("amount", "400000"),
+            Attribute::new("penalty_rate", "0.3"),
+            Attribute::new("bbro_burn_amount", "642"),
+        ]
+    );
+
+    assert_eq!(
+        from_binary::<StakerInfoResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::StakerInfo {
+                    staker: "addr0000".to_string()
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap(),
+        StakerInfoResponse {
+            staker: "addr0000".to_string(),
+            reward_index: Decimal::zero(),
+            unlocked_stake_amount: Uint128::from(400000u128),
+            locked_stake_amount: Uint128::from(600000u128),
+            pending_bro_reward: Uint128::zero(),
+            pending_bbro_reward: Uint128::from(1_500000u128),
+            last_balance_update: env.block.height,
+            lockups: vec![LockupInfoResponse {
+                amount: Uint128::from(600000u128),
+                locked_at_block,
+                epochs_locked: 10,
+            }],
+            auto_compound: false,
+        }
+    );
+
+    // switch penalty to bro fee
+    let msg = ExecuteMsg::UpdateConfig {
+        paused: None,
+        unstake_period_blocks: None,
+        min_staking_amount: None,
+        min_lockup_period_epochs: None,
+        max_lockup_period_epochs: None,
+        base_rate: None,
+        linear_growth: None,
+        exponential_growth: None,
+        community_bonding_contract: None,
+        early_unlock_penalty: Some(Box::new(EarlyUnlockPenalty {
+            penalty_type: EarlyUnlockPenaltyType::BroFee {},
+            min_rate: Decimal::from_str("0.1").unwrap(),
+            max_rate: Decimal::from_str("0.5").unwrap(),
+        })),
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: None,
+    };
+    let info = mock_info("owner", &[]);
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    // enable auto compounding, pending reward is compounded on early unlock
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::SetAutoCompound { enabled: true };
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "reward0000".to_string(),
+        amount: Uint128::from(1000u128),
+        msg: to_binary(&Cw20HookMsg::DistributeReward {
+            distributed_at_block: env.block.height,
+        })
+        .unwrap(),
+    });
+    let info = mock_info("bro0000", &[]);
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    let distributed_at_block = env.block.height;
+
+    // penalty rate: 0.1 + (0.5 - 0.1) * 2 / 10 = 0.18
+    env.block.height += 3;
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::EarlyUnlock {
+        lockup_index: 0,
+        amount: Uint128::from(600000u128),
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "bro0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "reward0000".to_string(),
+                amount: Uint128::from(108000u128),
+            })
+            .unwrap(),
+        }))]
+    );
+
+    assert_eq!(
+        from_binary::<StakerInfoResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::StakerInfo {
+                    staker: "addr0000".to_string()
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap(),
+        StakerInfoResponse {
+            staker: "addr0000".to_string(),
+            reward_index: Decimal::from_str("0.001").unwrap(),
+            unlocked_stake_amount: Uint128::from(893000u128),
+            locked_stake_amount: Uint128::zero(),
+            pending_bro_reward: Uint128::zero(),
+            pending_bbro_reward: Uint128::from(2_400000u128),
+            last_balance_update: env.block.height,
+            lockups: vec![],
+            auto_compound: true,
+        }
+    );
+
+    assert_eq!(
+        from_binary::<StateResponse>(
+            &query(deps.as_ref(), env.clone(), QueryMsg::State {}).unwrap()
+        )
+        .unwrap(),
+        StateResponse {
+            total_stake_amount: Uint128::from(893000u128),
+            global_reward_index: Decimal::from_str("0.001").unwrap(),
+            last_distribution_block: env.block.height,
+            reward_rate: Decimal::from_ratio(1000u128, 1u128),
+            period_finish: distributed_at_block + 1,
+        }
+    );
+}
+
+#[test]
+fn extend_and_merge_lockups() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        bro_token: "bro0000".to_string(),
+        rewards_pool_contract: "reward0000".to_string(),
+        bbro_minter_contract: "bbrominter0000".to_string(),
+        epoch_manager_contract: "epoch0000".to_string(),
+        community_bonding_contract: Some("community_bonding0000".to_string()),
+        unstake_period_blocks: 10,
+        min_staking_amount: Uint128::zero(),
+        min_lockup_period_epochs: 1,
+        max_lockup_period_epochs: 365,
+        base_rate: Decimal::from_str("0.0001").unwrap(),
+        linear_growth: Decimal::from_str("0.0005").unwrap(),
+        exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let mut env = mock_env();
+    env.block.height += 1;
+
+    // create lockups for 10, 4 and 2 epochs
+    for epochs_locked in [10, 4, 2] {
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "addr0000".to_string(),
+            amount: Uint128::from(1_000000u128),
+            msg: to_binary(&Cw20HookMsg::Stake {
+                stake_type: StakeType::Locked { epochs_locked },
+            })
+            .unwrap(),
+        });
+        let info = mock_info("bro0000", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    }
+
+    // remaining epochs: 9, 3 and 1
+    env.block.height += 1;
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::ExtendLockup {
+        lockup_index: 3,
+        additional_epochs: 1,
+    };
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::LockupNotFound {}) => (),
+        _ => panic!("expecting ContractError::LockupNotFound"),
+    }
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::ExtendLockup {
+        lockup_index: 0,
+        additional_epochs: 0,
+    };
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::InvalidLockupPeriod {}) => (),
+        _ => panic!("expecting ContractError::InvalidLockupPeriod"),
+    }
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::ExtendLockup {
+        lockup_index: 0,
+        additional_epochs: 357,
+    };
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::InvalidLockupPeriod {}) => (),
+        _ => panic!("expecting ContractError::InvalidLockupPeriod"),
+    }
+
+    // extend second lockup from 3 remaining epochs to 10 epochs,
+    // premium reward difference: 1000000 * (0.00535 - 0.0011675) = 4183
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::ExtendLockup {
+        lockup_index: 1,
+        additional_epochs: 7,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "bbrominter0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&BbroMintMsg::Mint {
+                recipient: "addr0000".to_string(),
+                amount: Uint128::from(4183u128),
+            })
+            .unwrap(),
+        }))]
+    );
+
+    assert_eq!(
+        from_binary::<StakerInfoResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::StakerInfo {
+                    staker: "addr0000".to_string()
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+        .lockups,
+        vec![
+            LockupInfoResponse {
+                amount: Uint128::from(1_000000u128),
+                locked_at_block: env.block.height - 1,
+                epochs_locked: 10,
+            },
+            LockupInfoResponse {
+                amount: Uint128::from(1_000000u128),
+                locked_at_block: env.block.height,
+                epochs_locked: 10,
+            },
+            LockupInfoResponse {
+                amount: Uint128::from(1_000000u128),
+                locked_at_block: env.block.height - 1,
+                epochs_locked: 2,
+            },
+        ]
+    );
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::MergeLockups {
+        indices: vec![0, 0],
+    };
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::InvalidLockupsToMerge {}) => (),
+        _ => panic!("expecting ContractError::InvalidLockupsToMerge"),
+    }
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::MergeLockups {
+        indices: vec![0, 3],
+    };
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::LockupNotFound {}) => (),
+        _ => panic!("expecting ContractError::LockupNotFound"),
+    }
+
+    // merge all lockups into one locked for 10 epochs,
+    // premium reward difference: 1000000 * (0.00535 - 0.0047075) + 0 + 1000000 * (0.00535 - 0.0001075) = 5886
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::MergeLockups {
+        indices: vec![2, 0, 1],
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "bbrominter0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&BbroMintMsg::Mint {
+                recipient: "addr0000".to_string(),
+                amount: Uint128::from(5886u128),
+            })
+            .unwrap(),
+        }))]
+    );
+
+    assert_eq!(
+        from_binary::<StakerInfoResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::StakerInfo {
+                    staker: "addr0000".to_string()
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap(),
+        StakerInfoResponse {
+            staker: "addr0000".to_string(),
+            reward_index: Decimal::zero(),
+            unlocked_stake_amount: Uint128::zero(),
+            locked_stake_amount: Uint128::from(3_000000u128),
+            pending_bro_reward: Uint128::zero(),
+            pending_bbro_reward: Uint128::from(900000u128),
+            last_balance_update: env.block.height - 1,
+            lockups: vec![LockupInfoResponse {
+                amount: Uint128::from(3_000000u128),
+                locked_at_block: env.block.height,
+                epochs_locked: 10,
+            }],
+            auto_compound: false,
+        }
+    );
+}
+
+#[test]
+fn merge_lockups_below_min_lockup_period() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        bro_token: "bro0000".to_string(),
+        rewards_pool_contract: "reward0000".to_string(),
+        bbro_minter_contract: "bbrominter0000".to_string(),
+        epoch_manager_contract: "epoch0000".to_string(),
+        community_bonding_contract: None,
+        unstake_period_blocks: 10,
+        min_staking_amount: Uint128::zero(),
+        min_lockup_period_epochs: 3,
+        max_lockup_period_epochs: 365,
+        base_rate: Decimal::from_str("0.0001").unwrap(),
+        linear_growth: Decimal::from_str("0.0005").unwrap(),
+        exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let mut env = mock_env();
+
+    // create lockups for 3 and 4 epochs
+    for epochs_locked in [3, 4] {
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "addr0000".to_string(),
+            amount: Uint128::from(1_000000u128),
+            msg: to_binary(&Cw20HookMsg::Stake {
+                stake_type: StakeType::Locked { epochs_locked },
+            })
+            .unwrap(),
+        });
+        let info = mock_info("bro0000", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    }
+
+    // remaining epochs: 1 and 2, both below min lockup period
+    env.block.height += 2;
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::MergeLockups {
+        indices: vec![0, 1],
+    };
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::InvalidLockupPeriod {}) => (),
+        _ => panic!("expecting ContractError::InvalidLockupPeriod"),
+    }
+
+    // expired lockups are unlocked before merging
+    env.block.height += 2;
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::MergeLockups {
+        indices: vec![0, 1],
+    };
+    match execute(deps.as_mut(), env, info, msg) {
+        Err(ContractError::LockupNotFound {}) => (),
+        _ => panic!("expecting ContractError::LockupNotFound"),
+    }
+}
+
+#[test]
+fn stakers_and_lockup_stats() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        bro_token: "bro0000".to_string(),
+        rewards_pool_contract: "reward0000".to_string(),
+        bbro_minter_contract: "bbrominter0000".to_string(),
+        epoch_manager_contract: "epoch0000".to_string(),
+        community_bonding_contract: None,
+        unstake_period_blocks: 10,
+        min_staking_amount: Uint128::zero(),
+        min_lockup_period_epochs: 1,
+        max_lockup_period_epochs: 365,
+        base_rate: Decimal::from_str("0.0001").unwrap(),
+        linear_growth: Decimal::from_str("0.0005").unwrap(),
+        exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let mut env = mock_env();
+
+    assert_eq!(
+        from_binary::<LockupStatsResponse>(
+            &query(deps.as_ref(), env.clone(), QueryMsg::LockupStats {}).unwrap()
+        )
+        .unwrap(),
+        LockupStatsResponse {
+            active_stakers: 0,
+            total_locked_amount: Uint128::zero(),
+            lockups: vec![],
+        }
+    );
+
+    let stakes = vec![
+        (
+            "addr0000",
+            Uint128::from(1_000000u128),
+            StakeType::Unlocked {},
+        ),
+        (
+            "addr0000",
+            Uint128::from(1_000000u128),
+            StakeType::Locked { epochs_locked: 10 },
+        ),
+        (
+            "addr0001",
+            Uint128::from(2_000000u128),
+            StakeType::Locked { epochs_locked: 10 },
+        ),
+        (
+            "addr0001",
+            Uint128::from(1_000000u128),
+            StakeType::Locked { epochs_locked: 4 },
+        ),
+    ];
+    for (sender, amount, stake_type) in stakes {
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: sender.to_string(),
+            amount,
+            msg: to_binary(&Cw20HookMsg::Stake { stake_type }).unwrap(),
+        });
+        let info = mock_info("bro0000", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    }
+
+    assert_eq!(
+        from_binary::<LockupStatsResponse>(
+            &query(deps.as_ref(), env.clone(), QueryMsg::LockupStats {}).unwrap()
+        )
+        .unwrap(),
+        LockupStatsResponse {
+            active_stakers: 2,
+            total_locked_amount: Uint128::from(4_000000u128),
+            lockups: vec![
+                LockupStatsItem {
+                    epochs_locked: 4,
+                    locked_amount: Uint128::from(1_000000u128),
+                },
+                LockupStatsItem {
+                    epochs_locked: 10,
+                    locked_amount: Uint128::from(3_000000u128),
+                },
+            ],
+        }
+    );
+
+    // paginate in ascending order
+    let res = from_binary::<StakersResponse>(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Stakers {
+                start_after: None,
+                limit: Some(1),
+                order_by: Some(OrderBy::Asc),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.stakers,
+        vec![StakerInfoResponse {
+            staker: "addr0000".to_string(),
+            reward_index: Decimal::zero(),
+            unlocked_stake_amount: Uint128::from(1_000000u128),
+            locked_stake_amount: Uint128::from(1_000000u128),
+            pending_bro_reward: Uint128::zero(),
+            pending_bbro_reward: Uint128::zero(),
+            last_balance_update: env.block.height,
+            lockups: vec![LockupInfoResponse {
+                amount: Uint128::from(1_000000u128),
+                locked_at_block: env.block.height,
+                epochs_locked: 10,
+            }],
+            auto_compound: false,
+        }]
+    );
+
+    let res = from_binary::<StakersResponse>(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Stakers {
+                start_after: Some("addr0000".to_string()),
+                limit: None,
+                order_by: Some(OrderBy::Asc),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.stakers
+            .into_iter()
+            .map(|s| s.staker)
+            .collect::<Vec<String>>(),
+        vec!["addr0001".to_string()]
+    );
+
+    // descending order is used by default
+    let res = from_binary::<StakersResponse>(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Stakers {
+                start_after: None,
+                limit: None,
+                order_by: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.stakers
+            .into_iter()
+            .map(|s| s.staker)
+            .collect::<Vec<String>>(),
+        vec!["addr0001".to_string(), "addr0000".to_string()]
+    );
+
+    // 4 epochs lockup expires and is unstaked
+    env.block.height += 5;
+    let info = mock_info("addr0001", &[]);
+    let msg = ExecuteMsg::Unstake {
+        amount: Uint128::from(1_000000u128),
+        unstake_type: None,
+    };
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    assert_eq!(
+        from_binary::<LockupStatsResponse>(
+            &query(deps.as_ref(), env.clone(), QueryMsg::LockupStats {}).unwrap()
+        )
+        .unwrap(),
+        LockupStatsResponse {
+            active_stakers: 2,
+            total_locked_amount: Uint128::from(3_000000u128),
+            lockups: vec![LockupStatsItem {
+                epochs_locked: 10,
+                locked_amount: Uint128::from(3_000000u128),
+            }],
+        }
+    );
+
+    // 10 epochs lockups expire and first staker unstakes everything
+    env.block.height += 6;
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::Unstake {
+        amount: Uint128::from(2_000000u128),
+        unstake_type: None,
+    };
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    assert_eq!(
+        from_binary::<LockupStatsResponse>(
+            &query(deps.as_ref(), env.clone(), QueryMsg::LockupStats {}).unwrap()
+        )
+        .unwrap(),
+        LockupStatsResponse {
+            active_stakers: 1,
+            total_locked_amount: Uint128::from(2_000000u128),
+            lockups: vec![LockupStatsItem {
+                epochs_locked: 10,
+                locked_amount: Uint128::from(2_000000u128),
+            }],
+        }
+    );
+}
+
+#[test]
+fn cancel_unstake_and_restake_withdrawals() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        bro_token: "bro0000".to_string(),
+        rewards_pool_contract: "reward0000".to_string(),
+        bbro_minter_contract: "bbrominter0000".to_string(),
+        epoch_manager_contract: "epoch0000".to_string(),
+        community_bonding_contract: None,
+        unstake_period_blocks: 10,
+        min_staking_amount: Uint128::zero(),
+        min_lockup_period_epochs: 1,
+        max_lockup_period_epochs: 365,
+        base_rate: Decimal::from_str("0.0001").unwrap(),
+        linear_growth: Decimal::from_str("0.0005").unwrap(),
+        exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let mut env = mock_env();
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::RestakeWithdrawals {};
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::NothingToRestake {}) => (),
+        _ => panic!("expecting ContractError::NothingToRestake"),
+    }
+
+    let info = mock_info("bro0000", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Stake {
+            stake_type: StakeType::Unlocked {},
+        })
+        .unwrap(),
+    });
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    env.block.height += 1;
+    for amount in [60u128, 20u128] {
+        let info = mock_info("addr0000", &[]);
+        let msg = ExecuteMsg::Unstake {
+            amount: Uint128::from(amount),
+            unstake_type: None,
+        };
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    }
+
+    // distribute reward for remaining 20 staked tokens
+    env.block.height += 1;
+    let info = mock_info("bro0000", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "reward0000".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::DistributeReward {
+            distributed_at_block: env.block.height,
+        })
+        .unwrap(),
+    });
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    env.block.height += 1;
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::CancelUnstake { index: 2 };
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::WithdrawalNotFound {}) => (),
+        _ => panic!("expecting ContractError::WithdrawalNotFound"),
+    }
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::CancelUnstake { index: 0 };
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            Attribute::new("action", "cancel_unstake"),
+            Attribute::new("staker", "addr0000"),
+            Attribute::new("amount", "60"),
+        ]
+    );
+
+    assert_eq!(
+        from_binary::<WithdrawalsResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::Withdrawals {
+                    staker: "addr0000".to_string(),
+                },
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        WithdrawalsResponse {
+            claims: vec![WithdrawalInfoResponse {
+                amount: Uint128::from(20u128),
+                claimable_at: Expiration::AtHeight(env.block.height - 2 + 10),
+            }],
+        }
+    );
+
+    // rewards accrued before cancellation are settled with previous stake amount
+    assert_eq!(
+        from_binary::<StakerInfoResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::StakerInfo {
+                    staker: "addr0000".to_string(),
+                },
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        StakerInfoResponse {
+            staker: "addr0000".to_string(),
+            reward_index: Decimal::from_str("5").unwrap(),
+            unlocked_stake_amount: Uint128::from(80u128),
+            locked_stake_amount: Uint128::zero(),
+            pending_bro_reward: Uint128::from(100u128),
+            pending_bbro_reward: Uint128::from(42u128),
+            last_balance_update: env.block.height,
+            lockups: vec![],
+            auto_compound: false,
+        }
+    );
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::RestakeWithdrawals {};
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            Attribute::new("action", "restake_withdrawals"),
+            Attribute::new("staker", "addr0000"),
+            Attribute::new("amount", "20"),
+        ]
+    );
+
+    assert_eq!(
+        from_binary::<StateResponse>(
+            &query(deps.as_ref(), env.clone(), QueryMsg::State {}).unwrap()
+        )
+        .unwrap()
+        .total_stake_amount,
+        Uint128::from(100u128)
+    );
+
+    assert_eq!(
+        from_binary::<WithdrawalsResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::Withdrawals {
+                    staker: "addr0000".to_string(),
+                },
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        WithdrawalsResponse { claims: vec![] }
+    );
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::RestakeWithdrawals {};
+    match execute(deps.as_mut(), env, info, msg) {
+        Err(ContractError::NothingToRestake {}) => (),
+        _ => panic!("expecting ContractError::NothingToRestake"),
+    }
+}
+
+#[test]
+fn reward_recipient_and_claim_for() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        bro_token: "bro0000".to_string(),
+        rewards_pool_contract: "reward0000".to_string(),
+        bbro_minter_contract: "bbrominter0000".to_string(),
+        epoch_manager_contract: "epoch0000".to_string(),
+        community_bonding_contract: None,
+        unstake_period_blocks: 10,
+        min_staking_amount: Uint128::zero(),
+        min_lockup_period_epochs: 1,
+        max_lockup_period_epochs: 365,
+        base_rate: Decimal::from_str("0.0001").unwrap(),
+        linear_growth: Decimal::from_str("0.0005").unwrap(),
+        exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let mut env = mock_env();
+
+    let info = mock_info("bro0000", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Stake {
+            stake_type: StakeType::Unlocked {},
+        })
+        .unwrap(),
+    });
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let info = mock_info("bro0000", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "reward0000".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::DistributeReward {
+            distributed_at_block: env.block.height,
+        })
+        .unwrap(),
+    });
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::SetRewardRecipient {
+        recipient: Some("multisig0000".to_string()),
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            Attribute::new("action", "set_reward_recipient"),
+            Attribute::new("staker", "addr0000"),
+            Attribute::new("recipient", "multisig0000"),
+        ]
+    );
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::ApproveClaimOperator {
+        operator: "addr0000".to_string(),
+        expires: None,
+    };
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::CannotSetOwnAccount {}) => (),
+        _ => panic!("expecting ContractError::CannotSetOwnAccount"),
+    }
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::ApproveClaimOperator {
+        operator: "keeper0000".to_string(),
+        expires: Some(Expiration::AtHeight(env.block.height)),
+    };
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::InvalidExpiration {}) => (),
+        _ => panic!("expecting ContractError::InvalidExpiration"),
+    }
+
+    for operator in ["keeper0000", "keeper0001"] {
+        let info = mock_info("addr0000", &[]);
+        let msg = ExecuteMsg::ApproveClaimOperator {
+            operator: operator.to_string(),
+            expires: None,
+        };
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    }
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::RevokeClaimOperator {
+        operator: "keeper0001".to_string(),
+    };
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    assert_eq!(
+        from_binary::<ClaimPermissionsResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::ClaimPermissions {
+                    staker: "addr0000".to_string(),
+                },
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        ClaimPermissionsResponse {
+            reward_recipient: "multisig0000".to_string(),
+            operators: vec![ClaimOperatorResponse {
+                operator: "keeper0000".to_string(),
+                expires: Expiration::Never {},
+            }],
+        }
+    );
+
+    env.block.height += 1;
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::Unstake {
+        amount: Uint128::from(50u128),
+        unstake_type: None,
+    };
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    env.block.height += 11;
+    let info = mock_info("keeper0001", &[]);
+    let msg = ExecuteMsg::ClaimFor {
+        staker: "addr0000".to_string(),
+    };
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::Unauthorized {}) => (),
+        _ => panic!("expecting ContractError::Unauthorized"),
+    }
+
+    // bbro reward: 100 staked for 1 epoch and 50 staked for 11 epochs = 30 + 165
+    let info = mock_info("keeper0000", &[]);
+    let msg = ExecuteMsg::ClaimFor {
+        staker: "addr0000".to_string(),
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "bro0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "multisig0000".to_string(),
+                    amount: Uint128::from(150u128),
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "bbrominter0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&BbroMintMsg::Mint {
+                    recipient: "multisig0000".to_string(),
+                    amount: Uint128::from(195u128),
+                })
+                .unwrap(),
+            })),
+        ]
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            Attribute::new("action", "claim_for"),
+            Attribute::new("staker", "addr0000"),
+            Attribute::new("recipient", "multisig0000"),
+            Attribute::new("operator", "keeper0000"),
+            Attribute::new("bro_reward", "100"),
+            Attribute::new("bbro_reward", "195"),
+            Attribute::new("withdraw_amount", "50"),
+        ]
+    );
+
+    let info = mock_info("keeper0000", &[]);
+    let msg = ExecuteMsg::ClaimFor {
+        staker: "addr0000".to_string(),
+    };
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::NothingToClaim {}) => (),
+        _ => panic!("expecting ContractError::NothingToClaim"),
+    }
+
+    // reset recipient to staker address
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::SetRewardRecipient { recipient: None };
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    assert_eq!(
+        from_binary::<ClaimPermissionsResponse>(
+            &query(
+                deps.as_ref(),
+                env,
+                QueryMsg::ClaimPermissions {
+                    staker: "addr0000".to_string(),
+                },
+            )
+            .unwrap()
+        )
+        .unwrap()
+        .reward_recipient,
+        "addr0000".to_string()
+    );
+}
+
+#[test]
+fn transfer_position() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        bro_token: "bro0000".to_string(),
+        rewards_pool_contract: "reward0000".to_string(),
+        bbro_minter_contract: "bbrominter0000".to_string(),
+        epoch_manager_contract: "epoch0000".to_string(),
+        community_bonding_contract: None,
+        unstake_period_blocks: 10,
+        min_staking_amount: Uint128::zero(),
+        min_lockup_period_epochs: 1,
+        max_lockup_period_epochs: 365,
+        base_rate: Decimal::from_str("0.0001").unwrap(),
+        linear_growth: Decimal::from_str("0.0005").unwrap(),
+        exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let mut env = mock_env();
+
+    let stakes = vec![
+        (Uint128::from(100u128), StakeType::Unlocked {}),
+        (
+            Uint128::from(1000u128),
+            StakeType::Locked { epochs_locked: 10 },
+        ),
+        (
+            Uint128::from(500u128),
+            StakeType::Locked { epochs_locked: 4 },
+        ),
+    ];
+    for (amount, stake_type) in stakes {
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "addr0000".to_string(),
+            amount,
+            msg: to_binary(&Cw20HookMsg::Stake { stake_type }).unwrap(),
+        });
+        let info = mock_info("bro0000", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    }
+
+    let info = mock_info("bro0000", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "reward0000".to_string(),
+        amount: Uint128::from(160u128),
+        msg: to_binary(&Cw20HookMsg::DistributeReward {
+            distributed_at_block: env.block.height,
+        })
+        .unwrap(),
+    });
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    // pending reward asset is transferred to recipient as well
+    let native_asset_info = AssetInfo::NativeToken {
+        denom: "uusd".to_string(),
+    };
+    let info = mock_info("owner", &[]);
+    let msg = ExecuteMsg::AddRewardAsset {
+        asset_info: native_asset_info.clone(),
+    };
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let info = mock_info("funder0000", &coins(320, "uusd"));
+    let msg = ExecuteMsg::FundRewardAsset {
+        asset: Asset {
+            info: native_asset_info.clone(),
+            amount: Uint128::from(320u128),
+        },
+    };
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    env.block.height += 1;
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::TransferPosition {
+        recipient: "addr0000".to_string(),
+        lockup_indices: vec![0],
+        include_unlocked: true,
+    };
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::InvalidTransferRecipient {}) => (),
+        _ => panic!("expecting ContractError::InvalidTransferRecipient"),
+    }
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::TransferPosition {
+        recipient: "addr0001".to_string(),
+        lockup_indices: vec![0, 2],
+        include_unlocked: true,
+    };
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::LockupNotFound {}) => (),
+        _ => panic!("expecting ContractError::LockupNotFound"),
+    }
+
+    let info = mock_info("addr0002", &[]);
+    let msg = ExecuteMsg::TransferPosition {
+        recipient: "addr0001".to_string(),
+        lockup_indices: vec![],
+        include_unlocked: true,
+    };
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::NothingToTransfer {}) => (),
+        _ => panic!("expecting ContractError::NothingToTransfer"),
+    }
+
+    // bbro reward accrued by sender: 1600 staked for 1 epoch = 480
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::TransferPosition {
+        recipient: "addr0001".to_string(),
+        lockup_indices: vec![0],
+        include_unlocked: true,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            Attribute::new("action", "transfer_position"),
+            Attribute::new("staker", "addr0000"),
+            Attribute::new("recipient", "addr0001"),
+            Attribute::new("locked_amount", "1000"),
+            Attribute::new("unlocked_amount", "100"),
+            Attribute::new("bro_reward", "160"),
+            Attribute::new("bbro_reward", "480"),
+            Attribute::new("asset_reward", "320uusd"),
+        ]
+    );
+
+    let query_pending_asset_reward = |deps: &cosmwasm_std::OwnedDeps<_, _, _>, staker: &str| {
+        from_binary::<PendingRewardsResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::PendingRewards {
+                    staker: staker.to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+        .rewards[1]
+            .clone()
+    };
+    assert_eq!(
+        query_pending_asset_reward(&deps, "addr0000"),
+        Asset {
+            info: native_asset_info.clone(),
+            amount: Uint128::zero(),
+        }
+    );
+    assert_eq!(
+        query_pending_asset_reward(&deps, "addr0001"),
+        Asset {
+            info: native_asset_info,
+            amount: Uint128::from(320u128),
+        }
+    );
+
+    assert_eq!(
+        from_binary::<StakerInfoResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::StakerInfo {
+                    staker: "addr0000".to_string(),
+                },
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        StakerInfoResponse {
+            staker: "addr0000".to_string(),
+            reward_index: Decimal::from_str("0.1").unwrap(),
+            unlocked_stake_amount: Uint128::zero(),
+            locked_stake_amount: Uint128::from(500u128),
+            pending_bro_reward: Uint128::zero(),
+            pending_bbro_reward: Uint128::zero(),
+            last_balance_update: env.block.height,
+            lockups: vec![LockupInfoResponse {
+                amount: Uint128::from(500u128),
+                locked_at_block: env.block.height - 1,
+                epochs_locked: 4,
+            }],
+            auto_compound: false,
+        }
+    );
+
+    assert_eq!(
+        from_binary::<StakerInfoResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::StakerInfo {
+                    staker: "addr0001".to_string(),
+                },
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        StakerInfoResponse {
+            staker: "addr0001".to_string(),
+            reward_index: Decimal::from_str("0.1").unwrap(),
+            unlocked_stake_amount: Uint128::from(100u128),
+            locked_stake_amount: Uint128::from(1000u128),
+            pending_bro_reward: Uint128::from(160u128),
+            pending_bbro_reward: Uint128::from(480u128),
+            last_balance_update: env.block.height,
+            lockups: vec![LockupInfoResponse {
+                amount: Uint128::from(1000u128),
+                locked_at_block: env.block.height - 1,
+                epochs_locked: 10,
+            }],
+            auto_compound: false,
+        }
+    );
+
+    // total stake amount is not changed by transfer
+    assert_eq!(
+        from_binary::<StateResponse>(&query(deps.as_ref(), env, QueryMsg::State {}).unwrap())
+            .unwrap()
+            .total_stake_amount,
+        Uint128::from(1600u128)
+    );
+}
+
+#[test]
+fn reward_history_and_estimated_apr() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        bro_token: "bro0000".to_string(),
+        rewards_pool_contract: "reward0000".to_string(),
+        bbro_minter_contract: "bbrominter0000".to_string(),
+        epoch_manager_contract: "epoch0000".to_string(),
+        community_bonding_contract: None,
+        unstake_period_blocks: 10,
+        min_staking_amount: Uint128::zero(),
+        min_lockup_period_epochs: 1,
+        max_lockup_period_epochs: 365,
+        base_rate: Decimal::from_str("0.0001").unwrap(),
+        linear_growth: Decimal::from_str("0.0005").unwrap(),
+        exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let mut env = mock_env();
+
+    let info = mock_info("bro0000", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(1000u128),
+        msg: to_binary(&Cw20HookMsg::Stake {
+            stake_type: StakeType::Unlocked {},
+        })
+        .unwrap(),
+    });
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    // distributions at the same block are accumulated in one snapshot,
+    // snapshot keeps global reward index streamed up to distribution block
+    let distributions = vec![(12345u64, 100u128), (12346, 200), (12346, 50)];
+    for (distributed_at_block, amount) in distributions {
+        env.block.height = distributed_at_block;
+        let info = mock_info("bro0000", &[]);
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "reward0000".to_string(),
+            amount: Uint128::from(amount),
+            msg: to_binary(&Cw20HookMsg::DistributeReward {
+                distributed_at_block,
+            })
+            .unwrap(),
+        });
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    }
+
+    assert_eq!(
+        from_binary::<RewardHistoryResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::RewardHistory {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        RewardHistoryResponse {
+            snapshots: vec![
+                RewardSnapshotResponse {
+                    distributed_at_block: 12345,
+                    global_reward_index: Decimal::zero(),
+                    total_stake_amount: Uint128::from(1000u128),
+                    distributed_amount: Uint128::from(100u128),
+                },
+                RewardSnapshotResponse {
+                    distributed_at_block: 12346,
+                    global_reward_index: Decimal::from_str("0.1").unwrap(),
+                    total_stake_amount: Uint128::from(1000u128),
+                    distributed_amount: Uint128::from(250u128),
+                },
+            ],
+        }
+    );
+
+    assert_eq!(
+        from_binary::<RewardHistoryResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::RewardHistory {
+                    start_after: Some(12345),
+                    limit: Some(1),
+                },
+            )
+            .unwrap()
+        )
+        .unwrap()
+        .snapshots
+        .into_iter()
+        .map(|s| s.distributed_at_block)
+        .collect::<Vec<u64>>(),
+        vec![12346]
+    );
+
+    match query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::EstimatedApr { window_epochs: 0 },
+    ) {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "window_epochs must be greater than zero")
+        }
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    // only distribution at block 12346 is within window:
+    // 250 / 1000 * 10 blocks per year / 2 blocks = 1.25
+    env.block.height = 12347;
+    assert_eq!(
+        from_binary::<EstimatedAprResponse>(
+            &query(
+                deps.as_ref(),
+                env,
+                QueryMsg::EstimatedApr { window_epochs: 2 },
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        EstimatedAprResponse {
+            apr: Decimal::from_str("1.25").unwrap(),
+            distributed_amount: Uint128::from(250u128),
+            window_blocks: 2,
+        }
+    );
+}
+
+#[test]
+fn emergency_withdraw() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        bro_token: "bro0000".to_string(),
+        rewards_pool_contract: "reward0000".to_string(),
+        bbro_minter_contract: "bbrominter0000".to_string(),
+        epoch_manager_contract: "epoch0000".to_string(),
+        community_bonding_contract: None,
+        unstake_period_blocks: 10,
+        min_staking_amount: Uint128::zero(),
+        min_lockup_period_epochs: 1,
+        max_lockup_period_epochs: 365,
+        base_rate: Decimal::from_str("0.0001").unwrap(),
+        linear_growth: Decimal::from_str("0.0005").unwrap(),
+        exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let mut env = mock_env();
+
+    let stakes = vec![
+        (Uint128::from(100u128), StakeType::Unlocked {}),
+        (
+            Uint128::from(200u128),
+            StakeType::Locked { epochs_locked: 10 },
+        ),
+    ];
+    for (amount, stake_type) in stakes {
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "addr0000".to_string(),
+            amount,
+            msg: to_binary(&Cw20HookMsg::Stake { stake_type }).unwrap(),
+        });
+        let info = mock_info("bro0000", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    }
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::Unstake {
+        amount: Uint128::from(50u128),
+        unstake_type: None,
+    };
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let info = mock_info("bro0000", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "reward0000".to_string(),
+        amount: Uint128::from(25u128),
+        msg: to_binary(&Cw20HookMsg::DistributeReward {
+            distributed_at_block: env.block.height,
+        })
+        .unwrap(),
+    });
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let native_asset_info = AssetInfo::NativeToken {
+        denom: "uusd".to_string(),
+    };
+    let info = mock_info("owner", &[]);
+    let msg = ExecuteMsg::AddRewardAsset {
+        asset_info: native_asset_info.clone(),
+    };
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let info = mock_info("funder0000", &coins(250, "uusd"));
+    let msg = ExecuteMsg::FundRewardAsset {
+        asset: Asset {
+            info: native_asset_info.clone(),
+            amount: Uint128::from(250u128),
+        },
+    };
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    env.block.height += 1;
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::EmergencyWithdraw {};
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::EmergencyModeIsNotEnabled {}) => (),
+        _ => panic!("expecting ContractError::EmergencyModeIsNotEnabled"),
+    }
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::EnableEmergencyMode {};
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::Unauthorized {}) => (),
+        _ => panic!("expecting ContractError::Unauthorized"),
+    }
+
+    let info = mock_info("owner", &[]);
+    let msg = ExecuteMsg::EnableEmergencyMode {};
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            Attribute::new("action", "enable_emergency_mode"),
+            Attribute::new("emergency_mode", "true"),
+        ]
+    );
+
+    // emergency mode is one-way
+    let info = mock_info("owner", &[]);
+    let msg = ExecuteMsg::EnableEmergencyMode {};
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::EmergencyModeIsEnabled {}) => (),
+        _ => panic!("expecting ContractError::EmergencyModeIsEnabled"),
+    }
+
+    assert!(
+        from_binary::<ConfigResponse>(
+            &query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap()
+        )
+        .unwrap()
+        .emergency_mode
+    );
+
+    let info = mock_info("bro0000", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Stake {
+            stake_type: StakeType::Unlocked {},
+        })
+        .unwrap(),
+    });
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::EmergencyModeIsEnabled {}) => (),
+        _ => panic!("expecting ContractError::EmergencyModeIsEnabled"),
+    }
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::LockupStaked {
+        amount: Uint128::from(50u128),
+        epochs_locked: 10,
+    };
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::EmergencyModeIsEnabled {}) => (),
+        _ => panic!("expecting ContractError::EmergencyModeIsEnabled"),
+    }
+
+    // emergency withdrawal is available even if contract is paused
+    let msg = ExecuteMsg::UpdateConfig {
+        paused: Some(true),
+        unstake_period_blocks: None,
+        min_staking_amount: None,
+        min_lockup_period_epochs: None,
+        max_lockup_period_epochs: None,
+        base_rate: None,
+        linear_growth: None,
+        exponential_growth: None,
+        community_bonding_contract: None,
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: None,
+    };
+    let info = mock_info("owner", &[]);
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::EmergencyWithdraw {};
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "bro0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "addr0000".to_string(),
+                    amount: Uint128::from(300u128),
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "bro0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "reward0000".to_string(),
+                    amount: Uint128::from(25u128),
+                })
+                .unwrap(),
+            })),
+        ]
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            Attribute::new("action", "emergency_withdraw"),
+            Attribute::new("staker", "addr0000"),
+            Attribute::new("amount", "300"),
+            Attribute::new("unlocked_amount", "50"),
+            Attribute::new("locked_amount", "200"),
+            Attribute::new("withdrawals_amount", "50"),
+            Attribute::new("forfeited_bro_reward", "25"),
+            Attribute::new("forfeited_asset_reward", "250uusd"),
+        ]
+    );
+
+    // forfeited asset rewards are not claimable anymore
+    assert_eq!(
+        from_binary::<PendingRewardsResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::PendingRewards {
+                    staker: "addr0000".to_string(),
+                },
+            )
+            .unwrap()
+        )
+        .unwrap()
+        .rewards,
+        vec![
+            Asset {
+                info: AssetInfo::Token {
+                    contract_addr: Addr::unchecked("bro0000"),
+                },
+                amount: Uint128::zero(),
+            },
+            Asset {
+                info: native_asset_info,
+                amount: Uint128::zero(),
+            },
+        ]
+    );
+
+    assert_eq!(
+        from_binary::<StateResponse>(
+            &query(deps.as_ref(), env.clone(), QueryMsg::State {}).unwrap()
+        )
+        .unwrap()
+        .total_stake_amount,
+        Uint128::zero()
+    );
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::EmergencyWithdraw {};
+    match execute(deps.as_mut(), env, info, msg) {
+        Err(ContractError::NothingToClaim {}) => (),
+        _ => panic!("expecting ContractError::NothingToClaim"),
+    }
+}
+
+#[test]
+fn simulate_rewards_and_lockup() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        bro_token: "bro0000".to_string(),
+        rewards_pool_contract: "reward0000".to_string(),
+        bbro_minter_contract: "bbrominter0000".to_string(),
+        epoch_manager_contract: "epoch0000".to_string(),
+        community_bonding_contract: None,
+        unstake_period_blocks: 10,
+        min_staking_amount: Uint128::zero(),
+        min_lockup_period_epochs: 1,
+        max_lockup_period_epochs: 365,
+        base_rate: Decimal::from_str("0.0001").unwrap(),
+        linear_growth: Decimal::from_str("0.0005").unwrap(),
+        exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let env = mock_env();
+
+    let stakes = vec![
+        (Uint128::from(1000u128), StakeType::Unlocked {}),
+        (
+            Uint128::from(1000u128),
+            StakeType::Locked { epochs_locked: 4 },
+        ),
+    ];
+    for (amount, stake_type) in stakes {
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "addr0000".to_string(),
+            amount,
+            msg: to_binary(&Cw20HookMsg::Stake { stake_type }).unwrap(),
+        });
+        let info = mock_info("bro0000", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    }
+
+    let info = mock_info("bro0000", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "reward0000".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::DistributeReward {
+            distributed_at_block: env.block.height,
+        })
+        .unwrap(),
+    });
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    match query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::SimulateRewards {
+            staker: "addr0000".to_string(),
+            at_block: Some(env.block.height - 1),
+        },
+    ) {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(
+                msg,
+                "at_block must be greater than or equal to current block"
+            )
+        }
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    // bbro reward for 5 epochs: 2000 / 10 * 3 * 5 = 3000, lockup for 4 epochs is expired
+    assert_eq!(
+        from_binary::<SimulateRewardsResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::SimulateRewards {
+                    staker: "addr0000".to_string(),
+                    at_block: Some(env.block.height + 5),
+                },
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        SimulateRewardsResponse {
+            staker: "addr0000".to_string(),
+            at_block: env.block.height + 5,
+            pending_bro_reward: Uint128::from(100u128),
+            pending_bbro_reward: Uint128::from(3000u128),
+            unlocked_stake_amount: Uint128::from(2000u128),
+            locked_stake_amount: Uint128::zero(),
+            expired_lockups_amount: Uint128::from(1000u128),
+        }
+    );
+
+    // distributed bro reward is not streamed yet at current block
+    assert_eq!(
+        from_binary::<SimulateRewardsResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::SimulateRewards {
+                    staker: "addr0000".to_string(),
+                    at_block: None,
+                },
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        SimulateRewardsResponse {
+            staker: "addr0000".to_string(),
+            at_block: env.block.height,
+            pending_bro_reward: Uint128::zero(),
+            pending_bbro_reward: Uint128::zero(),
+            unlocked_stake_amount: Uint128::from(1000u128),
+            locked_stake_amount: Uint128::from(1000u128),
+            expired_lockups_amount: Uint128::zero(),
+        }
+    );
+
+    match query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::SimulateLockup {
+            amount: Uint128::from(1_000000u128),
+            epochs_locked: 366,
+        },
+    ) {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "Invalid lockup period"),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    // 1000000 * 0.00535 = 5350
+    assert_eq!(
+        from_binary::<SimulateLockupResponse>(
+            &query(
+                deps.as_ref(),
+                env,
+                QueryMsg::SimulateLockup {
+                    amount: Uint128::from(1_000000u128),
+                    epochs_locked: 10,
+                },
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        SimulateLockupResponse {
+            bbro_premium_lockup_reward: Uint128::from(5350u128),
+        }
+    );
+}
+
+#[test]
+fn boost_weighting() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        bro_token: "bro0000".to_string(),
+        rewards_pool_contract: "reward0000".to_string(),
+        bbro_minter_contract: "bbrominter0000".to_string(),
+        epoch_manager_contract: "epoch0000".to_string(),
+        community_bonding_contract: None,
+        unstake_period_blocks: 10,
+        min_staking_amount: Uint128::zero(),
+        min_lockup_period_epochs: 1,
+        max_lockup_period_epochs: 365,
+        base_rate: Decimal::from_str("0.0001").unwrap(),
+        linear_growth: Decimal::from_str("0.0005").unwrap(),
+        exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let mut env = mock_env();
+
+    // boost is not enabled
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::UpdateBoost {
+        stakers: vec!["addr0000".to_string()],
+    };
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::BoostIsNotEnabled {}) => (),
+        _ => panic!("expecting ContractError::BoostIsNotEnabled"),
+    }
+
+    let update_config_msg = |max_boost: &str| ExecuteMsg::UpdateConfig {
+        paused: None,
+        unstake_period_blocks: None,
+        min_staking_amount: None,
+        min_lockup_period_epochs: None,
+        max_lockup_period_epochs: None,
+        base_rate: None,
+        linear_growth: None,
+        exponential_growth: None,
+        community_bonding_contract: None,
+        early_unlock_penalty: None,
+        boost: Some(Box::new(BoostParams {
+            bbro_token: MOCK_BBRO_TOKEN_ADDR.to_string(),
+            max_boost: Decimal::from_str(max_boost).unwrap(),
+        })),
+        instant_unstake: None,
+        staker_history_limit: None,
+    };
+
+    let info = mock_info("owner", &[]);
+    match execute(deps.as_mut(), env.clone(), info, update_config_msg("0.5")) {
+        Err(ContractError::Std(StdError::GenericErr { msg, .. })) => {
+            assert_eq!(msg, "max_boost must be greater than or equal to 1.0")
+        }
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    let info = mock_info("owner", &[]);
+    let _res = execute(deps.as_mut(), env.clone(), info, update_config_msg("2.5")).unwrap();
+
+    assert_eq!(
+        from_binary::<ConfigResponse>(
+            &query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap()
+        )
+        .unwrap()
+        .boost,
+        Some(BoostParams {
+            bbro_token: MOCK_BBRO_TOKEN_ADDR.to_string(),
+            max_boost: Decimal::from_str("2.5").unwrap(),
+        })
+    );
+
+    deps.querier.with_token_balances(&[(
+        &MOCK_BBRO_TOKEN_ADDR.to_string(),
+        &[(&"addr0000".to_string(), &Uint128::from(100u128))],
+    )]);
+
+    for staker in ["addr0000", "addr0001"] {
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: staker.to_string(),
+            amount: Uint128::from(1000u128),
+            msg: to_binary(&Cw20HookMsg::Stake {
+                stake_type: StakeType::Unlocked {},
+            })
+            .unwrap(),
+        });
+        let info = mock_info("bro0000", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    }
+
+    // addr0000 holds whole bbro supply: 1000 * 2.5 = 2500
+    // addr0001 has no bbro: 1000 * 1 = 1000
+    let query_boost_info = |deps: &cosmwasm_std::OwnedDeps<_, _, _>, staker: &str| {
+        from_binary::<BoostInfoResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::BoostInfo {
+                    staker: staker.to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    };
+
+    assert_eq!(
+        query_boost_info(&deps, "addr0000"),
+        BoostInfoResponse {
+            staker: "addr0000".to_string(),
+            boost_multiplier: Decimal::from_str("2.5").unwrap(),
+            working_balance: Uint128::from(2500u128),
+            total_working_balance: Uint128::from(3500u128),
+        }
+    );
+    assert_eq!(
+        query_boost_info(&deps, "addr0001"),
+        BoostInfoResponse {
+            staker: "addr0001".to_string(),
+            boost_multiplier: Decimal::one(),
+            working_balance: Uint128::from(1000u128),
+            total_working_balance: Uint128::from(3500u128),
+        }
+    );
+
+    let info = mock_info("bro0000", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "reward0000".to_string(),
+        amount: Uint128::from(350u128),
+        msg: to_binary(&Cw20HookMsg::DistributeReward {
+            distributed_at_block: env.block.height,
+        })
+        .unwrap(),
+    });
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    env.block.height += 1;
+
+    let query_pending_bro_reward =
+        |deps: &cosmwasm_std::OwnedDeps<_, _, _>, env: Env, staker: &str| {
+            from_binary::<StakerInfoResponse>(
+                &query(
+                    deps.as_ref(),
+                    env,
+                    QueryMsg::StakerInfo {
+                        staker: staker.to_string(),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+            .pending_bro_reward
+        };
+
+    assert_eq!(
+        query_pending_bro_reward(&deps, env.clone(), "addr0000"),
+        Uint128::from(250u128)
+    );
+    assert_eq!(
+        query_pending_bro_reward(&deps, env.clone(), "addr0001"),
+        Uint128::from(100u128)
+    );
+
+    deps.querier.with_token_balances(&[(
+        &MOCK_BBRO_TOKEN_ADDR.to_string(),
+        &[
+            (&"addr0000".to_string(), &Uint128::from(25u128)),
+            (&"addr0001".to_string(), &Uint128::from(75u128)),
+        ],
+    )]);
+
+    let info = mock_info("addr0002", &[]);
+    let msg = ExecuteMsg::UpdateBoost {
+        stakers: vec![
+            "addr0000".to_string(),
+            "addr0001".to_string(),
+            "addr0002".to_string(),
+        ],
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            Attribute::new("action", "update_boost"),
+            Attribute::new("updated_stakers", "2"),
+        ]
+    );
+
+    // addr0000: 1 + 1.5 * min(0.25 / 0.5, 1) = 1.75
+    // addr0001: 1 + 1.5 * min(0.75 / 0.5, 1) = 2.5
+    assert_eq!(
+        query_boost_info(&deps, "addr0000"),
+        BoostInfoResponse {
+            staker: "addr0000".to_string(),
+            boost_multiplier: Decimal::from_str("1.75").unwrap(),
+            working_balance: Uint128::from(1750u128),
+            total_working_balance: Uint128::from(4250u128),
+        }
+    );
+    assert_eq!(
+        query_boost_info(&deps, "addr0001"),
+        BoostInfoResponse {
+            staker: "addr0001".to_string(),
+            boost_multiplier: Decimal::from_str("2.5").unwrap(),
+            working_balance: Uint128::from(2500u128),
+            total_working_balance: Uint128::from(4250u128),
+        }
+    );
+
+    // rewards accrued before update are kept
+    assert_eq!(
+        query_pending_bro_reward(&deps, env.clone(), "addr0000"),
+        Uint128::from(250u128)
+    );
+    assert_eq!(
+        query_pending_bro_reward(&deps, env.clone(), "addr0001"),
+        Uint128::from(100u128)
+    );
+}
+
+#[test]
+fn boost_checkpoint_on_stake_increase() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        bro_token: "bro0000".to_string(),
+        rewards_pool_contract: "reward0000".to_string(),
+        bbro_minter_contract: "bbrominter0000".to_string(),
+        epoch_manager_contract: "epoch0000".to_string(),
+        community_bonding_contract: None,
+        unstake_period_blocks: 10,
+        min_staking_amount: Uint128::zero(),
+        min_lockup_period_epochs: 1,
+        max_lockup_period_epochs: 365,
+        base_rate: Decimal::from_str("0.0001").unwrap(),
+        linear_growth: Decimal::from_str("0.0005").unwrap(),
+        exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: Some(BoostParams {
+            bbro_token: MOCK_BBRO_TOKEN_ADDR.to_string(),
+            max_boost: Decimal::from_str("2.5").unwrap(),
+        }),
+        instant_unstake: None,
+        staker_history_limit: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let env = mock_env();
+
+    deps.querier.with_token_balances(&[(
+        &MOCK_BBRO_TOKEN_ADDR.to_string(),
+        &[
+            (&"addr0000".to_string(), &Uint128::from(20u128)),
+            (&"addr0001".to_string(), &Uint128::from(30u128)),
+            (&"addr0002".to_string(), &Uint128::from(50u128)),
+        ],
+    )]);
+
+    let stake_msg = |staker: &str, amount: u128| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: staker.to_string(),
+            amount: Uint128::from(amount),
+            msg: to_binary(&Cw20HookMsg::Stake {
+                stake_type: StakeType::Unlocked {},
+            })
+            .unwrap(),
+        })
+    };
+    let query_boost_multiplier = |deps: &cosmwasm_std::OwnedDeps<_, _, _>, staker: &str| {
+        from_binary::<BoostInfoResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::BoostInfo {
+                    staker: staker.to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+        .boost_multiplier
+    };
+
+    for staker in ["addr0001", "addr0000"] {
+        let info = mock_info("bro0000", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), info, stake_msg(staker, 1000)).unwrap();
+    }
+
+    // addr0000: 1 + 1.5 * min(0.2 / 0.5, 1) = 1.6
+    assert_eq!(
+        query_boost_multiplier(&deps, "addr0000"),
+        Decimal::from_str("1.6").unwrap()
+    );
+
+    let unstake_msg = ExecuteMsg::Unstake {
+        amount: Uint128::from(900u128),
+        unstake_type: None,
+    };
+
+    // small position gets max boost: 1 + 1.5 * min(0.2 / (100 / 1100), 1) = 2.5
+    let info = mock_info("addr0000", &[]);
+    let _res = execute(deps.as_mut(), env.clone(), info, unstake_msg.clone()).unwrap();
+    assert_eq!(
+        query_boost_multiplier(&deps, "addr0000"),
+        Decimal::from_str("2.5").unwrap()
+    );
+
+    // boost is recalculated when unstaked amount is staked back
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::CancelUnstake { index: 0 };
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        query_boost_multiplier(&deps, "addr0000"),
+        Decimal::from_str("1.6").unwrap()
+    );
+
+    let info = mock_info("addr0000", &[]);
+    let _res = execute(deps.as_mut(), env.clone(), info, unstake_msg).unwrap();
+    assert_eq!(
+        query_boost_multiplier(&deps, "addr0000"),
+        Decimal::from_str("2.5").unwrap()
+    );
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::RestakeWithdrawals {};
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        query_boost_multiplier(&deps, "addr0000"),
+        Decimal::from_str("1.6").unwrap()
+    );
+
+    // recipient: 1 + 1.5 * min(0.5 / (1000 / 3000), 1) = 2.5
+    let info = mock_info("bro0000", &[]);
+    let _res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        stake_msg("addr0002", 1000),
+    )
+    .unwrap();
+    assert_eq!(
+        query_boost_multiplier(&deps, "addr0002"),
+        Decimal::from_str("2.5").unwrap()
+    );
+
+    // transferred position is boosted using recipient's new stake amount:
+    // 1 + 1.5 * min(0.5 / (2000 / 3000), 1) = 2.125
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::TransferPosition {
+        recipient: "addr0002".to_string(),
+        lockup_indices: vec![],
+        include_unlocked: true,
+    };
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        from_binary::<BoostInfoResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::BoostInfo {
+                    staker: "addr0002".to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap(),
+        BoostInfoResponse {
+            staker: "addr0002".to_string(),
+            boost_multiplier: Decimal::from_str("2.125").unwrap(),
+            working_balance: Uint128::from(4250u128),
+            total_working_balance: Uint128::from(5700u128),
+        }
+    );
+
+    // boost is recalculated when rewards are claimed by operator
+    let info = mock_info("addr0001", &[]);
+    let msg = ExecuteMsg::ApproveClaimOperator {
+        operator: "keeper0000".to_string(),
+        expires: None,
+    };
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "reward0000".to_string(),
+        amount: Uint128::from(3000u128),
+        msg: to_binary(&Cw20HookMsg::DistributeReward {
+            distributed_at_block: env.block.height,
+        })
+        .unwrap(),
+    });
+    let info = mock_info("bro0000", &[]);
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    deps.querier.with_token_balances(&[(
+        &MOCK_BBRO_TOKEN_ADDR.to_string(),
+        &[
+            (&"addr0000".to_string(), &Uint128::from(20u128)),
+            (&"addr0001".to_string(), &Uint128::from(10u128)),
+            (&"addr0002".to_string(), &Uint128::from(50u128)),
+        ],
+    )]);
+
+    // addr0001: 1 + 1.5 * min(0.125 / (1000 / 3000), 1) = 1.5625
+    let mut env = env;
+    env.block.height += 1;
+    let info = mock_info("keeper0000", &[]);
+    let msg = ExecuteMsg::ClaimFor {
+        staker: "addr0001".to_string(),
+    };
+    let _res = execute(deps.as_mut(), env, info, msg).unwrap();
+    assert_eq!(
+        query_boost_multiplier(&deps, "addr0001"),
+        Decimal::from_str("1.5625").unwrap()
+    );
+}
+
+#[test]
+fn reward_assets() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        bro_token: "bro0000".to_string(),
+        rewards_pool_contract: "reward0000".to_string(),
+        bbro_minter_contract: "bbrominter0000".to_string(),
+        epoch_manager_contract: "epoch0000".to_string(),
+        community_bonding_contract: None,
+        unstake_period_blocks: 10,
+        min_staking_amount: Uint128::zero(),
+        min_lockup_period_epochs: 1,
+        max_lockup_period_epochs: 365,
+        base_rate: Decimal::from_str("0.0001").unwrap(),
+        linear_growth: Decimal::from_str("0.0005").unwrap(),
+        exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let env = mock_env();
+
+    let native_asset_info = AssetInfo::NativeToken {
+        denom: "uusd".to_string(),
+    };
+    let token_asset_info = AssetInfo::Token {
+        contract_addr: Addr::unchecked("partner0000"),
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::AddRewardAsset {
+        asset_info: native_asset_info.clone(),
+    };
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::Unauthorized {}) => (),
+        _ => panic!("expecting ContractError::Unauthorized"),
+    }
+
+    let info = mock_info("owner", &[]);
+    let msg = ExecuteMsg::AddRewardAsset {
+        asset_info: AssetInfo::Token {
+            contract_addr: Addr::unchecked("bro0000"),
+        },
+    };
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::InvalidRewardAsset {}) => (),
+        _ => panic!("expecting ContractError::InvalidRewardAsset"),
+    }
+
+    for asset_info in [native_asset_info.clone(), token_asset_info.clone()] {
+        let info = mock_info("owner", &[]);
+        let msg = ExecuteMsg::AddRewardAsset { asset_info };
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    }
+
+    let info = mock_info("owner", &[]);
+    let msg = ExecuteMsg::AddRewardAsset {
+        asset_info: native_asset_info.clone(),
+    };
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::RewardAssetAlreadyExists {}) => (),
+        _ => panic!("expecting ContractError::RewardAssetAlreadyExists"),
+    }
+
+    let fund_native_msg = |amount: u128| ExecuteMsg::FundRewardAsset {
+        asset: Asset {
+            info: AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            amount: Uint128::from(amount),
+        },
+    };
+
+    let info = mock_info("funder0000", &coins(400, "uusd"));
+    match execute(deps.as_mut(), env.clone(), info, fund_native_msg(400)) {
+        Err(ContractError::NothingStaked {}) => (),
+        _ => panic!("expecting ContractError::NothingStaked"),
+    }
+
+    for (staker, amount) in [("addr0000", 1000u128), ("addr0001", 3000u128)] {
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: staker.to_string(),
+            amount: Uint128::from(amount),
+            msg: to_binary(&Cw20HookMsg::Stake {
+                stake_type: StakeType::Unlocked {},
+            })
+            .unwrap(),
+        });
+        let info = mock_info("bro0000", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    }
+
+    let info = mock_info("funder0000", &coins(300, "uusd"));
+    match execute(deps.as_mut(), env.clone(), info, fund_native_msg(400)) {
+        Err(ContractError::Std(StdError::GenericErr { msg, .. })) => assert_eq!(
+            msg,
+            "Native token balance mismatch between the argument and the transferred"
+        ),
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    let info = mock_info("funder0000", &[]);
+    let msg = ExecuteMsg::FundRewardAsset {
+        asset: Asset {
+            info: token_asset_info.clone(),
+            amount: Uint128::from(800u128),
+        },
+    };
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::InvalidRewardAsset {}) => (),
+        _ => panic!("expecting ContractError::InvalidRewardAsset"),
+    }
+
+    let info = mock_info("funder0000", &coins(400, "uusd"));
+    let res = execute(deps.as_mut(), env.clone(), info, fund_native_msg(400)).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            Attribute::new("action", "fund_reward_asset"),
+            Attribute::new("sender", "funder0000"),
+            Attribute::new("asset", "400uusd"),
+        ]
+    );
+
+    let info = mock_info("other0000", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "funder0000".to_string(),
+        amount: Uint128::from(800u128),
+        msg: to_binary(&Cw20HookMsg::FundRewardAsset {}).unwrap(),
+    });
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::RewardAssetNotFound {}) => (),
+        _ => panic!("expecting ContractError::RewardAssetNotFound"),
+    }
+
+    let info = mock_info("partner0000", &[]);
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "funder0000".to_string(),
+        amount: Uint128::from(800u128),
+        msg: to_binary(&Cw20HookMsg::FundRewardAsset {}).unwrap(),
+    });
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let query_pending_rewards = |deps: &cosmwasm_std::OwnedDeps<_, _, _>, staker: &str| {
+        from_binary::<PendingRewardsResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::PendingRewards {
+                    staker: staker.to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+        .rewards
+    };
+
+    let bro_asset_info = AssetInfo::Token {
+        contract_addr: Addr::unchecked("bro0000"),
+    };
+    assert_eq!(
+        query_pending_rewards(&deps, "addr0000"),
+        vec![
+            Asset {
+                info: bro_asset_info.clone(),
+                amount: Uint128::zero(),
+            },
+            Asset {
+                info: token_asset_info.clone(),
+                amount: Uint128::from(200u128),
+            },
+            Asset {
+                info: native_asset_info.clone(),
+                amount: Uint128::from(100u128),
+            },
+        ]
+    );
+
+    // rewards accrued before stake change are kept
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::Unstake {
+        amount: Uint128::from(500u128),
+        unstake_type: None,
+    };
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let info = mock_info("funder0000", &coins(350, "uusd"));
+    let _res = execute(deps.as_mut(), env.clone(), info, fund_native_msg(350)).unwrap();
+
+    assert_eq!(
+        query_pending_rewards(&deps, "addr0001"),
+        vec![
+            Asset {
+                info: bro_asset_info.clone(),
+                amount: Uint128::zero(),
+            },
+            Asset {
+                info: token_asset_info.clone(),
+                amount: Uint128::from(600u128),
+            },
+            Asset {
+                info: native_asset_info.clone(),
+                amount: Uint128::from(600u128),
+            },
+        ]
+    );
+
+    let info = mock_info("addr0000", &[]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        ExecuteMsg::ClaimAllRewards {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "partner0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "addr0000".to_string(),
+                    amount: Uint128::from(200u128),
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "addr0000".to_string(),
+                amount: coins(150, "uusd"),
+            })),
+        ]
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            Attribute::new("action", "claim_all_rewards"),
+            Attribute::new("staker", "addr0000"),
+            Attribute::new("reward", "200partner0000"),
+            Attribute::new("reward", "150uusd"),
+            Attribute::new("recipient", "addr0000"),
+        ]
+    );
+
+    let info = mock_info("addr0000", &[]);
+    match execute(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        ExecuteMsg::ClaimAllRewards {},
+    ) {
+        Err(ContractError::NothingToClaim {}) => (),
+        _ => panic!("expecting ContractError::NothingToClaim"),
+    }
+
+    assert_eq!(
+        from_binary::<RewardAssetsResponse>(
+            &query(deps.as_ref(), env, QueryMsg::RewardAssets {}).unwrap()
+        )
+        .unwrap(),
+        RewardAssetsResponse {
+            reward_assets: vec![
+                RewardAssetResponse {
+                    asset_info: token_asset_info,
+                    global_reward_index: Decimal::from_str("0.2").unwrap(),
+                    total_distributed_amount: Uint128::from(800u128),
+                },
+                RewardAssetResponse {
+                    asset_info: native_asset_info,
+                    global_reward_index: Decimal::from_str("0.2").unwrap(),
+                    total_distributed_amount: Uint128::from(750u128),
+                },
+            ],
+        }
+    );
+}
+
+#[test]
+fn reward_streaming() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_epoch(10);
+
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        bro_token: "bro0000".to_string(),
+        rewards_pool_contract: "reward0000".to_string(),
+        bbro_minter_contract: "bbrominter0000".to_string(),
+        epoch_manager_contract: "epoch0000".to_string(),
+        community_bonding_contract: None,
+        unstake_period_blocks: 10,
+        min_staking_amount: Uint128::zero(),
+        min_lockup_period_epochs: 1,
+        max_lockup_period_epochs: 365,
+        base_rate: Decimal::from_str("0.0001").unwrap(),
+        linear_growth: Decimal::from_str("0.0005").unwrap(),
+        exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let mut env = mock_env();
+
+    let stake_msg = |staker: &str| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: staker.to_string(),
+            amount: Uint128::from(1000u128),
+            msg: to_binary(&Cw20HookMsg::Stake {
+                stake_type: StakeType::Unlocked {},
+            })
+            .unwrap(),
+        })
+    };
+    let distribute_msg = |amount: u128, distributed_at_block: u64| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "reward0000".to_string(),
+            amount: Uint128::from(amount),
+            msg: to_binary(&Cw20HookMsg::DistributeReward {
+                distributed_at_block,
+            })
+            .unwrap(),
+        })
+    };
+    let query_pending_bro_reward =
+        |deps: &cosmwasm_std::OwnedDeps<_, _, _>, env: Env, staker: &str| {
+            from_binary::<StakerInfoResponse>(
+                &query(
+                    deps.as_ref(),
+                    env,
+                    QueryMsg::StakerInfo {
+                        staker: staker.to_string(),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+            .pending_bro_reward
+        };
+
+    let info = mock_info("bro0000", &[]);
+    let _res = execute(deps.as_mut(), env.clone(), info, stake_msg("addr0000")).unwrap();
+
+    // 1000 reward is streamed over 10 blocks, nothing is accrued at distribution block
+    let info = mock_info("bro0000", &[]);
+    let _res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        distribute_msg(1000, env.block.height),
+    )
+    .unwrap();
+
+    assert_eq!(
+        query_pending_bro_reward(&deps, env.clone(), "addr0000"),
+        Uint128::zero()
+    );
+    assert_eq!(
+        from_binary::<StateResponse>(
+            &query(deps.as_ref(), env.clone(), QueryMsg::State {}).unwrap()
+        )
+        .unwrap(),
+        StateResponse {
+            total_stake_amount: Uint128::from(1000u128),
+            global_reward_index: Decimal::zero(),
+            last_distribution_block: 12345,
+            reward_rate: Decimal::from_ratio(100u128, 1u128),
+            period_finish: 12355,
+        }
+    );
+
+    // addr0001 joins in the middle of the stream and receives only a share of the rest
+    env.block.height = 12350;
+    let info = mock_info("bro0000", &[]);
+    let _res = execute(deps.as_mut(), env.clone(), info, stake_msg("addr0001")).unwrap();
+
+    // stream is finished, no more rewards are accrued
+    env.block.height = 12360;
+    assert_eq!(
+        query_pending_bro_reward(&deps, env.clone(), "addr0000"),
+        Uint128::from(750u128)
+    );
+    assert_eq!(
+        query_pending_bro_reward(&deps, env.clone(), "addr0001"),
+        Uint128::from(250u128)
+    );
+
+    // not yet streamed amount is added to the new stream:
+    // (500 + 5 * 100) / 10 = 100 per block
+    let info = mock_info("bro0000", &[]);
+    let _res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        distribute_msg(1000, env.block.height),
+    )
+    .unwrap();
+
+    env.block.height = 12365;
+    let info = mock_info("bro0000", &[]);
+    let _res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        distribute_msg(500, env.block.height),
+    )
+    .unwrap();
+
+    assert_eq!(
+        from_binary::<StateResponse>(
+            &query(deps.as_ref(), env.clone(), QueryMsg::State {}).unwrap()
+        )
+        .unwrap(),
+        StateResponse {
+            total_stake_amount: Uint128::from(2000u128),
+            global_reward_index: Decimal::from_str("1").unwrap(),
+            last_distribution_block: 12365,
+            reward_rate: Decimal::from_ratio(100u128, 1u128),
+            period_finish: 12375,
+        }
+    );
+
+    env.block.height = 12375;
+    assert_eq!(
+        query_pending_bro_reward(&deps, env.clone(), "addr0000"),
+        Uint128::from(1500u128)
+    );
+    assert_eq!(
+        query_pending_bro_reward(&deps, env.clone(), "addr0001"),
+        Uint128::from(1000u128)
+    );
+
+    // stream is postponed while nothing is staked
+    let info = mock_info("bro0000", &[]);
+    let _res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        distribute_msg(1000, env.block.height),
+    )
+    .unwrap();
+
+    env.block.height = 12380;
+    for staker in ["addr0000", "addr0001"] {
+        let info = mock_info(staker, &[]);
+        let msg = ExecuteMsg::Unstake {
+            amount: Uint128::from(1000u128),
+            unstake_type: None,
+        };
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    }
+
+    env.block.height = 12390;
+    assert_eq!(
+        from_binary::<StateResponse>(
+            &query(deps.as_ref(), env.clone(), QueryMsg::State {}).unwrap()
+        )
+        .unwrap(),
+        StateResponse {
+            total_stake_amount: Uint128::zero(),
+            global_reward_index: Decimal::from_str("1.75").unwrap(),
+            last_distribution_block: 12390,
+            reward_rate: Decimal::from_ratio(100u128, 1u128),
+            period_finish: 12395,
+        }
+    );
+
+    let info = mock_info("bro0000", &[]);
+    let _res = execute(deps.as_mut(), env.clone(), info, stake_msg("addr0000")).unwrap();
+
+    env.block.height = 12400;
+    assert_eq!(
+        query_pending_bro_reward(&deps, env.clone(), "addr0000"),
+        Uint128::from(2250u128)
+    );
+    assert_eq!(
+        query_pending_bro_reward(&deps, env, "addr0001"),
+        Uint128::from(1250u128)
+    );
+}
+
+#[test]
+fn voting_power() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        bro_token: "bro0000".to_string(),
+        rewards_pool_contract: "reward0000".to_string(),
+        bbro_minter_contract: "bbrominter0000".to_string(),
+        epoch_manager_contract: "epoch0000".to_string(),
+        community_bonding_contract: None,
+        unstake_period_blocks: 10,
+        min_staking_amount: Uint128::zero(),
+        min_lockup_period_epochs: 1,
+        max_lockup_period_epochs: 10,
+        base_rate: Decimal::from_str("0.0001").unwrap(),
+        linear_growth: Decimal::from_str("0.0005").unwrap(),
+        exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let mut env = mock_env();
+
+    let stakes = vec![
+        ("addr0000", StakeType::Unlocked {}),
+        ("addr0001", StakeType::Locked { epochs_locked: 10 }),
+    ];
+    for (staker, stake_type) in stakes {
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: staker.to_string(),
+            amount: Uint128::from(1000u128),
+            msg: to_binary(&Cw20HookMsg::Stake { stake_type }).unwrap(),
+        });
+        let info = mock_info("bro0000", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    }
+
+    let query_voting_power =
+        |deps: &cosmwasm_std::OwnedDeps<_, _, _>, env: Env, address: &str, at_block| {
+            from_binary::<VotingPowerResponse>(
+                &query(
+                    deps.as_ref(),
+                    env,
+                    QueryMsg::VotingPower {
+                        address: address.to_string(),
+                        at_block,
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+            .voting_power
+        };
+    let query_total_voting_power = |deps: &cosmwasm_std::OwnedDeps<_, _, _>, env: Env, at_block| {
+        from_binary::<TotalVotingPowerResponse>(
+            &query(deps.as_ref(), env, QueryMsg::TotalVotingPower { at_block }).unwrap(),
+        )
+        .unwrap()
+        .total_voting_power
+    };
+
+    // locked amount with max remaining lockup period has double voting power
+    assert_eq!(
+        from_binary::<VotingPowerResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::VotingPower {
+                    address: "addr0001".to_string(),
+                    at_block: None,
+                },
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        VotingPowerResponse {
+            address: "addr0001".to_string(),
+            at_block: 12345,
+            voting_power: Uint128::from(2000u128),
+        }
+    );
+    assert_eq!(
+        query_voting_power(&deps, env.clone(), "addr0000", None),
+        Uint128::from(1000u128)
+    );
+    assert_eq!(
+        from_binary::<TotalVotingPowerResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::TotalVotingPower { at_block: None },
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        TotalVotingPowerResponse {
+            at_block: 12345,
+            total_voting_power: Uint128::from(3000u128),
+        }
+    );
+
+    // historical voting power is taken at the beginning of block
+    assert_eq!(
+        query_voting_power(&deps, env.clone(), "addr0001", Some(12345)),
+        Uint128::zero()
+    );
+    assert_eq!(
+        query_total_voting_power(&deps, env.clone(), Some(12345)),
+        Uint128::zero()
+    );
+
+    match query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::TotalVotingPower {
+            at_block: Some(12346),
+        },
+    ) {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "at_block must be less than or equal to current block")
+        }
+        _ => panic!("DO NOT ENTER HERE"),
+    }
+
+    // lockup weight decreases with remaining lockup period: 1000 + 1000 * 5 / 10 = 1500
+    env.block.height = 12350;
+    assert_eq!(
+        query_voting_power(&deps, env.clone(), "addr0001", None),
+        Uint128::from(1500u128)
+    );
+    assert_eq!(
+        query_voting_power(&deps, env.clone(), "addr0001", Some(12346)),
+        Uint128::from(1900u128)
+    );
+    assert_eq!(
+        query_total_voting_power(&deps, env.clone(), Some(12346)),
+        Uint128::from(2900u128)
+    );
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::LockupStaked {
+        amount: Uint128::from(500u128),
+        epochs_locked: 10,
+    };
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    assert_eq!(
+        query_voting_power(&deps, env.clone(), "addr0000", None),
+        Uint128::from(1500u128)
+    );
+    assert_eq!(
+        query_total_voting_power(&deps, env.clone(), None),
+        Uint128::from(3000u128)
+    );
+
+    // all lockups are expired, addr0000 unstakes everything
+    env.block.height = 12360;
+    assert_eq!(
+        query_total_voting_power(&deps, env.clone(), None),
+        Uint128::from(2000u128)
+    );
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::Unstake {
+        amount: Uint128::from(1000u128),
+        unstake_type: None,
+    };
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    assert_eq!(
+        query_voting_power(&deps, env.clone(), "addr0000", None),
+        Uint128::zero()
+    );
+    assert_eq!(
+        query_total_voting_power(&deps, env.clone(), None),
+        Uint128::from(1000u128)
+    );
+
+    // history is not affected by later changes
+    assert_eq!(
+        query_voting_power(&deps, env.clone(), "addr0000", Some(12348)),
+        Uint128::from(1000u128)
+    );
+    assert_eq!(
+        query_total_voting_power(&deps, env.clone(), Some(12348)),
+        Uint128::from(2700u128)
+    );
+    assert_eq!(
+        query_voting_power(&deps, env.clone(), "addr0000", Some(12352)),
+        Uint128::from(1400u128)
+    );
+    assert_eq!(
+        query_total_voting_power(&deps, env.clone(), Some(12352)),
+        Uint128::from(2700u128)
+    );
+
+    // history keeps voting weight of max lockup period at that time
+    let msg = ExecuteMsg::UpdateConfig {
+        paused: None,
+        unstake_period_blocks: None,
+        min_staking_amount: None,
+        min_lockup_period_epochs: None,
+        max_lockup_period_epochs: Some(20),
+        base_rate: None,
+        linear_growth: None,
+        exponential_growth: None,
+        community_bonding_contract: None,
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: None,
+    };
+    let info = mock_info("owner", &[]);
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    env.block.height += 1;
+    assert_eq!(
+        query_voting_power(&deps, env.clone(), "addr0000", Some(12352)),
+        Uint128::from(1400u128)
+    );
+    assert_eq!(
+        query_total_voting_power(&deps, env.clone(), Some(12352)),
+        Uint128::from(2700u128)
+    );
+
+    // new lockup is weighted with new max lockup period: 1000 + 1000 * 20 / 20 = 2000
+    let info = mock_info("addr0001", &[]);
+    let msg = ExecuteMsg::LockupStaked {
+        amount: Uint128::from(1000u128),
+        epochs_locked: 20,
+    };
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        query_voting_power(&deps, env, "addr0001", None),
+        Uint128::from(2000u128)
+    );
+}
+
+#[test]
+fn instant_unstake() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        bro_token: "bro0000".to_string(),
+        rewards_pool_contract: "reward0000".to_string(),
+        bbro_minter_contract: "bbrominter0000".to_string(),
+        epoch_manager_contract: "epoch0000".to_string(),
+        community_bonding_contract: None,
+        unstake_period_blocks: 10,
+        min_staking_amount: Uint128::zero(),
+        min_lockup_period_epochs: 1,
+        max_lockup_period_epochs: 365,
+        base_rate: Decimal::from_str("0.0001").unwrap(),
+        linear_growth: Decimal::from_str("0.0005").unwrap(),
+        exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 0,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let mut env = mock_env();
+
+    for staker in ["addr0000", "addr0001"] {
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: staker.to_string(),
+            amount: Uint128::from(100u128),
+            msg: to_binary(&Cw20HookMsg::Stake {
+                stake_type: StakeType::Unlocked {},
+            })
+            .unwrap(),
+        });
+        let info = mock_info("bro0000", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    }
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::Unstake {
+        amount: Uint128::from(50u128),
+        unstake_type: Some(UnstakeType::Instant {}),
+    };
+    match execute(deps.as_mut(), env.clone(), info, msg) {
+        Err(ContractError::InstantUnstakeIsNotEnabled {}) => (),
+        _ => panic!("expecting instant unstake is not enabled error"),
+    }
+
+    let update_config_msg = |fee_rate: &str, fee_destination| ExecuteMsg::UpdateConfig {
+        paused: None,
+        unstake_period_blocks: None,
+        min_staking_amount: None,
+        min_lockup_period_epochs: None,
+        max_lockup_period_epochs: None,
+        base_rate: None,
+        linear_growth: None,
+        exponential_growth: None,
+        community_bonding_contract: None,
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: Some(Box::new(InstantUnstake {
+            fee_rate: Decimal::from_str(fee_rate).unwrap(),
+            fee_destination,
+        })),
+        staker_history_limit: None,
+    };
+
+    let info = mock_info("owner", &[]);
+    let msg = update_config_msg("1", InstantUnstakeFeeDestination::Stakers {});
+    match execute(deps.as_mut(), env.clone(), info.clone(), msg) {
+        Err(ContractError::Std(StdError::GenericErr { msg, .. })) => {
+            assert_eq!(msg, "fee_rate must be less than 1.0")
+        }
+        _ => panic!("expecting invalid fee rate error"),
+    }
+
+    let msg = update_config_msg("0.1", InstantUnstakeFeeDestination::Stakers {});
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    // fee is streamed to remaining stakers, unstaked amount is sent in the same response
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::Unstake {
+        amount: Uint128::from(50u128),
+        unstake_type: Some(UnstakeType::Instant {}),
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "bro0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "addr0000".to_string(),
+                amount: Uint128::from(45u128),
+            })
+            .unwrap(),
+        }))]
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            Attribute::new("action", "unstake"),
+            Attribute::new("staker", "addr0000"),
+            Attribute::new("amount", "50"),
+            Attribute::new("unstake_type", "instant"),
+            Attribute::new("fee_amount", "5"),
+            Attribute::new("withdrawal_amount", "45"),
+        ]
+    );
+
+    // no withdrawal is created, so sent amount can't be withdrawn or restaked again
+    assert_eq!(
+        from_binary::<WithdrawalsResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::Withdrawals {
+                    staker: "addr0000".to_string()
+                }
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        WithdrawalsResponse { claims: vec![] }
+    );
+    match execute(deps.as_mut(), env.clone(), info, ExecuteMsg::Withdraw {}) {
+        Err(ContractError::NothingToClaim {}) => (),
+        _ => panic!("expecting nothing to claim error"),
+    }
+
+    // addr0001 receives 100/150 of the fee
+    env.block.height += 1;
+    let staker_info = from_binary::<StakerInfoResponse>(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::StakerInfo {
+                staker: "addr0001".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(staker_info.pending_bro_reward, Uint128::from(3u128));
+
+    // fee is sent to the rewards pool
+    let info = mock_info("owner", &[]);
+    let msg = update_config_msg("0.1", InstantUnstakeFeeDestination::RewardsPool {});
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let info = mock_info("addr0001", &[]);
+    let msg = ExecuteMsg::Unstake {
+        amount: Uint128::from(100u128),
+        unstake_type: Some(UnstakeType::Instant {}),
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "bro0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "reward0000".to_string(),
+                    amount: Uint128::from(10u128),
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "bro0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "addr0001".to_string(),
+                    amount: Uint128::from(90u128),
+                })
+                .unwrap(),
+            })),
+        ]
+    );
+    assert_eq!(res.attributes[5], Attribute::new("withdrawal_amount", "90"));
+}
+
+#[test]
+fn staker_history() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        bro_token: "bro0000".to_string(),
+        rewards_pool_contract: "reward0000".to_string(),
+        bbro_minter_contract: "bbrominter0000".to_string(),
+        epoch_manager_contract: "epoch0000".to_string(),
+        community_bonding_contract: None,
+        unstake_period_blocks: 10,
+        min_staking_amount: Uint128::zero(),
+        min_lockup_period_epochs: 1,
+        max_lockup_period_epochs: 365,
+        base_rate: Decimal::from_str("0.0001").unwrap(),
+        linear_growth: Decimal::from_str("0.0005").unwrap(),
+        exponential_growth: Decimal::from_str("0.0000075").unwrap(),
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: 3,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let mut env = mock_env();
+
+    let query_history = |deps: Deps, start_after: Option<u64>, limit: Option<u32>| {
+        from_binary::<StakerHistoryResponse>(
+            &query(
+                deps,
+                mock_env(),
+                QueryMsg::StakerHistory {
+                    staker: "addr0000".to_string(),
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+        .history
+    };
+
+    for stake_type in [
+        StakeType::Unlocked {},
+        StakeType::Locked { epochs_locked: 10 },
+    ] {
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "addr0000".to_string(),
+            amount: Uint128::from(100u128),
+            msg: to_binary(&Cw20HookMsg::Stake { stake_type }).unwrap(),
+        });
+        let info = mock_info("bro0000", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+        env.block.height += 1;
+    }
+
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::Unstake {
+        amount: Uint128::from(50u128),
+        unstake_type: None,
+    };
+    let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    env.block.height += 1;
+
+    let msg = ExecuteMsg::CancelUnstake { index: 0 };
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    // the oldest entry exceeding history limit is removed
+    assert_eq!(
+        query_history(deps.as_ref(), None, None),
+        vec![
+            StakerHistoryEntryResponse {
+                id: 1,
+                block: 12346,
+                action: "stake".to_string(),
+                amount: Uint128::from(100u128),
+                epochs_locked: Some(10),
+            },
+            StakerHistoryEntryResponse {
+                id: 2,
+                block: 12347,
+                action: "unstake".to_string(),
+                amount: Uint128::from(50u128),
+                epochs_locked: None,
+            },
+            StakerHistoryEntryResponse {
+                id: 3,
+                block: 12348,
+                action: "cancel_unstake".to_string(),
+                amount: Uint128::from(50u128),
+                epochs_locked: None,
+            },
+        ]
+    );
+
+    let history = query_history(deps.as_ref(), Some(1), Some(1));
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].id, 2);
+
+    let update_config_msg = |staker_history_limit| ExecuteMsg::UpdateConfig {
+        paused: None,
+        unstake_period_blocks: None,
+        min_staking_amount: None,
+        min_lockup_period_epochs: None,
+        max_lockup_period_epochs: None,
+        base_rate: None,
+        linear_growth: None,
+        exponential_growth: None,
+        community_bonding_contract: None,
+        early_unlock_penalty: None,
+        boost: None,
+        instant_unstake: None,
+        staker_history_limit: Some(staker_history_limit),
+    };
+
+    let info = mock_info("owner", &[]);
+    match execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        update_config_msg(101),
+    ) {
+        Err(ContractError::Std(StdError::GenericErr { msg, .. })) => {
+            assert_eq!(
+                msg,
+                "staker_history_limit must be less than or equal to 100"
+            )
+        }
+        _ => panic!("expecting invalid staker history limit error"),
+    }
+
+    // lowered limit applies to stored entries immediately
+    let _res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        update_config_msg(1),
+    )
+    .unwrap();
+    let history = query_history(deps.as_ref(), None, None);
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].id, 3);
+
+    // disabled history is neither recorded nor returned
+    let _res = execute(deps.as_mut(), env.clone(), info, update_config_msg(0)).unwrap();
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Stake {
+            stake_type: StakeType::Unlocked {},
+        })
+        .unwrap(),
+    });
+    let _res = execute(deps.as_mut(), env, mock_info("bro0000", &[]), msg).unwrap();
+    assert!(query_history(deps.as_ref(), None, None).is_empty());
 }
@@ -3,6 +3,8 @@ use cw20::{Cw20ReceiveMsg, Expiration};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use astroport::asset::AssetInfo;
+
 /// ## InstantiateMsg
 /// This structure describes the basic settings for creating a contract.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -25,6 +27,22 @@ pub struct InstantiateMsg {
     pub min_bro_payout: Uint128,
     /// bonding mode
     pub bonding_mode: BondingModeMsg,
+    /// dynamic discount config,
+    /// if value is set to none
+    /// than bonding discounts are fixed
+    pub discount_control: Option<DiscountControl>,
+    /// per epoch bonding limits config,
+    /// if value is set to none
+    /// than bonding is limited only by bonding balance
+    pub bonding_limits: Option<BondingLimitsMsg>,
+    /// config for locking bond payout in staking contract,
+    /// if value is set to none
+    /// than bonders can't choose lockup period
+    pub bond_lock: Option<BondLockMsg>,
+    /// config for staking bonded lp tokens in astroport generator,
+    /// if value is set to none
+    /// than bonded lp tokens are sent to treasury
+    pub lp_staking: Option<LpStakingMsg>,
 }
 
 /// ## BondingModeMsg
@@ -57,6 +75,98 @@ pub enum BondingModeMsg {
     },
 }
 
+/// ## DiscountControl
+/// This structure describes the dynamic bonding discount settings.
+/// Configured bonding discount is used as max discount and decreases
+/// as bonded debt grows relative to available bonding balance
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DiscountControl {
+    /// lower bound of bonding discount
+    pub min_discount: Decimal,
+    /// bond control variable, discount decreases by this value multiplied by debt ratio
+    pub control_variable: Decimal,
+    /// amount of blocks in which bonded debt decays back to zero
+    pub debt_decay_blocks: u64,
+}
+
+/// ## BondingLimitsMsg
+/// This structure describes the per epoch bonding limits settings.
+/// Epoch boundaries are taken from epoch-manager contract
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BondingLimitsMsg {
+    /// epoch-manager contract address
+    pub epoch_manager_contract: String,
+    /// limits for ust bonding
+    pub ust_bond: EpochLimits,
+    /// limits for lp token bonding
+    pub lp_bond: EpochLimits,
+}
+
+/// ## EpochLimits
+/// This structure describes the per epoch limits of bond type.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EpochLimits {
+    /// max amount of bro paid out per epoch
+    pub epoch_capacity: Option<Uint128>,
+    /// max amount of bro paid out per epoch to a single address
+    pub address_cap: Option<Uint128>,
+}
+
+/// ## MarketBondedAmount
+/// This structure describes asset amount bonded via bond market.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MarketBondedAmount {
+    /// bond market id
+    pub market_id: u64,
+    /// bonded asset amount
+    pub amount: Uint128,
+}
+
+/// ## BondLockMsg
+/// This structure describes the settings for locking bond payout in staking contract.
+/// Extra discount grows linearly with lockup period and reaches
+/// max lock discount at max lockup period of staking contract
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BondLockMsg {
+    /// staking contract address
+    pub staking_contract: String,
+    /// extra discount for locking bond payout for max lockup period
+    pub max_lock_discount: Decimal,
+}
+
+/// ## LpStakingMsg
+/// This structure describes the settings for staking bonded lp tokens in astroport generator.
+/// Staking rewards are harvested to treasury
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LpStakingMsg {
+    /// astroport generator contract address
+    pub generator_contract: String,
+}
+
+/// ## BondMarketAssetMsg
+/// This structure describes the asset accepted by bond market.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BondMarketAssetMsg {
+    /// native token with specified denom
+    NativeToken { denom: String },
+    /// cw20 token with specified address
+    Token { contract_addr: String },
+    /// lp token of astroport pair of bro token and specified asset
+    LpToken { paired_asset: AssetInfo },
+}
+
+/// ## PriceSourceMsg
+/// This structure describes the source of bro price for bond market asset.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceSourceMsg {
+    /// price oracle contract which returns bro amount for specified asset amount
+    Oracle { oracle_contract: String },
+    /// fixed amount of bro for one unit of asset
+    Fixed { bro_per_asset: Decimal },
+}
+
 /// ## ExecuteMsg
 /// This structure describes the execute messages of the contract.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -68,9 +178,22 @@ pub enum ExecuteMsg {
     Receive(Cw20ReceiveMsg),
     /// ## Description
     /// Bond bro tokens by providing ust amount.
-    UstBond {},
+    UstBond {
+        /// minimum amount of bro to receive, bond is rejected if payout is lower
+        min_bro_payout: Option<Uint128>,
+        /// amount of epochs to lock bond payout in staking contract
+        lock_epochs: Option<u64>,
+    },
     /// ## Description
-    /// Claim available reward amount.
+    /// Bond bro tokens by providing native token amount to specified bond market.
+    Bond {
+        /// bond market id
+        market_id: u64,
+        /// minimum amount of bro to receive, bond is rejected if payout is lower
+        min_bro_payout: Option<Uint128>,
+    },
+    /// ## Description
+    /// Claim vested amount of all bonder claims.
     Claim {},
     /// ## Description
     /// Updates contract settings
@@ -89,6 +212,14 @@ pub enum ExecuteMsg {
         ust_bonding_discount: Option<Decimal>,
         /// new minimum amount of bro to receive via bonding
         min_bro_payout: Option<Uint128>,
+        /// new dynamic discount config
+        discount_control: Option<Box<DiscountControl>>,
+        /// new per epoch bonding limits config
+        bonding_limits: Option<Box<BondingLimitsMsg>>,
+        /// new config for locking bond payout in staking contract
+        bond_lock: Option<Box<BondLockMsg>>,
+        /// new config for staking bonded lp tokens in astroport generator
+        lp_staking: Option<Box<LpStakingMsg>>,
     },
     /// ## Description
     /// Updates specific settings for bonding mode config
@@ -109,6 +240,72 @@ pub enum ExecuteMsg {
         epochs_locked_community: Option<u64>,
     },
     /// ## Description
+    /// Registers new bond market
+    /// ## Executor
+    /// Only owner can execute this function
+    RegisterBondMarket {
+        /// asset accepted by bond market
+        asset: BondMarketAssetMsg,
+        /// discount percentage for bonding
+        discount: Decimal,
+        /// vesting period for withdrawal
+        vesting_period_blocks: u64,
+        /// source of bro price for bonded asset
+        price_source: PriceSourceMsg,
+        /// dynamic discount settings based on bond market debt
+        discount_control: Option<DiscountControl>,
+        /// per epoch limits of bond market, requires bonding limits to be configured
+        epoch_limits: Option<EpochLimits>,
+    },
+    /// ## Description
+    /// Updates specified bond market settings
+    /// ## Executor
+    /// Only owner can execute this function
+    UpdateBondMarket {
+        /// bond market id
+        market_id: u64,
+        /// new discount percentage for bonding
+        discount: Option<Decimal>,
+        /// new vesting period for withdrawal
+        vesting_period_blocks: Option<u64>,
+        /// new source of bro price for bonded asset
+        price_source: Option<PriceSourceMsg>,
+        /// enables or disables bonding
+        enabled: Option<bool>,
+        /// new dynamic discount settings based on bond market debt
+        discount_control: Option<DiscountControl>,
+        /// new per epoch limits of bond market, requires bonding limits to be configured
+        epoch_limits: Option<EpochLimits>,
+    },
+    /// ## Description
+    /// Withdraws unused capacity of specified bond market to treasury
+    /// ## Executor
+    /// Only owner can execute this function
+    WithdrawBondMarketCapacity {
+        /// bond market id
+        market_id: u64,
+        /// amount of bro to withdraw, withdraws all capacity if not specified
+        amount: Option<Uint128>,
+    },
+    /// ## Description
+    /// Claims lp staking rewards from astroport generator and sends them to treasury
+    HarvestLpRewards {},
+    /// ## Description
+    /// Withdraws specified amount of staked lp tokens from astroport generator
+    /// and sends them to treasury along with staking rewards
+    /// ## Executor
+    /// Only owner can execute this function
+    UnstakeLp {
+        /// amount of lp tokens to withdraw
+        amount: Uint128,
+    },
+    /// ## Description
+    /// Withdraws all staked lp tokens from astroport generator to treasury
+    /// along with staking rewards and disables lp staking
+    /// ## Executor
+    /// Only owner can execute this function
+    DisableLpStaking {},
+    /// ## Description
     /// Creates an offer for a new owner.
     /// The validity period of the offer is set in the `expires_in_blocks` variable
     /// ## Executor
@@ -141,7 +338,26 @@ pub enum Cw20HookMsg {
     DistributeReward {},
     /// ## Description
     /// Bond bro tokens by providing lp token amount.
-    LpBond {},
+    LpBond {
+        /// minimum amount of bro to receive, bond is rejected if payout is lower
+        min_bro_payout: Option<Uint128>,
+        /// amount of epochs to lock bond payout in staking contract
+        lock_epochs: Option<u64>,
+    },
+    /// ## Description
+    /// Bond bro tokens by providing cw20 or lp token amount to specified bond market.
+    Bond {
+        /// bond market id
+        market_id: u64,
+        /// minimum amount of bro to receive, bond is rejected if payout is lower
+        min_bro_payout: Option<Uint128>,
+    },
+    /// ## Description
+    /// Increases capacity of specified bond market by received bro amount
+    FundBondMarket {
+        /// bond market id
+        market_id: u64,
+    },
 }
 
 /// ## QueryMsg
@@ -164,10 +380,63 @@ pub enum QueryMsg {
     },
     /// ## Description
     /// Returns simulated bro bond using specified uusd amount in the [`SimulateExchangeResponse`] object
-    SimulateUstBond { uusd_amount: Uint128 },
+    SimulateUstBond {
+        uusd_amount: Uint128,
+        /// amount of epochs to lock bond payout in staking contract
+        lock_epochs: Option<u64>,
+    },
     /// ## Description
     /// Returns simulated bro bond using specified ust/bro lp token amount in the [`SimulateExchangeResponse`] object
-    SimulateLpBond { lp_amount: Uint128 },
+    SimulateLpBond {
+        lp_amount: Uint128,
+        /// amount of epochs to lock bond payout in staking contract
+        lock_epochs: Option<u64>,
+    },
+    /// ## Description
+    /// Returns bonding capacity available in current epoch in the [`BondingCapacityResponse`] object
+    BondingCapacity {
+        /// bond market id to start reading capacities after
+        start_after: Option<u64>,
+        /// max amount of bond market capacities to read
+        limit: Option<u32>,
+    },
+    /// ## Description
+    /// Returns information about specified bond market in the [`BondMarketResponse`] object
+    BondMarket {
+        /// bond market id
+        market_id: u64,
+    },
+    /// ## Description
+    /// Returns a list of bond markets in the [`BondMarketsResponse`] object
+    BondMarkets {
+        /// bond market id to start reading after
+        start_after: Option<u64>,
+        /// max amount of bond markets to read
+        limit: Option<u32>,
+    },
+    /// ## Description
+    /// Returns simulated bro bond using specified asset amount of bond market
+    /// in the [`SimulateExchangeResponse`] object
+    SimulateBond {
+        /// bond market id
+        market_id: u64,
+        /// amount of bond market asset
+        amount: Uint128,
+    },
+    /// ## Description
+    /// Returns lifetime bonding totals of specified bonder in the [`BonderResponse`] object
+    Bonder {
+        /// bonder address
+        address: String,
+    },
+    /// ## Description
+    /// Returns a list of bonders with their lifetime bonding totals in the [`BondersResponse`] object
+    Bonders {
+        /// bonder address to start reading after
+        start_after: Option<String>,
+        /// max amount of bonders to read
+        limit: Option<u32>,
+    },
     /// ## Description
     /// Returns information about created ownership proposal in the [`OwnershipProposalResponse`] object
     /// otherwise returns not-found error
@@ -205,6 +474,14 @@ pub struct ConfigResponse {
     pub min_bro_payout: Uint128,
     /// bonding mode
     pub bonding_mode: BondingModeMsg,
+    /// dynamic discount config
+    pub discount_control: Option<DiscountControl>,
+    /// per epoch bonding limits config
+    pub bonding_limits: Option<BondingLimitsMsg>,
+    /// config for locking bond payout in staking contract
+    pub bond_lock: Option<BondLockMsg>,
+    /// config for staking bonded lp tokens in astroport generator
+    pub lp_staking: Option<LpStakingMsg>,
 }
 
 /// ## StateResponse
@@ -215,6 +492,20 @@ pub struct StateResponse {
     pub ust_bonding_balance: Uint128,
     /// available bro balance for lp token bonding
     pub lp_bonding_balance: Uint128,
+    /// decayed bro amount bonded via ust bonding
+    pub ust_bonding_debt: Uint128,
+    /// decayed bro amount bonded via lp token bonding
+    pub lp_bonding_debt: Uint128,
+    /// total uusd amount bonded via ust bonding
+    pub total_ust_bonded: Uint128,
+    /// total lp token amount bonded via lp token bonding
+    pub total_lp_bonded: Uint128,
+    /// total bro amount paid out to bonders
+    pub total_bro_payout: Uint128,
+    /// total bro amount claimed by bonders
+    pub total_bro_claimed: Uint128,
+    /// total number of bonds
+    pub bonds_count: u64,
 }
 
 /// ## ClaimInfoResponse
@@ -223,10 +514,18 @@ pub struct StateResponse {
 pub struct ClaimInfoResponse {
     /// bond type
     pub bond_type: String,
-    /// amount to claim
+    /// bro payout of the bond
     pub amount: Uint128,
-    /// block at which amount can be claimed
+    /// block at which amount is fully vested
     pub claimable_at: Expiration,
+    /// amount vested so far, including already claimed amount
+    pub vested_amount: Uint128,
+    /// amount already claimed
+    pub claimed_amount: Uint128,
+    /// vested amount available to claim
+    pub claimable_amount: Uint128,
+    /// amount which is not vested yet
+    pub remaining_amount: Uint128,
 }
 
 /// ## ClaimsResponse
@@ -243,4 +542,114 @@ pub struct ClaimsResponse {
 pub struct SimulateExchangeResponse {
     pub bro_payout: Uint128,
     pub can_be_exchanged: bool,
+    /// current bonding discount
+    pub discount: Decimal,
+}
+
+/// ## BondMarketResponse
+/// This structure describes the fields for bond market response message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BondMarketResponse {
+    /// bond market id
+    pub market_id: u64,
+    /// asset accepted by bond market
+    pub asset_info: AssetInfo,
+    /// astroport pair address if accepted asset is lp token
+    pub pair_contract: Option<String>,
+    /// asset paired with bro token if accepted asset is lp token
+    pub paired_asset: Option<AssetInfo>,
+    /// discount percentage for bonding
+    pub discount: Decimal,
+    /// available bro balance for bonding
+    pub capacity: Uint128,
+    /// vesting period for withdrawal
+    pub vesting_period_blocks: u64,
+    /// source of bro price for bonded asset
+    pub price_source: PriceSourceMsg,
+    /// whether bonding is enabled
+    pub enabled: bool,
+    /// dynamic discount settings based on bond market debt
+    pub discount_control: Option<DiscountControl>,
+    /// decayed bro payout of bond market
+    pub debt: Uint128,
+    /// per epoch limits of bond market
+    pub epoch_limits: Option<EpochLimits>,
+    /// total asset amount bonded via bond market
+    pub total_bonded: Uint128,
+}
+
+/// ## BondMarketsResponse
+/// This structure describes the fields for bond markets response message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BondMarketsResponse {
+    /// a list of bond markets
+    pub markets: Vec<BondMarketResponse>,
+}
+
+/// ## BonderResponse
+/// This structure describes the fields for bonder response message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BonderResponse {
+    /// bonder address
+    pub address: String,
+    /// uusd amount bonded via ust bonding
+    pub ust_bonded: Uint128,
+    /// lp token amount bonded via lp token bonding
+    pub lp_bonded: Uint128,
+    /// asset amounts bonded via bond markets
+    pub market_bonded: Vec<MarketBondedAmount>,
+    /// bro amount paid out to bonder
+    pub bro_payout: Uint128,
+    /// bro amount claimed by bonder
+    pub bro_claimed: Uint128,
+    /// number of bonds
+    pub bonds_count: u64,
+}
+
+/// ## BondersResponse
+/// This structure describes the fields for bonders response message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BondersResponse {
+    /// a list of bonders
+    pub bonders: Vec<BonderResponse>,
+}
+
+/// ## BondingCapacityResponse
+/// This structure describes the fields for bonding capacity response message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BondingCapacityResponse {
+    /// current epoch, none if bonding limits are not configured
+    pub epoch: Option<u64>,
+    /// block at which current epoch ends, none if bonding limits are not configured
+    pub epoch_end_block: Option<u64>,
+    /// ust bonding capacity
+    pub ust_bond: BondTypeCapacityResponse,
+    /// lp token bonding capacity
+    pub lp_bond: BondTypeCapacityResponse,
+    /// a list of bond market capacities
+    pub bond_markets: Vec<BondMarketCapacityResponse>,
+}
+
+/// ## BondTypeCapacityResponse
+/// This structure describes the fields for bond type capacity response message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BondTypeCapacityResponse {
+    /// max amount of bro paid out per epoch
+    pub epoch_capacity: Option<Uint128>,
+    /// max amount of bro paid out per epoch to a single address
+    pub address_cap: Option<Uint128>,
+    /// amount of bro paid out in current epoch
+    pub bonded_amount: Uint128,
+    /// amount of bro still available for bonding in current epoch
+    pub available_amount: Uint128,
+}
+
+/// ## BondMarketCapacityResponse
+/// This structure describes the fields for bond market capacity response message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BondMarketCapacityResponse {
+    /// bond market id
+    pub market_id: u64,
+    /// bond market capacity in current epoch
+    pub capacity: BondTypeCapacityResponse,
 }
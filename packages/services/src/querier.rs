@@ -1,5 +1,5 @@
 use cosmwasm_std::{to_binary, Addr, QuerierWrapper, QueryRequest, StdResult, Uint128, WasmQuery};
-use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20QueryMsg};
+use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20QueryMsg, TokenInfoResponse};
 
 use crate::{
     epoch_manager::{EpochInfoResponse, QueryMsg as EpochManagerQueryMsg},
@@ -10,6 +10,7 @@ use crate::{
 
 use astroport::{
     asset::{Asset, AssetInfo},
+    generator::{PendingTokenResponse, QueryMsg as GeneratorQueryMsg, RewardInfoResponse},
     pair::{CumulativePricesResponse, QueryMsg as PairQueryMsg, SimulationResponse},
     querier::query_pair_info,
 };
@@ -43,6 +44,21 @@ pub fn query_token_balance(
     Ok(res.balance)
 }
 
+/// ## Description
+/// Returns the total supply of the token at the specified contract address.
+/// ## Params
+/// * **querier** is an object of type [`QuerierWrapper`].
+///
+/// * **contract_addr** is an object of type [`Addr`]. Sets the address of the token contract
+pub fn query_token_supply(querier: &QuerierWrapper, contract_addr: Addr) -> StdResult<Uint128> {
+    let res: TokenInfoResponse = querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: String::from(contract_addr),
+        msg: to_binary(&Cw20QueryMsg::TokenInfo {})?,
+    }))?;
+
+    Ok(res.total_supply)
+}
+
 /// ## Description
 /// Returns the epoch info at the specified contract address.
 /// ## Params
@@ -190,6 +206,71 @@ pub fn query_staking_config(
     }))
 }
 
+/// ## Description
+/// Returns pending rewards of user for specified lp token staked in astroport generator
+/// in the [`PendingTokenResponse`] object.
+/// ## Params
+/// * **querier** is an object of type [`QuerierWrapper`].
+///
+/// * **generator_contract** is an object of type [`Addr`]
+///
+/// * **lp_token** is an object of type [`Addr`]
+///
+/// * **user** is an object of type [`Addr`]
+pub fn query_generator_pending_token(
+    querier: &QuerierWrapper,
+    generator_contract: Addr,
+    lp_token: Addr,
+    user: Addr,
+) -> StdResult<PendingTokenResponse> {
+    querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: generator_contract.to_string(),
+        msg: to_binary(&GeneratorQueryMsg::PendingToken { lp_token, user })?,
+    }))
+}
+
+/// ## Description
+/// Returns reward tokens for specified lp token staked in astroport generator
+/// in the [`RewardInfoResponse`] object.
+/// ## Params
+/// * **querier** is an object of type [`QuerierWrapper`].
+///
+/// * **generator_contract** is an object of type [`Addr`]
+///
+/// * **lp_token** is an object of type [`Addr`]
+pub fn query_generator_reward_info(
+    querier: &QuerierWrapper,
+    generator_contract: Addr,
+    lp_token: Addr,
+) -> StdResult<RewardInfoResponse> {
+    querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: generator_contract.to_string(),
+        msg: to_binary(&GeneratorQueryMsg::RewardInfo { lp_token })?,
+    }))
+}
+
+/// ## Description
+/// Returns amount of specified lp token deposited by user in astroport generator
+/// ## Params
+/// * **querier** is an object of type [`QuerierWrapper`].
+///
+/// * **generator_contract** is an object of type [`Addr`]
+///
+/// * **lp_token** is an object of type [`Addr`]
+///
+/// * **user** is an object of type [`Addr`]
+pub fn query_generator_deposit(
+    querier: &QuerierWrapper,
+    generator_contract: Addr,
+    lp_token: Addr,
+    user: Addr,
+) -> StdResult<Uint128> {
+    querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: generator_contract.to_string(),
+        msg: to_binary(&GeneratorQueryMsg::Deposit { lp_token, user })?,
+    }))
+}
+
 /// ## Description
 /// Queries bro/ust pair using astroport factory.
 /// result.0 - bro asset info of type [`Asset`]
@@ -1,8 +1,11 @@
+use astroport::asset::{Asset, AssetInfo};
 use cosmwasm_std::{Binary, Decimal, Uint128};
 use cw20::{Cw20ReceiveMsg, Expiration};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::common::OrderBy;
+
 /// ## InstantiateMsg
 /// This structure describes the basic settings for creating a contract.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -35,6 +38,22 @@ pub struct InstantiateMsg {
     pub linear_growth: Decimal,
     /// exponential growth for bbro premium reward calculation
     pub exponential_growth: Decimal,
+    /// penalty config for unlocking lockups before expiration,
+    /// if value is set to none
+    /// than option to unlock lockups early is disabled
+    pub early_unlock_penalty: Option<EarlyUnlockPenalty>,
+    /// bbro boost config for bro rewards weighting,
+    /// if value is set to none
+    /// than rewards are shared by staked amount only
+    pub boost: Option<BoostParams>,
+    /// instant unstake config,
+    /// if value is set to none
+    /// than option to unstake instantly is disabled
+    pub instant_unstake: Option<InstantUnstake>,
+    /// max amount of history entries kept per staker,
+    /// if value is set to zero
+    /// than staker history is not recorded
+    pub staker_history_limit: u64,
 }
 
 /// ## ExecuteMsg
@@ -55,22 +74,163 @@ pub enum ExecuteMsg {
         epochs_locked: u64,
     },
     /// ## Description
+    /// Locks lockup again starting from current block for its remaining epochs
+    /// plus specified amount of additional epochs.
+    /// Only bbro premium reward for additional epochs is minted
+    ExtendLockup {
+        /// index of lockup in staker's lockups list
+        lockup_index: u64,
+        /// how many epochs will be added to remaining lockup period
+        additional_epochs: u64,
+    },
+    /// ## Description
+    /// Merges specified lockups into single lockup locked starting from current block
+    /// for the longest remaining period among them.
+    /// Only bbro premium reward for extended periods is minted
+    MergeLockups {
+        /// indices of lockups in staker's lockups list
+        indices: Vec<u64>,
+    },
+    /// ## Description
+    /// Moves specified amount of lockup to unlocked staked amount before lockup expiration.
+    /// Staker pays a penalty according to configured early unlock penalty
+    EarlyUnlock {
+        /// index of lockup in staker's lockups list
+        lockup_index: u64,
+        /// amount of tokens to unlock
+        amount: Uint128,
+    },
+    /// ## Description
     /// Unstake staked amount of tokens.
-    /// Tokens will be claimable only after passing the unstaking period.
+    /// With delayed unstake tokens will be claimable only after passing the unstaking period.
+    /// With instant unstake tokens are sent immediately, but staker pays a fee
     Unstake {
         /// amount of tokens to unstake
         amount: Uint128,
+        /// unstake type, delayed if not specified
+        unstake_type: Option<UnstakeType>,
     },
     /// ## Description
     /// Withdraw the amount of tokens that have already passed the unstaking period.
     Withdraw {},
     /// ## Description
+    /// Sets the address which receives staker's rewards and withdrawals.
+    /// If recipient is not specified resets it to staker address
+    SetRewardRecipient {
+        /// address of rewards recipient
+        recipient: Option<String>,
+    },
+    /// ## Description
+    /// Allows operator to claim rewards and withdrawals on behalf of staker
+    ApproveClaimOperator {
+        /// operator address
+        operator: String,
+        /// permission expiration, never expires if not specified
+        expires: Option<Expiration>,
+    },
+    /// ## Description
+    /// Revokes operator permission to claim on behalf of staker
+    RevokeClaimOperator {
+        /// operator address
+        operator: String,
+    },
+    /// ## Description
+    /// Claims available rewards and withdrawals on behalf of staker.
+    /// Funds are sent to staker's reward recipient
+    ClaimFor {
+        /// staker address
+        staker: String,
+    },
+    /// ## Description
+    /// Moves staker's lockups by indices, unlocked stake if specified
+    /// and pending rewards to another address
+    TransferPosition {
+        /// address of new position owner
+        recipient: String,
+        /// indices of lockups to transfer
+        lockup_indices: Vec<u64>,
+        /// defines whether unlocked stake amount is transferred or not
+        include_unlocked: bool,
+    },
+    /// ## Description
+    /// Cancels pending withdrawal by specified index and stakes its amount back as unlocked stake
+    CancelUnstake {
+        /// index of withdrawal in staker's withdrawals list
+        index: u64,
+    },
+    /// ## Description
+    /// Stakes back the amount of all staker's pending withdrawals as unlocked stake
+    RestakeWithdrawals {},
+    /// ## Description
     /// Claim available bro reward amount
     ClaimBroRewards {},
     /// ## Description
     /// Claim available bbro reward amount
     ClaimBbroRewards {},
     /// ## Description
+    /// Claim available bro reward amount and pending amounts of all reward assets
+    ClaimAllRewards {},
+    /// ## Description
+    /// Restakes available bro reward amount.
+    /// If `epochs_locked` is set compounded amount will be locked
+    /// for specified amount of epochs
+    CompoundBroRewards {
+        /// how many epochs compounded amount will be locked
+        epochs_locked: Option<u64>,
+    },
+    /// ## Description
+    /// Enables or disables automatic restaking of bro rewards
+    SetAutoCompound {
+        /// defines whether bro rewards will be restaked automatically or not
+        enabled: bool,
+    },
+    /// ## Description
+    /// Restakes available bro reward amount for specified stakers
+    /// which enabled auto-compounding
+    AutoCompoundBroRewards {
+        /// list of stakers addresses
+        stakers: Vec<String>,
+    },
+    /// ## Description
+    /// Recalculates boost multiplier of specified stakers using their current bbro balance
+    UpdateBoost {
+        /// list of stakers addresses
+        stakers: Vec<String>,
+    },
+    /// ## Description
+    /// Withdraws whole staked amount and pending withdrawals immediately forfeiting pending rewards.
+    /// Available only while emergency mode is enabled
+    EmergencyWithdraw {},
+    /// ## Description
+    /// Enables emergency mode. Emergency mode cannot be disabled
+    /// ## Executor
+    /// Only owner can execute this function
+    EnableEmergencyMode {},
+    /// ## Description
+    /// Registers new reward asset distributed to stakers
+    /// ## Executor
+    /// Only owner can execute this function
+    AddRewardAsset {
+        /// reward asset info
+        asset_info: AssetInfo,
+    },
+    /// ## Description
+    /// Distributes attached native tokens of registered reward asset to stakers.
+    /// Cw20 reward assets are funded via [`Cw20HookMsg::FundRewardAsset`]
+    FundRewardAsset {
+        /// reward asset to distribute
+        asset: Asset,
+    },
+    /// ## Description
+    /// Replaces a list of contracts allowed to lock bond payouts of bonders
+    /// in addition to community bonding contract
+    /// ## Executor
+    /// Only owner can execute this function
+    UpdateBondLockContracts {
+        /// new list of bond lock contract addresses
+        bond_lock_contracts: Vec<String>,
+    },
+    /// ## Description
     /// Updates contract settings
     /// ## Executor
     /// Only owner can execute this function
@@ -93,11 +253,27 @@ pub enum ExecuteMsg {
         exponential_growth: Option<Decimal>,
         /// community bonding contract
         community_bonding_contract: Option<String>,
+        /// penalty config for unlocking lockups before expiration
+        early_unlock_penalty: Option<Box<EarlyUnlockPenalty>>,
+        /// bbro boost config for bro rewards weighting
+        boost: Option<Box<BoostParams>>,
+        /// instant unstake config
+        instant_unstake: Option<Box<InstantUnstake>>,
+        /// max amount of history entries kept per staker
+        staker_history_limit: Option<u64>,
     },
     UpdateStakerLockups {
         stakers: Vec<String>,
     },
     /// ## Description
+    /// Converts deprecated lockups of the next batch of stakers to epoch lockups
+    /// and stores their info using current lockups schema.
+    /// Progress is saved, so every call continues after the last migrated staker
+    MigrateLockupsBatch {
+        /// amount of stakers to migrate
+        limit: Option<u32>,
+    },
+    /// ## Description
     /// Creates an offer for a new owner.
     /// The validity period of the offer is set in the `expires_in_blocks` variable
     /// ## Executor
@@ -126,7 +302,7 @@ pub enum ExecuteMsg {
 #[serde(rename_all = "snake_case")]
 pub enum Cw20HookMsg {
     /// ## Description
-    /// Distributes received reward
+    /// Distributes received reward, reward is streamed linearly over the next epoch
     DistributeReward {
         /// last rewards distribution block
         distributed_at_block: u64,
@@ -148,6 +324,9 @@ pub enum Cw20HookMsg {
         /// how many epochs specified amount will be locked
         epochs_locked: u64,
     },
+    /// ## Description
+    /// Distributes received tokens of registered reward asset to stakers
+    FundRewardAsset {},
 }
 
 /// ## StakeType
@@ -167,6 +346,87 @@ pub enum StakeType {
     },
 }
 
+/// ## EarlyUnlockPenaltyType
+/// This structure describes the way early unlock penalty is paid.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EarlyUnlockPenaltyType {
+    /// ## Description
+    /// Penalty is paid by burning part of bbro premium reward received for the lockup
+    BurnBbro {},
+    /// ## Description
+    /// Penalty is paid in BRO tokens which are sent to the rewards pool
+    BroFee {},
+}
+
+/// ## EarlyUnlockPenalty
+/// This structure describes the penalty curve for unlocking lockups before expiration.
+/// Penalty rate decreases linearly from `max_rate` right after locking
+/// to `min_rate` at lockup expiration.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EarlyUnlockPenalty {
+    /// the way penalty is paid
+    pub penalty_type: EarlyUnlockPenaltyType,
+    /// penalty rate at lockup expiration
+    pub min_rate: Decimal,
+    /// penalty rate right after locking
+    pub max_rate: Decimal,
+}
+
+/// ## UnstakeType
+/// This structure describes the unstake type.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UnstakeType {
+    /// ## Description
+    /// Unstaked amount is claimable after passing the unstaking period
+    Delayed {},
+    /// ## Description
+    /// Unstaked amount is sent immediately, staker pays instant unstake fee
+    Instant {},
+}
+
+impl Default for UnstakeType {
+    fn default() -> Self {
+        UnstakeType::Delayed {}
+    }
+}
+
+/// ## InstantUnstakeFeeDestination
+/// This structure describes where instant unstake fee is sent.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum InstantUnstakeFeeDestination {
+    /// ## Description
+    /// Fee is sent to the rewards pool
+    RewardsPool {},
+    /// ## Description
+    /// Fee is distributed to remaining stakers like regular bro reward
+    Stakers {},
+}
+
+/// ## InstantUnstake
+/// This structure describes the instant unstake settings.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantUnstake {
+    /// share of unstaked amount taken as a fee
+    pub fee_rate: Decimal,
+    /// where fee is sent
+    pub fee_destination: InstantUnstakeFeeDestination,
+}
+
+/// ## BoostParams
+/// This structure describes the bbro boost settings.
+/// Staker's reward weight is multiplied by value from 1 up to `max_boost`
+/// depending on staker's share of bbro supply compared to staker's share of total stake.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BoostParams {
+    /// bbro token address
+    pub bbro_token: String,
+    /// max reward weight multiplier
+    pub max_boost: Decimal,
+}
+
 /// ## QueryMsg
 /// This structure describes the query messages of the contract
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -196,6 +456,104 @@ pub enum QueryMsg {
         limit: Option<u32>,
     },
     /// ## Description
+    /// Returns a paginated list of stakers info in the [`StakersResponse`] object
+    Stakers {
+        /// staker address to start reading after
+        start_after: Option<String>,
+        /// max amount of stakers to return
+        limit: Option<u32>,
+        /// order of reading
+        order_by: Option<OrderBy>,
+    },
+    /// ## Description
+    /// Returns staker rewards and stake amounts simulated at specified block
+    /// in the [`SimulateRewardsResponse`] object
+    SimulateRewards {
+        /// staker address
+        staker: String,
+        /// block at which rewards are simulated, current block is used if not specified
+        at_block: Option<u64>,
+    },
+    /// ## Description
+    /// Returns premium bbro reward for locking specified amount
+    /// in the [`SimulateLockupResponse`] object
+    SimulateLockup {
+        /// amount to lock
+        amount: Uint128,
+        /// amount of epochs to lock for
+        epochs_locked: u64,
+    },
+    /// ## Description
+    /// Returns staker's boost multiplier and reward weight in the [`BoostInfoResponse`] object
+    BoostInfo {
+        /// staker address
+        staker: String,
+    },
+    /// ## Description
+    /// Returns reward recipient and claim operators of staker in the [`ClaimPermissionsResponse`] object
+    ClaimPermissions {
+        /// staker address
+        staker: String,
+    },
+    /// ## Description
+    /// Returns reward distribution snapshots in ascending order of distribution block
+    /// in the [`RewardHistoryResponse`] object
+    RewardHistory {
+        /// distribution block to start reading after
+        start_after: Option<u64>,
+        /// max amount of snapshots to return
+        limit: Option<u32>,
+    },
+    /// ## Description
+    /// Returns balance-changing actions of staker in ascending order of entry id
+    /// in the [`StakerHistoryResponse`] object
+    StakerHistory {
+        /// staker address
+        staker: String,
+        /// entry id to start reading after
+        start_after: Option<u64>,
+        /// max amount of entries to return
+        limit: Option<u32>,
+    },
+    /// ## Description
+    /// Returns annual percentage rate estimated using distributions within specified amount
+    /// of last epochs in the [`EstimatedAprResponse`] object
+    EstimatedApr {
+        /// amount of last epochs to use for estimation
+        window_epochs: u64,
+    },
+    /// ## Description
+    /// Returns aggregated lockup statistics in the [`LockupStatsResponse`] object
+    LockupStats {},
+    /// ## Description
+    /// Returns registered reward assets in the [`RewardAssetsResponse`] object
+    RewardAssets {},
+    /// ## Description
+    /// Returns staker's pending amounts per reward asset in the [`PendingRewardsResponse`] object
+    PendingRewards {
+        /// staker address
+        staker: String,
+    },
+    /// ## Description
+    /// Returns voting power of address at the beginning of specified block
+    /// in the [`VotingPowerResponse`] object
+    VotingPower {
+        /// address to query voting power for
+        address: String,
+        /// block at which voting power is queried, current voting power is returned if not specified
+        at_block: Option<u64>,
+    },
+    /// ## Description
+    /// Returns total voting power at the beginning of specified block
+    /// in the [`TotalVotingPowerResponse`] object
+    TotalVotingPower {
+        /// block at which total voting power is queried, current voting power is returned if not specified
+        at_block: Option<u64>,
+    },
+    /// ## Description
+    /// Returns progress of migration to epoch lockups in the [`LockupsMigrationProgressResponse`] object
+    LockupsMigrationProgress {},
+    /// ## Description
     /// Returns information about created ownership proposal in the [`OwnershipProposalResponse`] object
     /// otherwise returns not-found error
     OwnershipProposal {},
@@ -218,6 +576,8 @@ pub struct ConfigResponse {
     pub owner: String,
     /// defines either contract paused or not
     pub paused: bool,
+    /// defines whether emergency mode is enabled or not
+    pub emergency_mode: bool,
     /// bro token address
     pub bro_token: String,
     /// rewards pool address
@@ -230,12 +590,21 @@ pub struct ConfigResponse {
     /// if value is set to none
     /// than option to stake from community bonding contract is disabled
     pub community_bonding_contract: Option<String>,
+    /// contracts allowed to lock bond payouts of bonders
+    /// in addition to community bonding contract
+    pub bond_lock_contracts: Vec<String>,
     /// vesting period for withdrawal
     pub unstake_period_blocks: u64,
     /// minimum staking amount
     pub min_staking_amount: Uint128,
     /// lockup config
     pub lockup_config: LockupConfigResponse,
+    /// bbro boost config
+    pub boost: Option<BoostParams>,
+    /// instant unstake config
+    pub instant_unstake: Option<InstantUnstake>,
+    /// max amount of history entries kept per staker
+    pub staker_history_limit: u64,
 }
 
 /// ## LockupConfigResponse
@@ -252,6 +621,8 @@ pub struct LockupConfigResponse {
     pub linear_growth: Decimal,
     /// exponential growth for bbro premium reward calculation
     pub exponential_growth: Decimal,
+    /// penalty config for unlocking lockups before expiration
+    pub early_unlock_penalty: Option<EarlyUnlockPenalty>,
 }
 
 /// ## StateResponse
@@ -262,8 +633,12 @@ pub struct StateResponse {
     pub global_reward_index: Decimal,
     /// total amount of staked BRO tokens by all stakers
     pub total_stake_amount: Uint128,
-    /// last rewards distribution block
+    /// block up to which received rewards are streamed into global reward index
     pub last_distribution_block: u64,
+    /// amount of BRO tokens streamed per block
+    pub reward_rate: Decimal,
+    /// block at which current reward stream ends
+    pub period_finish: u64,
 }
 
 /// ## StakerInfoResponse
@@ -286,6 +661,8 @@ pub struct StakerInfoResponse {
     pub last_balance_update: u64,
     /// amounts locked for specified amount of epochs
     pub lockups: Vec<LockupInfoResponse>,
+    /// defines whether pending bro rewards are restaked automatically or not
+    pub auto_compound: bool,
 }
 
 /// ## Description
@@ -300,6 +677,222 @@ pub struct LockupInfoResponse {
     pub epochs_locked: u64,
 }
 
+/// ## SimulateRewardsResponse
+/// This structure describes the fields for simulate rewards response message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulateRewardsResponse {
+    /// staker address
+    pub staker: String,
+    /// block at which rewards are simulated
+    pub at_block: u64,
+    /// amount of pending bro rewards of staker
+    pub pending_bro_reward: Uint128,
+    /// amount of pending bbro rewards of staker
+    pub pending_bbro_reward: Uint128,
+    /// amount of unlocked BRO tokens staked by staker
+    pub unlocked_stake_amount: Uint128,
+    /// amount of locked BRO tokens staked by staker
+    pub locked_stake_amount: Uint128,
+    /// amount of expired lockups which are moved to unlocked stake amount
+    pub expired_lockups_amount: Uint128,
+}
+
+/// ## SimulateLockupResponse
+/// This structure describes the fields for simulate lockup response message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulateLockupResponse {
+    /// amount of premium bbro reward minted for lockup
+    pub bbro_premium_lockup_reward: Uint128,
+}
+
+/// ## BoostInfoResponse
+/// This structure describes the fields for boost info response message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BoostInfoResponse {
+    /// staker address
+    pub staker: String,
+    /// staker's reward weight multiplier
+    pub boost_multiplier: Decimal,
+    /// staker's reward weight
+    pub working_balance: Uint128,
+    /// total reward weight of all stakers
+    pub total_working_balance: Uint128,
+}
+
+/// ## ClaimPermissionsResponse
+/// This structure describes the fields for claim permissions response message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClaimPermissionsResponse {
+    /// address which receives staker's rewards and withdrawals
+    pub reward_recipient: String,
+    /// a list of operators allowed to claim on behalf of staker
+    pub operators: Vec<ClaimOperatorResponse>,
+}
+
+/// ## Description
+/// This structure describes the fields for claim operator response message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClaimOperatorResponse {
+    /// operator address
+    pub operator: String,
+    /// permission expiration
+    pub expires: Expiration,
+}
+
+/// ## RewardHistoryResponse
+/// This structure describes the fields for reward history response message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardHistoryResponse {
+    /// a list of reward distribution snapshots
+    pub snapshots: Vec<RewardSnapshotResponse>,
+}
+
+/// ## Description
+/// This structure describes the fields for reward snapshot response message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardSnapshotResponse {
+    /// block at which reward was distributed
+    pub distributed_at_block: u64,
+    /// global reward index after distribution
+    pub global_reward_index: Decimal,
+    /// total amount of staked BRO tokens at distribution
+    pub total_stake_amount: Uint128,
+    /// amount of distributed BRO tokens
+    pub distributed_amount: Uint128,
+}
+
+/// ## StakerHistoryResponse
+/// This structure describes the fields for staker history response message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakerHistoryResponse {
+    /// a list of staker history entries
+    pub history: Vec<StakerHistoryEntryResponse>,
+}
+
+/// ## Description
+/// This structure describes the fields for staker history entry response message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakerHistoryEntryResponse {
+    /// entry id
+    pub id: u64,
+    /// block at which action was performed
+    pub block: u64,
+    /// performed action
+    pub action: String,
+    /// amount of BRO tokens affected by action
+    pub amount: Uint128,
+    /// amount of epochs locked by action
+    pub epochs_locked: Option<u64>,
+}
+
+/// ## EstimatedAprResponse
+/// This structure describes the fields for estimated apr response message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EstimatedAprResponse {
+    /// estimated annual percentage rate
+    pub apr: Decimal,
+    /// amount of BRO tokens distributed within window
+    pub distributed_amount: Uint128,
+    /// amount of blocks used for estimation
+    pub window_blocks: u64,
+}
+
+/// ## StakersResponse
+/// This structure describes the fields for stakers response message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakersResponse {
+    /// a list of stakers info
+    pub stakers: Vec<StakerInfoResponse>,
+}
+
+/// ## RewardAssetsResponse
+/// This structure describes the fields for reward assets response message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardAssetsResponse {
+    /// list of registered reward assets
+    pub reward_assets: Vec<RewardAssetResponse>,
+}
+
+/// ## Description
+/// This structure describes the fields for reward asset response object.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardAssetResponse {
+    /// reward asset info
+    pub asset_info: AssetInfo,
+    /// global reward index of reward asset
+    pub global_reward_index: Decimal,
+    /// total distributed amount of reward asset
+    pub total_distributed_amount: Uint128,
+}
+
+/// ## PendingRewardsResponse
+/// This structure describes the fields for pending rewards response message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingRewardsResponse {
+    /// staker address
+    pub staker: String,
+    /// pending amounts of bro reward and all registered reward assets
+    pub rewards: Vec<Asset>,
+}
+
+/// ## VotingPowerResponse
+/// This structure describes the fields for voting power response message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VotingPowerResponse {
+    /// address of voter
+    pub address: String,
+    /// block at which voting power is calculated
+    pub at_block: u64,
+    /// staked amount with lockups weighted by their remaining lockup period
+    pub voting_power: Uint128,
+}
+
+/// ## TotalVotingPowerResponse
+/// This structure describes the fields for total voting power response message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TotalVotingPowerResponse {
+    /// block at which total voting power is calculated
+    pub at_block: u64,
+    /// sum of voting powers of all stakers
+    pub total_voting_power: Uint128,
+}
+
+/// ## LockupsMigrationProgressResponse
+/// This structure describes the fields for lockups migration progress response message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LockupsMigrationProgressResponse {
+    /// last migrated staker, next batch starts after it
+    pub last_migrated_staker: Option<String>,
+    /// amount of migrated stakers
+    pub migrated_stakers: u64,
+    /// amount of converted deprecated lockups
+    pub converted_lockups: u64,
+    /// defines whether all stakers are migrated or not
+    pub completed: bool,
+}
+
+/// ## LockupStatsResponse
+/// This structure describes the fields for lockup stats response message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LockupStatsResponse {
+    /// amount of stakers with non-zero staked amount
+    pub active_stakers: u64,
+    /// total amount of locked BRO tokens by all stakers
+    pub total_locked_amount: Uint128,
+    /// locked amounts grouped by lockup period
+    pub lockups: Vec<LockupStatsItem>,
+}
+
+/// ## Description
+/// This structure describes the fields for lockup stats item.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LockupStatsItem {
+    /// lockup period in epochs
+    pub epochs_locked: u64,
+    /// total amount locked for specified lockup period
+    pub locked_amount: Uint128,
+}
+
 /// ## WithdrawalInfoResponse
 /// This structure describes the fields for withdrawal info response message.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]